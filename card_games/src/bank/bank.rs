@@ -1,27 +1,211 @@
+use std::{fmt::Display, time::SystemTime};
+
+use super::ledger::{Ledger, RoundId, SessionId, Transaction, TransactionKind};
+
+/// A player's bankroll. Every change to the balance is recorded in a
+/// [`Ledger`].
+///
+/// # Example
+/// ```
+/// use card_games::bank::bank::Bank;
+/// let mut bank = Bank::new(100);
+/// let round = bank.start_round();
+/// bank.wager(10).unwrap();
+/// bank.payout(20).unwrap();
+/// assert_eq!(bank.balance(), 110);
+/// assert_eq!(bank.ledger().round_summary(round).net(), 10);
+/// ```
 #[derive(Debug, Clone)]
 pub struct Bank {
     balance: u32,
+    round: RoundId,
+    session: SessionId,
+    ledger: Ledger,
 }
 
 impl Bank {
     pub fn new(balance: u32) -> Self {
-        Self { balance }
+        Self {
+            balance,
+            round: 0,
+            session: 0,
+            ledger: Ledger::new(balance),
+        }
     }
 
     pub fn balance(&self) -> u32 {
         self.balance
     }
 
-    pub fn withdraw(&mut self, amount: u32) -> bool {
-        if self.balance >= amount {
-            self.balance -= amount;
-            true
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    /// The round that new transactions are recorded against.
+    pub fn round(&self) -> RoundId {
+        self.round
+    }
+
+    pub fn session(&self) -> SessionId {
+        self.session
+    }
+
+    /// Begins a new round and returns its id.
+    pub fn start_round(&mut self) -> RoundId {
+        self.round += 1;
+        self.round
+    }
+
+    /// Begins a new session and returns its id.
+    pub fn start_session(&mut self) -> SessionId {
+        self.session += 1;
+        self.session
+    }
+
+    /// Returns `true` if the balance covers `amount`.
+    pub fn can_cover(&self, amount: u32) -> bool {
+        self.balance >= amount
+    }
+
+    /// Takes a stake from the balance.
+    pub fn wager(&mut self, amount: u32) -> Result<u32, BankError> {
+        self.record(TransactionKind::Wager, amount)
+    }
+
+    /// Takes an insurance stake from the balance.
+    pub fn insure(&mut self, amount: u32) -> Result<u32, BankError> {
+        self.record(TransactionKind::Insurance, amount)
+    }
+
+    /// Pays winnings, stake included, into the balance.
+    pub fn payout(&mut self, amount: u32) -> Result<u32, BankError> {
+        self.record(TransactionKind::Payout, amount)
+    }
+
+    /// Returns a stake that neither won nor lost.
+    pub fn refund(&mut self, amount: u32) -> Result<u32, BankError> {
+        self.record(TransactionKind::Refund, amount)
+    }
+
+    /// Adds money from outside the game.
+    pub fn reload(&mut self, amount: u32) -> Result<u32, BankError> {
+        self.record(TransactionKind::Reload, amount)
+    }
+
+    /// Credits `amount`, or as much of it as the balance can hold, and
+    /// returns the amount credited. Settling a round should not fail half
+    /// way through, so games pay out with this rather than stop at
+    /// [`BankError::Overflow`].
+    ///
+    /// # Panics
+    /// Panics if `kind` is not a credit.
+    pub fn credit_capped(&mut self, kind: TransactionKind, amount: u32) -> u32 {
+        assert!(kind.is_credit(), "{} is not a credit", kind);
+        let amount = amount.min(u32::MAX - self.balance);
+        self.record(kind, amount)
+            .expect("a capped credit fits in the balance");
+        amount
+    }
+
+    /// Applies a transaction and returns the new balance.
+    ///
+    /// # Errors
+    /// Debits fail with [`BankError::InsufficientFunds`] if the balance
+    /// cannot cover them, and credits with [`BankError::Overflow`] if the
+    /// balance would overflow. The balance and ledger are left untouched.
+    pub fn record(&mut self, kind: TransactionKind, amount: u32) -> Result<u32, BankError> {
+        self.balance = if kind.is_credit() {
+            self.balance
+                .checked_add(amount)
+                .ok_or(BankError::Overflow {
+                    balance: self.balance,
+                    amount,
+                })?
         } else {
-            false
+            self.balance
+                .checked_sub(amount)
+                .ok_or(BankError::InsufficientFunds {
+                    balance: self.balance,
+                    amount,
+                })?
+        };
+
+        self.ledger.record(Transaction {
+            kind,
+            amount,
+            round: self.round,
+            session: self.session,
+            balance: self.balance,
+            timestamp: SystemTime::now(),
+        });
+        Ok(self.balance)
+    }
+}
+
+/// An error from moving money in or out of a [`Bank`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankError {
+    InsufficientFunds { balance: u32, amount: u32 },
+    Overflow { balance: u32, amount: u32 },
+}
+
+impl Display for BankError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BankError::InsufficientFunds { balance, amount } => {
+                write!(f, "Cannot take {} from a balance of {}", amount, balance)
+            }
+            BankError::Overflow { balance, amount } => {
+                write!(f, "Adding {} to a balance of {} overflows", amount, balance)
+            }
         }
     }
+}
+
+impl std::error::Error for BankError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debits_fail_without_funds() {
+        let mut bank = Bank::new(10);
+        assert_eq!(
+            bank.wager(11),
+            Err(BankError::InsufficientFunds {
+                balance: 10,
+                amount: 11
+            })
+        );
+        assert_eq!(bank.balance(), 10);
+        assert!(bank.ledger().transactions().is_empty());
+    }
+
+    #[test]
+    fn credits_fail_on_overflow() {
+        let mut bank = Bank::new(u32::MAX - 1);
+        assert!(matches!(bank.payout(2), Err(BankError::Overflow { .. })));
+        assert_eq!(bank.payout(1), Ok(u32::MAX));
+        assert_eq!(bank.credit_capped(TransactionKind::Payout, 2), 0);
+        assert_eq!(bank.balance(), u32::MAX);
+    }
+
+    #[test]
+    fn transactions_carry_round_and_session() {
+        let mut bank = Bank::new(100);
+        bank.start_round();
+        bank.wager(10).unwrap();
+        bank.start_session();
+        bank.start_round();
+        bank.insure(5).unwrap();
+        bank.reload(50).unwrap();
 
-    pub fn deposit(&mut self, amount: u32) {
-        self.balance += amount;
+        let transactions = bank.ledger().transactions();
+        assert_eq!(transactions.len(), 3);
+        assert_eq!((transactions[0].round, transactions[0].session), (1, 0));
+        assert_eq!((transactions[1].round, transactions[1].session), (2, 1));
+        assert_eq!(transactions[2].balance, 135);
+        assert_eq!(bank.ledger().net_for_session(1), -5);
     }
 }
@@ -1,4 +1,50 @@
+use super::chips::{Chip, ChipStack};
+
 #[derive(Debug, Clone)]
 pub struct Bet {
     pub amount: u32,
 }
+
+impl Bet {
+    pub fn new(amount: u32) -> Self {
+        Bet { amount }
+    }
+
+    /// Adds a chip to the bet.
+    pub fn add_chip(&mut self, chip: Chip) {
+        self.amount = self.amount.saturating_add(chip.value());
+    }
+
+    /// Takes a chip back off the bet. Returns `false` if the bet is smaller
+    /// than the chip.
+    pub fn remove_chip(&mut self, chip: Chip) -> bool {
+        match self.amount.checked_sub(chip.value()) {
+            Some(amount) => {
+                self.amount = amount;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The bet as the fewest chips.
+    pub fn chips(&self) -> ChipStack {
+        ChipStack::from_amount(self.amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bet_is_built_chip_by_chip() {
+        let mut bet = Bet::new(0);
+        bet.add_chip(Chip::TwentyFive);
+        bet.add_chip(Chip::Five);
+        assert_eq!(bet.amount, 30);
+        assert!(!bet.remove_chip(Chip::Hundred));
+        assert!(bet.remove_chip(Chip::Five));
+        assert_eq!(bet.chips().count(Chip::TwentyFive), 1);
+    }
+}
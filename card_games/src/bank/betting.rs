@@ -0,0 +1,462 @@
+//! Betting progressions.
+//!
+//! A [`BettingSystem`] raises or lowers the stake after each round
+//! depending on whether it was won or lost. None of them changes the
+//! house edge: every bet still loses the same share of what is staked on
+//! average, and a progression only changes how the losses are spread out.
+//! Playing one with automated rounds makes that plain.
+
+use std::fmt::Display;
+
+use super::{bank::Bank, ledger::RoundSummary};
+
+/// Whether a round gained or lost money.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Push,
+}
+
+impl Outcome {
+    /// The outcome of a settled round, by its net.
+    pub fn from_summary(summary: &RoundSummary) -> Self {
+        match summary.net() {
+            net if net > 0 => Outcome::Win,
+            net if net < 0 => Outcome::Loss,
+            _ => Outcome::Push,
+        }
+    }
+}
+
+/// Chooses each wager from the results of the rounds before it.
+///
+/// # Example
+/// ```
+/// use card_games::bank::bank::Bank;
+/// use card_games::bank::betting::{BettingSystem, Martingale, Outcome};
+/// let bank = Bank::new(1_000);
+/// let mut system = Martingale::new(10);
+/// system.record(Outcome::Loss);
+/// system.record(Outcome::Loss);
+/// assert_eq!(system.next_bet(&bank), 40);
+/// system.record(Outcome::Win);
+/// assert_eq!(system.next_bet(&bank), 10);
+/// ```
+pub trait BettingSystem {
+    fn name(&self) -> &'static str;
+
+    /// Takes the result of a round into account.
+    fn record(&mut self, outcome: Outcome);
+
+    /// The stake the system wants next, before any cap.
+    fn stake(&self) -> u32;
+
+    /// Starts the progression over.
+    fn reset(&mut self);
+
+    /// The next wager, capped at what the bank holds.
+    fn next_bet(&self, bank: &Bank) -> u32 {
+        self.stake().min(bank.balance())
+    }
+
+    /// Records the bank's latest round from its ledger. Rounds without a
+    /// wager are ignored.
+    fn record_round(&mut self, bank: &Bank) {
+        let summary = bank.ledger().round_summary(bank.round());
+        if summary.wagered > 0 {
+            self.record(Outcome::from_summary(&summary));
+        }
+    }
+}
+
+/// The same bet every round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flat {
+    unit: u32,
+}
+
+impl Flat {
+    pub fn new(unit: u32) -> Self {
+        Flat { unit }
+    }
+}
+
+impl BettingSystem for Flat {
+    fn name(&self) -> &'static str {
+        "Flat"
+    }
+
+    fn record(&mut self, _outcome: Outcome) {}
+
+    fn stake(&self) -> u32 {
+        self.unit
+    }
+
+    fn reset(&mut self) {}
+}
+
+/// Doubles the bet after every loss and drops back to one unit after a
+/// win, so a single win recovers every loss in the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Martingale {
+    unit: u32,
+    losses: u32,
+}
+
+impl Martingale {
+    pub fn new(unit: u32) -> Self {
+        Martingale { unit, losses: 0 }
+    }
+}
+
+impl BettingSystem for Martingale {
+    fn name(&self) -> &'static str {
+        "Martingale"
+    }
+
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.losses = 0,
+            Outcome::Loss => self.losses = self.losses.saturating_add(1),
+            Outcome::Push => {}
+        }
+    }
+
+    fn stake(&self) -> u32 {
+        doubled(self.unit, self.losses)
+    }
+
+    fn reset(&mut self) {
+        self.losses = 0;
+    }
+}
+
+/// Doubles the bet after every win and drops back to one unit after a
+/// loss or once the run reaches its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paroli {
+    unit: u32,
+    /// Wins in a row before taking the profit; `None` lets the run go on.
+    target: Option<u32>,
+    wins: u32,
+}
+
+impl Paroli {
+    /// The classic Paroli, which banks the profit after three wins.
+    pub fn new(unit: u32) -> Self {
+        Paroli {
+            unit,
+            target: Some(3),
+            wins: 0,
+        }
+    }
+
+    /// The reverse Martingale, which keeps doubling until it loses.
+    pub fn reverse_martingale(unit: u32) -> Self {
+        Paroli {
+            unit,
+            target: None,
+            wins: 0,
+        }
+    }
+
+    /// Banks the profit after `wins` wins in a row.
+    pub fn with_target(mut self, wins: u32) -> Self {
+        self.target = Some(wins.max(1));
+        self
+    }
+}
+
+impl BettingSystem for Paroli {
+    fn name(&self) -> &'static str {
+        match self.target {
+            Some(_) => "Paroli",
+            None => "Reverse Martingale",
+        }
+    }
+
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => {
+                self.wins += 1;
+                if Some(self.wins) == self.target {
+                    self.wins = 0;
+                }
+            }
+            Outcome::Loss => self.wins = 0,
+            Outcome::Push => {}
+        }
+    }
+
+    fn stake(&self) -> u32 {
+        doubled(self.unit, self.wins)
+    }
+
+    fn reset(&mut self) {
+        self.wins = 0;
+    }
+}
+
+/// Adds a unit after a loss and takes one off after a win, never going
+/// below one unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DAlembert {
+    unit: u32,
+    units: u32,
+}
+
+impl DAlembert {
+    pub fn new(unit: u32) -> Self {
+        DAlembert { unit, units: 1 }
+    }
+}
+
+impl BettingSystem for DAlembert {
+    fn name(&self) -> &'static str {
+        "D'Alembert"
+    }
+
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.units = self.units.saturating_sub(1).max(1),
+            Outcome::Loss => self.units = self.units.saturating_add(1),
+            Outcome::Push => {}
+        }
+    }
+
+    fn stake(&self) -> u32 {
+        self.unit.saturating_mul(self.units)
+    }
+
+    fn reset(&mut self) {
+        self.units = 1;
+    }
+}
+
+/// Walks the Fibonacci sequence: one step on after a loss, two steps back
+/// after a win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fibonacci {
+    unit: u32,
+    step: u32,
+}
+
+impl Fibonacci {
+    pub fn new(unit: u32) -> Self {
+        Fibonacci { unit, step: 0 }
+    }
+}
+
+impl BettingSystem for Fibonacci {
+    fn name(&self) -> &'static str {
+        "Fibonacci"
+    }
+
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.step = self.step.saturating_sub(2),
+            Outcome::Loss => self.step = self.step.saturating_add(1),
+            Outcome::Push => {}
+        }
+    }
+
+    fn stake(&self) -> u32 {
+        let (mut a, mut b) = (1u32, 1u32);
+        for _ in 0..self.step {
+            (a, b) = (b, a.saturating_add(b));
+        }
+        self.unit.saturating_mul(a)
+    }
+
+    fn reset(&mut self) {
+        self.step = 0;
+    }
+}
+
+/// Bets 1, 3, 2 and 6 units on four wins in a row, starting over after
+/// a loss or once the four are won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OneThreeTwoSix {
+    unit: u32,
+    step: usize,
+}
+
+impl OneThreeTwoSix {
+    const UNITS: [u32; 4] = [1, 3, 2, 6];
+
+    pub fn new(unit: u32) -> Self {
+        OneThreeTwoSix { unit, step: 0 }
+    }
+}
+
+impl BettingSystem for OneThreeTwoSix {
+    fn name(&self) -> &'static str {
+        "1-3-2-6"
+    }
+
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.step = (self.step + 1) % Self::UNITS.len(),
+            Outcome::Loss => self.step = 0,
+            Outcome::Push => {}
+        }
+    }
+
+    fn stake(&self) -> u32 {
+        self.unit.saturating_mul(Self::UNITS[self.step])
+    }
+
+    fn reset(&mut self) {
+        self.step = 0;
+    }
+}
+
+/// `unit` doubled `times` times, stopping at `u32::MAX`.
+fn doubled(unit: u32, times: u32) -> u32 {
+    1u32.checked_shl(times)
+        .map_or(u32::MAX, |factor| unit.saturating_mul(factor))
+}
+
+/// The betting systems on offer, for choosing one by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemKind {
+    Flat,
+    Martingale,
+    Paroli,
+    ReverseMartingale,
+    DAlembert,
+    Fibonacci,
+    OneThreeTwoSix,
+}
+
+impl SystemKind {
+    pub const ALL: [SystemKind; 7] = [
+        SystemKind::Flat,
+        SystemKind::Martingale,
+        SystemKind::Paroli,
+        SystemKind::ReverseMartingale,
+        SystemKind::DAlembert,
+        SystemKind::Fibonacci,
+        SystemKind::OneThreeTwoSix,
+    ];
+
+    /// A fresh system of this kind betting `unit` a unit.
+    pub fn build(&self, unit: u32) -> Box<dyn BettingSystem> {
+        match self {
+            SystemKind::Flat => Box::new(Flat::new(unit)),
+            SystemKind::Martingale => Box::new(Martingale::new(unit)),
+            SystemKind::Paroli => Box::new(Paroli::new(unit)),
+            SystemKind::ReverseMartingale => Box::new(Paroli::reverse_martingale(unit)),
+            SystemKind::DAlembert => Box::new(DAlembert::new(unit)),
+            SystemKind::Fibonacci => Box::new(Fibonacci::new(unit)),
+            SystemKind::OneThreeTwoSix => Box::new(OneThreeTwoSix::new(unit)),
+        }
+    }
+
+    /// The next kind in [`SystemKind::ALL`], wrapping around.
+    pub fn next(&self) -> SystemKind {
+        let index = Self::ALL.iter().position(|k| k == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl Display for SystemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.build(1).name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Outcome::{Loss, Push, Win};
+
+    fn stakes(system: &mut dyn BettingSystem, outcomes: &[Outcome]) -> Vec<u32> {
+        let mut stakes = vec![system.stake()];
+        for outcome in outcomes {
+            system.record(*outcome);
+            stakes.push(system.stake());
+        }
+        stakes
+    }
+
+    #[test]
+    fn martingale_doubles_after_losses() {
+        let mut system = Martingale::new(5);
+        assert_eq!(
+            stakes(&mut system, &[Loss, Loss, Push, Loss, Win]),
+            vec![5, 10, 20, 20, 40, 5]
+        );
+        assert_eq!(doubled(5, 40), u32::MAX);
+    }
+
+    #[test]
+    fn paroli_banks_after_three_wins() {
+        let mut system = Paroli::new(5);
+        assert_eq!(
+            stakes(&mut system, &[Win, Win, Win, Win, Loss]),
+            vec![5, 10, 20, 5, 10, 5]
+        );
+        let mut reverse = Paroli::reverse_martingale(5);
+        assert_eq!(
+            stakes(&mut reverse, &[Win, Win, Win, Win]),
+            vec![5, 10, 20, 40, 80]
+        );
+        assert_eq!(reverse.name(), "Reverse Martingale");
+    }
+
+    #[test]
+    fn dalembert_moves_a_unit_at_a_time() {
+        let mut system = DAlembert::new(10);
+        assert_eq!(
+            stakes(&mut system, &[Win, Loss, Loss, Win, Win]),
+            vec![10, 10, 20, 30, 20, 10]
+        );
+    }
+
+    #[test]
+    fn fibonacci_steps_on_and_back() {
+        let mut system = Fibonacci::new(10);
+        assert_eq!(
+            stakes(&mut system, &[Loss, Loss, Loss, Loss, Win, Win]),
+            vec![10, 10, 20, 30, 50, 20, 10]
+        );
+    }
+
+    #[test]
+    fn one_three_two_six_restarts_after_four_wins_or_a_loss() {
+        let mut system = OneThreeTwoSix::new(10);
+        assert_eq!(
+            stakes(&mut system, &[Win, Win, Win, Win, Win, Loss]),
+            vec![10, 30, 20, 60, 10, 30, 10]
+        );
+    }
+
+    #[test]
+    fn bets_are_capped_by_the_bank_and_read_from_the_ledger() {
+        let mut bank = Bank::new(100);
+        let mut system = Martingale::new(40);
+        bank.start_round();
+        bank.wager(40).unwrap();
+        system.record_round(&bank);
+        assert_eq!(system.stake(), 80);
+        assert_eq!(system.next_bet(&bank), 60);
+
+        bank.start_round();
+        bank.wager(60).unwrap();
+        bank.payout(120).unwrap();
+        system.record_round(&bank);
+        assert_eq!(system.next_bet(&bank), 40);
+    }
+
+    #[test]
+    fn kinds_cycle_through_every_system() {
+        let mut kind = SystemKind::Flat;
+        for _ in 0..SystemKind::ALL.len() {
+            kind = kind.next();
+        }
+        assert_eq!(kind, SystemKind::Flat);
+        assert_eq!(SystemKind::OneThreeTwoSix.to_string(), "1-3-2-6");
+    }
+}
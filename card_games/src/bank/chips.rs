@@ -0,0 +1,312 @@
+//! Casino chips, chip stacks and payout rounding.
+//!
+//! Amounts are still plain `u32` dollars; this module turns them into
+//! stacks of physical chips and decides how odd payouts (such as 3:2 on a
+//! $5 bet) round to an amount the table can actually pay.
+
+use std::fmt::Display;
+
+/// A chip denomination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Chip {
+    One,
+    Five,
+    TwentyFive,
+    Hundred,
+    FiveHundred,
+    Thousand,
+}
+
+impl Chip {
+    /// Every denomination, smallest first.
+    pub const ALL: [Chip; 6] = [
+        Chip::One,
+        Chip::Five,
+        Chip::TwentyFive,
+        Chip::Hundred,
+        Chip::FiveHundred,
+        Chip::Thousand,
+    ];
+
+    pub fn value(&self) -> u32 {
+        match self {
+            Chip::One => 1,
+            Chip::Five => 5,
+            Chip::TwentyFive => 25,
+            Chip::Hundred => 100,
+            Chip::FiveHundred => 500,
+            Chip::Thousand => 1_000,
+        }
+    }
+
+    /// The usual colour of the chip in an American casino.
+    pub fn color(&self) -> &'static str {
+        match self {
+            Chip::One => "white",
+            Chip::Five => "red",
+            Chip::TwentyFive => "green",
+            Chip::Hundred => "black",
+            Chip::FiveHundred => "purple",
+            Chip::Thousand => "orange",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl Display for Chip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${}", self.value())
+    }
+}
+
+/// A pile of chips, counted by denomination.
+///
+/// # Example
+/// ```
+/// use card_games::bank::chips::{Chip, ChipStack};
+/// let stack = ChipStack::from_amount(137);
+/// assert_eq!(stack.count(Chip::Hundred), 1);
+/// assert_eq!(stack.count(Chip::TwentyFive), 1);
+/// assert_eq!(stack.count(Chip::Five), 2);
+/// assert_eq!(stack.count(Chip::One), 2);
+/// assert_eq!(stack.total(), 137);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ChipStack {
+    counts: [u32; 6],
+}
+
+impl ChipStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes change for `amount` with the fewest chips.
+    pub fn from_amount(amount: u32) -> Self {
+        Self::make_change(amount, &Chip::ALL).expect("$1 chips make change for any amount")
+    }
+
+    /// Makes change for `amount` with the fewest chips, using only the
+    /// given denominations. Returns `None` if the amount cannot be made.
+    ///
+    /// Each denomination divides every larger one, so taking the largest
+    /// chip that fits is always optimal.
+    pub fn make_change(amount: u32, denominations: &[Chip]) -> Option<Self> {
+        let mut denominations = denominations.to_vec();
+        denominations.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut stack = ChipStack::new();
+        let mut remaining = amount;
+        for chip in denominations {
+            stack.counts[chip.index()] += remaining / chip.value();
+            remaining %= chip.value();
+        }
+        (remaining == 0).then_some(stack)
+    }
+
+    pub fn add(&mut self, chip: Chip) {
+        self.counts[chip.index()] += 1;
+    }
+
+    /// Takes one chip off the stack. Returns `false` if there was none.
+    pub fn remove(&mut self, chip: Chip) -> bool {
+        let count = &mut self.counts[chip.index()];
+        if *count == 0 {
+            return false;
+        }
+        *count -= 1;
+        true
+    }
+
+    pub fn count(&self, chip: Chip) -> u32 {
+        self.counts[chip.index()]
+    }
+
+    /// The value of the whole stack.
+    pub fn total(&self) -> u64 {
+        Chip::ALL
+            .iter()
+            .map(|chip| chip.value() as u64 * self.count(*chip) as u64)
+            .sum()
+    }
+
+    /// Number of chips in the stack.
+    pub fn len(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Each denomination present with its count, largest first, ready to
+    /// draw as a stack.
+    pub fn denominations(&self) -> impl Iterator<Item = (Chip, u32)> + '_ {
+        Chip::ALL
+            .iter()
+            .rev()
+            .map(|chip| (*chip, self.count(*chip)))
+            .filter(|(_, count)| *count > 0)
+    }
+}
+
+impl Display for ChipStack {
+    /// Formats the stack as `2×$100 1×$5`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .denominations()
+            .map(|(chip, count)| format!("{}×{}", count, chip))
+            .collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// How a payout that is not a whole number of units is settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Pay the next smaller amount; the house keeps the odd change.
+    Down,
+    /// Pay the next larger amount.
+    Up,
+    /// Pay the closest amount, rounding halves up.
+    Nearest,
+}
+
+impl Rounding {
+    pub const ALL: [Rounding; 3] = [Rounding::Down, Rounding::Up, Rounding::Nearest];
+}
+
+impl Display for Rounding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Rounding::Down => "round down",
+            Rounding::Up => "round up",
+            Rounding::Nearest => "round to nearest",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The smallest amount a table pays and how odd payouts round to it.
+///
+/// # Example
+/// ```
+/// use card_games::bank::chips::{PayoutRule, Rounding};
+/// // 3:2 on a $5 bet is $7.50.
+/// assert_eq!(PayoutRule::default().winnings(5, 3, 2), 7);
+/// assert_eq!(PayoutRule::new(1, Rounding::Up).winnings(5, 3, 2), 8);
+/// assert_eq!(PayoutRule::new(5, Rounding::Nearest).winnings(5, 3, 2), 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayoutRule {
+    unit: u32,
+    rounding: Rounding,
+}
+
+impl PayoutRule {
+    /// A rule paying multiples of `unit` (at least 1).
+    pub fn new(unit: u32, rounding: Rounding) -> Self {
+        PayoutRule {
+            unit: unit.max(1),
+            rounding,
+        }
+    }
+
+    pub fn unit(&self) -> u32 {
+        self.unit
+    }
+
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Winnings, excluding the returned stake, for `bet` at odds of
+    /// `numerator:denominator`. A win always pays at least one unit, even
+    /// when the exact amount rounds down to nothing.
+    pub fn winnings(&self, bet: u32, numerator: u32, denominator: u32) -> u32 {
+        let exact = bet as u64 * numerator as u64;
+        let per_unit = denominator.max(1) as u64 * self.unit as u64;
+        let units = match self.rounding {
+            Rounding::Down => exact / per_unit,
+            Rounding::Up => exact.div_ceil(per_unit),
+            Rounding::Nearest => (2 * exact + per_unit) / (2 * per_unit),
+        };
+        let units = match exact {
+            0 => 0,
+            _ => units.max(1),
+        };
+        (units * self.unit as u64).min(u32::MAX as u64) as u32
+    }
+}
+
+impl Default for PayoutRule {
+    /// Whole dollars, rounding down.
+    fn default() -> Self {
+        Self::new(1, Rounding::Down)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_uses_the_fewest_chips() {
+        let stack = ChipStack::from_amount(1_631);
+        assert_eq!(stack.to_string(), "1×$1000 1×$500 1×$100 1×$25 1×$5 1×$1");
+        assert_eq!(stack.len(), 6);
+        assert_eq!(ChipStack::from_amount(0), ChipStack::new());
+    }
+
+    #[test]
+    fn change_with_limited_denominations() {
+        let stack = ChipStack::make_change(150, &[Chip::TwentyFive, Chip::Hundred]).unwrap();
+        assert_eq!(stack.count(Chip::Hundred), 1);
+        assert_eq!(stack.count(Chip::TwentyFive), 2);
+        assert_eq!(ChipStack::make_change(7, &[Chip::Five]), None);
+    }
+
+    #[test]
+    fn build_a_stack_chip_by_chip() {
+        let mut stack = ChipStack::new();
+        stack.add(Chip::TwentyFive);
+        stack.add(Chip::Five);
+        stack.add(Chip::Five);
+        assert_eq!(stack.total(), 35);
+
+        assert!(stack.remove(Chip::Five));
+        assert!(!stack.remove(Chip::Hundred));
+        assert_eq!(stack.total(), 30);
+        assert_eq!(
+            stack.denominations().collect::<Vec<_>>(),
+            [(Chip::TwentyFive, 1), (Chip::Five, 1)]
+        );
+    }
+
+    #[test]
+    fn payouts_round_to_the_table_unit() {
+        let down = PayoutRule::default();
+        assert_eq!(down.winnings(10, 3, 2), 15);
+        assert_eq!(down.winnings(15, 3, 2), 22);
+
+        let nearest = PayoutRule::new(1, Rounding::Nearest);
+        assert_eq!(nearest.winnings(15, 3, 2), 23);
+        assert_eq!(nearest.winnings(20, 6, 5), 24);
+
+        let fives = PayoutRule::new(5, Rounding::Down);
+        assert_eq!(fives.winnings(25, 3, 2), 35);
+        assert_eq!(PayoutRule::new(0, Rounding::Up).unit(), 1);
+    }
+
+    #[test]
+    fn a_win_pays_at_least_one_unit() {
+        let quarters = PayoutRule::new(25, Rounding::Down);
+        assert_eq!(quarters.winnings(10, 3, 2), 25);
+        assert_eq!(PayoutRule::new(25, Rounding::Nearest).winnings(5, 1, 1), 25);
+        assert_eq!(quarters.winnings(0, 3, 2), 0);
+        assert_eq!(quarters.winnings(10, 0, 1), 0);
+    }
+}
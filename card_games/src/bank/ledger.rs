@@ -0,0 +1,272 @@
+//! A record of every movement of money through a [`Bank`](super::bank::Bank).
+//!
+//! Each transaction carries its kind, the round and session it belongs to,
+//! the balance it left behind and when it happened, so a statement can be
+//! rebuilt and each round audited after the fact.
+
+use std::{fmt::Display, time::SystemTime};
+
+/// Identifies one round (a blackjack hand, a baccarat coup).
+pub type RoundId = u64;
+
+/// Identifies one sitting at the table.
+pub type SessionId = u32;
+
+/// What a transaction was for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionKind {
+    /// A stake placed on the table.
+    Wager,
+    /// Winnings returned to the player, stake included.
+    Payout,
+    /// A stake handed back without a win or a loss, such as a push.
+    Refund,
+    /// An insurance side bet.
+    Insurance,
+    /// Money added to the bankroll from outside the game.
+    Reload,
+}
+
+impl TransactionKind {
+    /// Returns `true` for kinds that add to the balance.
+    pub fn is_credit(&self) -> bool {
+        matches!(
+            self,
+            TransactionKind::Payout | TransactionKind::Refund | TransactionKind::Reload
+        )
+    }
+}
+
+impl Display for TransactionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TransactionKind::Wager => "Wager",
+            TransactionKind::Payout => "Payout",
+            TransactionKind::Refund => "Refund",
+            TransactionKind::Insurance => "Insurance",
+            TransactionKind::Reload => "Reload",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One entry in the ledger.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+    pub kind: TransactionKind,
+    pub amount: u32,
+    pub round: RoundId,
+    pub session: SessionId,
+    /// The balance after this transaction.
+    pub balance: u32,
+    pub timestamp: SystemTime,
+}
+
+impl Transaction {
+    /// The change to the balance: positive for credits, negative for debits.
+    pub fn signed_amount(&self) -> i64 {
+        if self.kind.is_credit() {
+            self.amount as i64
+        } else {
+            -(self.amount as i64)
+        }
+    }
+}
+
+/// Money in and out of a single round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoundSummary {
+    pub wagered: u64,
+    pub insurance: u64,
+    pub paid: u64,
+    pub refunded: u64,
+}
+
+impl RoundSummary {
+    /// Winnings minus stakes for the round.
+    pub fn net(&self) -> i64 {
+        (self.paid + self.refunded) as i64 - (self.wagered + self.insurance) as i64
+    }
+}
+
+/// The full transaction history of a bank. Rounds only move forward, so the
+/// transactions stay in round order and a round is found by binary search.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ledger {
+    opening_balance: u32,
+    transactions: Vec<Transaction>,
+}
+
+impl Ledger {
+    pub fn new(opening_balance: u32) -> Self {
+        Ledger {
+            opening_balance,
+            transactions: Vec::new(),
+        }
+    }
+
+    pub fn opening_balance(&self) -> u32 {
+        self.opening_balance
+    }
+
+    /// Every transaction, oldest first.
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    pub(crate) fn record(&mut self, transaction: Transaction) {
+        debug_assert!(
+            self.transactions
+                .last()
+                .is_none_or(|last| last.round <= transaction.round),
+            "transactions are recorded in round order"
+        );
+        self.transactions.push(transaction);
+    }
+
+    /// The transactions of one round, oldest first.
+    pub fn round(&self, round: RoundId) -> impl Iterator<Item = &Transaction> {
+        let start = self.transactions.partition_point(|t| t.round < round);
+        let end = self.transactions.partition_point(|t| t.round <= round);
+        self.transactions[start..end].iter()
+    }
+
+    /// Totals the money staked and returned in a round.
+    pub fn round_summary(&self, round: RoundId) -> RoundSummary {
+        summarise(self.round(round))
+    }
+
+    /// Each round that has transactions with its summary, oldest first.
+    pub fn round_summaries(&self) -> impl Iterator<Item = (RoundId, RoundSummary)> + '_ {
+        self.transactions
+            .chunk_by(|a, b| a.round == b.round)
+            .map(|round| (round[0].round, summarise(round)))
+    }
+
+    /// Sum of all transactions of one kind.
+    pub fn total(&self, kind: TransactionKind) -> u64 {
+        self.transactions
+            .iter()
+            .filter(|t| t.kind == kind)
+            .map(|t| t.amount as u64)
+            .sum()
+    }
+
+    /// Winnings minus stakes over a session. Reloads are not winnings and
+    /// are left out.
+    pub fn net_for_session(&self, session: SessionId) -> i64 {
+        self.transactions
+            .iter()
+            .filter(|t| t.session == session && t.kind != TransactionKind::Reload)
+            .map(Transaction::signed_amount)
+            .sum()
+    }
+
+    /// The round with the largest net win, and that win. Returns `None` if
+    /// no round finished ahead.
+    pub fn biggest_win(&self) -> Option<(RoundId, i64)> {
+        self.round_summaries()
+            .map(|(round, summary)| (round, summary.net()))
+            .filter(|(_, net)| *net > 0)
+            .max_by_key(|(_, net)| *net)
+    }
+
+    /// The largest fall in balance from a previous high, starting from the
+    /// opening balance.
+    pub fn max_drawdown(&self) -> u32 {
+        let mut peak = self.opening_balance;
+        let mut drawdown = 0;
+        for transaction in &self.transactions {
+            peak = peak.max(transaction.balance);
+            drawdown = drawdown.max(peak - transaction.balance);
+        }
+        drawdown
+    }
+}
+
+/// Totals the money staked and returned by some transactions.
+fn summarise<'a>(transactions: impl IntoIterator<Item = &'a Transaction>) -> RoundSummary {
+    let mut summary = RoundSummary::default();
+    for transaction in transactions {
+        let amount = transaction.amount as u64;
+        match transaction.kind {
+            TransactionKind::Wager => summary.wagered += amount,
+            TransactionKind::Insurance => summary.insurance += amount,
+            TransactionKind::Payout => summary.paid += amount,
+            TransactionKind::Refund => summary.refunded += amount,
+            TransactionKind::Reload => {}
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(
+        kind: TransactionKind,
+        amount: u32,
+        round: RoundId,
+        balance: u32,
+    ) -> Transaction {
+        Transaction {
+            kind,
+            amount,
+            round,
+            session: 0,
+            balance,
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    fn ledger() -> Ledger {
+        use TransactionKind::*;
+
+        let mut ledger = Ledger::new(100);
+        for t in [
+            transaction(Wager, 10, 1, 90),
+            transaction(Payout, 25, 1, 115),
+            transaction(Wager, 40, 2, 75),
+            transaction(Wager, 20, 3, 55),
+            transaction(Refund, 20, 3, 75),
+            transaction(Reload, 50, 3, 125),
+        ] {
+            ledger.record(t);
+        }
+        ledger
+    }
+
+    #[test]
+    fn round_summary_totals_each_kind() {
+        let ledger = ledger();
+        let first = ledger.round_summary(1);
+        assert_eq!(first.wagered, 10);
+        assert_eq!(first.paid, 25);
+        assert_eq!(first.net(), 15);
+
+        assert_eq!(ledger.round_summary(2).net(), -40);
+        assert_eq!(ledger.round_summary(3).net(), 0);
+        assert_eq!(ledger.round_summary(4), RoundSummary::default());
+
+        let rounds: Vec<RoundId> = ledger.round_summaries().map(|(round, _)| round).collect();
+        assert_eq!(rounds, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn session_net_ignores_reloads() {
+        assert_eq!(ledger().net_for_session(0), 15 - 40);
+        assert_eq!(ledger().net_for_session(1), 0);
+    }
+
+    #[test]
+    fn biggest_win_and_drawdown() {
+        let ledger = ledger();
+        assert_eq!(ledger.biggest_win(), Some((1, 15)));
+        assert_eq!(ledger.max_drawdown(), 115 - 55);
+        assert_eq!(ledger.total(TransactionKind::Wager), 70);
+
+        assert_eq!(Ledger::new(10).biggest_win(), None);
+        assert_eq!(Ledger::new(10).max_drawdown(), 0);
+    }
+}
@@ -0,0 +1,224 @@
+//! Table betting limits.
+//!
+//! Every table has a minimum and a maximum bet, and many cap the extra
+//! stake that can go out when doubling down or splitting. Engines check
+//! each wager against a [`TableLimits`] before taking it from the bank.
+
+use std::fmt::Display;
+
+/// The betting limits of one table.
+///
+/// # Example
+/// ```
+/// use card_games::bank::limits::{LimitError, TableLimits};
+/// let limits = TableLimits::new(5, 500);
+/// assert_eq!(limits.check_bet(25), Ok(()));
+/// assert_eq!(
+///     limits.check_bet(1_000),
+///     Err(LimitError::AboveMaximum { bet: 1_000, max: 500 })
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableLimits {
+    pub min_bet: u32,
+    pub max_bet: u32,
+    /// Largest extra stake allowed when doubling down.
+    pub max_double: u32,
+    /// Largest stake allowed on each hand created by a split.
+    pub max_split: u32,
+}
+
+impl TableLimits {
+    /// Limits where doubles and splits may go up to the table maximum.
+    pub fn new(min_bet: u32, max_bet: u32) -> Self {
+        TableLimits {
+            min_bet,
+            max_bet,
+            max_double: max_bet,
+            max_split: max_bet,
+        }
+    }
+
+    /// Limits that accept any bet.
+    pub fn unlimited() -> Self {
+        Self::new(1, u32::MAX)
+    }
+
+    pub fn with_max_double(mut self, max_double: u32) -> Self {
+        self.max_double = max_double;
+        self
+    }
+
+    pub fn with_max_split(mut self, max_split: u32) -> Self {
+        self.max_split = max_split;
+        self
+    }
+
+    /// Checks an opening bet.
+    pub fn check_bet(&self, bet: u32) -> Result<(), LimitError> {
+        if bet < self.min_bet {
+            return Err(LimitError::BelowMinimum {
+                bet,
+                min: self.min_bet,
+            });
+        }
+        if bet > self.max_bet {
+            return Err(LimitError::AboveMaximum {
+                bet,
+                max: self.max_bet,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks the extra stake put out to double down.
+    pub fn check_double(&self, stake: u32) -> Result<(), LimitError> {
+        if stake > self.max_double {
+            return Err(LimitError::DoubleAboveMaximum {
+                stake,
+                max: self.max_double,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks the stake put on a new split hand.
+    pub fn check_split(&self, stake: u32) -> Result<(), LimitError> {
+        if stake > self.max_split {
+            return Err(LimitError::SplitAboveMaximum {
+                stake,
+                max: self.max_split,
+            });
+        }
+        Ok(())
+    }
+
+    /// Clamps a desired bet into the table's range, as a bet-spread
+    /// strategy must.
+    pub fn clamp(&self, bet: u32) -> u32 {
+        bet.clamp(self.min_bet, self.max_bet.max(self.min_bet))
+    }
+}
+
+impl Default for TableLimits {
+    fn default() -> Self {
+        TablePreset::FiveDollar.limits()
+    }
+}
+
+/// Named tables found on a casino floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TablePreset {
+    /// $5–$500.
+    FiveDollar,
+    /// $25–$2,500.
+    TwentyFiveDollar,
+    /// $100–$10,000, with doubles and splits capped at $5,000.
+    HighLimit,
+}
+
+impl TablePreset {
+    pub const ALL: [TablePreset; 3] = [
+        TablePreset::FiveDollar,
+        TablePreset::TwentyFiveDollar,
+        TablePreset::HighLimit,
+    ];
+
+    pub fn limits(&self) -> TableLimits {
+        match self {
+            TablePreset::FiveDollar => TableLimits::new(5, 500),
+            TablePreset::TwentyFiveDollar => TableLimits::new(25, 2_500),
+            TablePreset::HighLimit => TableLimits::new(100, 10_000)
+                .with_max_double(5_000)
+                .with_max_split(5_000),
+        }
+    }
+}
+
+impl Display for TablePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TablePreset::FiveDollar => write!(f, "$5 table"),
+            TablePreset::TwentyFiveDollar => write!(f, "$25 table"),
+            TablePreset::HighLimit => write!(f, "$100 high limit"),
+        }
+    }
+}
+
+/// A wager outside the table limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitError {
+    BelowMinimum { bet: u32, min: u32 },
+    AboveMaximum { bet: u32, max: u32 },
+    DoubleAboveMaximum { stake: u32, max: u32 },
+    SplitAboveMaximum { stake: u32, max: u32 },
+}
+
+impl Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::BelowMinimum { bet, min } => {
+                write!(f, "A bet of ${} is below the ${} table minimum", bet, min)
+            }
+            LimitError::AboveMaximum { bet, max } => {
+                write!(f, "A bet of ${} is above the ${} table maximum", bet, max)
+            }
+            LimitError::DoubleAboveMaximum { stake, max } => {
+                write!(f, "Doubling for ${} is above the ${} limit", stake, max)
+            }
+            LimitError::SplitAboveMaximum { stake, max } => {
+                write!(f, "Splitting for ${} is above the ${} limit", stake, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_have_increasing_minimums() {
+        let mins: Vec<u32> = TablePreset::ALL
+            .iter()
+            .map(|p| p.limits().min_bet)
+            .collect();
+        assert_eq!(mins, [5, 25, 100]);
+        assert_eq!(TableLimits::default(), TablePreset::FiveDollar.limits());
+    }
+
+    #[test]
+    fn bets_outside_the_range_are_rejected() {
+        let limits = TablePreset::TwentyFiveDollar.limits();
+        assert_eq!(
+            limits.check_bet(10),
+            Err(LimitError::BelowMinimum { bet: 10, min: 25 })
+        );
+        assert!(limits.check_bet(2_500).is_ok());
+        assert!(limits.check_bet(2_501).is_err());
+    }
+
+    #[test]
+    fn doubles_and_splits_have_their_own_caps() {
+        let limits = TablePreset::HighLimit.limits();
+        assert!(limits.check_double(5_000).is_ok());
+        assert_eq!(
+            limits.check_double(6_000),
+            Err(LimitError::DoubleAboveMaximum {
+                stake: 6_000,
+                max: 5_000
+            })
+        );
+        assert!(limits.check_split(5_001).is_err());
+    }
+
+    #[test]
+    fn clamp_keeps_spreads_on_the_table() {
+        let limits = TableLimits::new(5, 500);
+        assert_eq!(limits.clamp(1), 5);
+        assert_eq!(limits.clamp(80), 80);
+        assert_eq!(limits.clamp(10_000), 500);
+    }
+}
@@ -1,2 +1,8 @@
+#[allow(clippy::module_inception)]
 pub mod bank;
 pub mod bet;
+pub mod betting;
+pub mod chips;
+pub mod ledger;
+pub mod limits;
+pub mod risk;
@@ -0,0 +1,465 @@
+//! Risk of ruin and bankroll forecasting.
+//!
+//! A game is described by its edge and variance per unit staked, and a
+//! player by how they size their bets. From those two a [`RiskModel`]
+//! works out the expected win and its spread per round, then the chance
+//! of losing the whole bankroll, the number of rounds before skill
+//! outweighs luck (N0) and the expected win per hour. A Monte Carlo
+//! [`RiskModel::simulate`] run shows the spread of bankrolls over time.
+
+use rand::Rng;
+
+use super::{bank::Bank, ledger::Ledger};
+
+/// How much each true count point adds to the player's edge, a common
+/// rule of thumb for Hi-Lo.
+pub const EDGE_PER_TRUE_COUNT: f64 = 0.005;
+
+/// The edge and variance of a game, both per unit staked.
+///
+/// # Example
+/// ```
+/// use card_games::bank::risk::GameStats;
+/// let stats = GameStats::new(-0.005, 1.3).with_rounds_per_hour(100.0);
+/// assert_eq!(stats.rounds_per_hour, 100.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameStats {
+    /// Average win per unit bet; negative when the house has the edge.
+    pub edge: f64,
+    /// Variance of the result of a one-unit bet.
+    pub variance: f64,
+    pub rounds_per_hour: f64,
+}
+
+impl GameStats {
+    /// Stats for a game played at 80 rounds an hour.
+    pub fn new(edge: f64, variance: f64) -> Self {
+        GameStats {
+            edge,
+            variance,
+            rounds_per_hour: 80.0,
+        }
+    }
+
+    pub fn with_rounds_per_hour(mut self, rounds_per_hour: f64) -> Self {
+        self.rounds_per_hour = rounds_per_hour;
+        self
+    }
+
+    /// Estimates the stats from the results of one-unit bets. Returns
+    /// `None` with fewer than two results.
+    pub fn from_results(results: &[f64]) -> Option<Self> {
+        if results.len() < 2 {
+            return None;
+        }
+        let n = results.len() as f64;
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        Some(GameStats::new(mean, variance))
+    }
+
+    /// Estimates the stats from the rounds in a ledger, each round's net
+    /// divided by its stake. Rounds without a wager are skipped.
+    pub fn from_ledger(ledger: &Ledger) -> Option<Self> {
+        let results: Vec<f64> = ledger
+            .round_summaries()
+            .map(|(_, summary)| summary)
+            .filter(|summary| summary.wagered > 0)
+            .map(|summary| summary.net() as f64 / summary.wagered as f64)
+            .collect();
+        Self::from_results(&results)
+    }
+}
+
+/// One step of a count-based bet ramp.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RampStep {
+    pub true_count: i32,
+    /// The bet at this count.
+    pub bet: u32,
+    /// How often rounds are played at this count; steps need not sum to
+    /// one.
+    pub frequency: f64,
+}
+
+/// How the player sizes each bet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BettingScheme {
+    /// The same bet every round.
+    Flat(u32),
+    /// A fraction of the Kelly bet, resized to the bankroll each round.
+    Kelly(f64),
+    /// Bets raised with the true count. The game's edge is the edge at a
+    /// count of zero, and each point adds [`EDGE_PER_TRUE_COUNT`].
+    Ramp(Vec<RampStep>),
+}
+
+/// A bet and the edge it is placed at, with the chance of placing it.
+#[derive(Debug, Clone, Copy)]
+struct Spot {
+    bet: f64,
+    edge: f64,
+    weight: f64,
+}
+
+/// What a betting scheme can expect from a game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskReport {
+    pub mean_per_round: f64,
+    pub sd_per_round: f64,
+    /// The chance of ever losing the whole bankroll. For Kelly betting,
+    /// which shrinks bets as the bankroll falls and so never goes broke,
+    /// the chance of ever losing half of it.
+    pub risk_of_ruin: f64,
+    /// Rounds until the expected win equals one standard deviation, or
+    /// `None` without an edge.
+    pub n0: Option<f64>,
+    pub hourly_win: f64,
+    pub hourly_sd: f64,
+}
+
+/// A game and a betting scheme.
+///
+/// # Example
+/// ```
+/// use card_games::bank::bank::Bank;
+/// use card_games::bank::risk::{BettingScheme, GameStats, RiskModel};
+/// let model = RiskModel::new(GameStats::new(0.01, 1.3), BettingScheme::Flat(10));
+/// let report = model.report_for(&Bank::new(1_000));
+/// assert!(report.risk_of_ruin > 0.2 && report.risk_of_ruin < 0.22);
+/// assert!((report.hourly_win - 8.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskModel {
+    pub stats: GameStats,
+    pub scheme: BettingScheme,
+}
+
+impl RiskModel {
+    pub fn new(stats: GameStats, scheme: BettingScheme) -> Self {
+        RiskModel { stats, scheme }
+    }
+
+    /// The bets the scheme makes with `bankroll`, each with its edge and
+    /// how likely it is.
+    fn spots(&self, bankroll: f64) -> Vec<Spot> {
+        let edge = self.stats.edge;
+        match &self.scheme {
+            BettingScheme::Flat(bet) => vec![Spot {
+                bet: *bet as f64,
+                edge,
+                weight: 1.0,
+            }],
+            BettingScheme::Kelly(fraction) => vec![Spot {
+                bet: self.kelly_bet(*fraction, bankroll),
+                edge,
+                weight: 1.0,
+            }],
+            BettingScheme::Ramp(steps) => {
+                let total: f64 = steps.iter().map(|s| s.frequency).sum();
+                steps
+                    .iter()
+                    .filter(|s| s.frequency > 0.0)
+                    .map(|s| Spot {
+                        bet: s.bet as f64,
+                        edge: edge + EDGE_PER_TRUE_COUNT * s.true_count as f64,
+                        weight: s.frequency / total,
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// The Kelly bet is edge over variance times the bankroll; nothing is
+    /// bet without an edge.
+    fn kelly_bet(&self, fraction: f64, bankroll: f64) -> f64 {
+        if self.stats.edge <= 0.0 || self.stats.variance <= 0.0 {
+            return 0.0;
+        }
+        (fraction * self.stats.edge / self.stats.variance * bankroll).max(0.0)
+    }
+
+    /// Mean and variance of one round's result with `bankroll`.
+    fn moments(&self, bankroll: f64) -> (f64, f64) {
+        let spots = self.spots(bankroll);
+        let mean: f64 = spots.iter().map(|s| s.weight * s.bet * s.edge).sum();
+        let square: f64 = spots
+            .iter()
+            .map(|s| s.weight * s.bet * s.bet * (self.stats.variance + s.edge * s.edge))
+            .sum();
+        (mean, (square - mean * mean).max(0.0))
+    }
+
+    /// The expected results of the scheme with `bankroll`.
+    pub fn report(&self, bankroll: u32) -> RiskReport {
+        let bankroll = bankroll as f64;
+        let (mean, variance) = self.moments(bankroll);
+        let risk_of_ruin = match &self.scheme {
+            BettingScheme::Kelly(fraction) if mean > 0.0 => {
+                // Betting a fraction k of Kelly, the chance of ever falling
+                // to a share x of the bankroll is x^(2/k - 1).
+                0.5_f64.powf(2.0 / fraction - 1.0).min(1.0)
+            }
+            BettingScheme::Kelly(_) => 0.0,
+            _ if mean <= 0.0 => {
+                if variance > 0.0 || mean < 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => (-2.0 * mean * bankroll / variance).exp(),
+        };
+        let n0 = (mean > 0.0).then(|| variance / (mean * mean));
+        let hours = self.stats.rounds_per_hour;
+        RiskReport {
+            mean_per_round: mean,
+            sd_per_round: variance.sqrt(),
+            risk_of_ruin,
+            n0,
+            hourly_win: mean * hours,
+            hourly_sd: (variance * hours).sqrt(),
+        }
+    }
+
+    /// The expected results of the scheme with the bank's balance.
+    pub fn report_for(&self, bank: &Bank) -> RiskReport {
+        self.report(bank.balance())
+    }
+
+    /// Plays `trials` bankrolls of `bankroll` for `rounds` rounds each,
+    /// drawing every result from a normal distribution with the game's
+    /// edge and variance. A bankroll that cannot cover its next bet is
+    /// ruined and stops playing.
+    pub fn simulate<R: Rng + ?Sized>(
+        &self,
+        bankroll: u32,
+        rounds: usize,
+        trials: usize,
+        rng: &mut R,
+    ) -> Simulation {
+        let checkpoints = checkpoints(rounds);
+        let mut balances = vec![Vec::with_capacity(trials); checkpoints.len()];
+        let mut ruined = 0;
+        let sd = self.stats.variance.max(0.0).sqrt();
+
+        for _ in 0..trials {
+            let mut balance = bankroll as f64;
+            let mut broke = false;
+            let mut next = 0;
+            for round in 1..=rounds {
+                if !broke {
+                    let spot = self.pick(balance, rng);
+                    if spot.bet > balance + 1e-9 || balance <= 0.0 {
+                        broke = true;
+                    } else {
+                        balance += spot.bet * (spot.edge + sd * standard_normal(rng));
+                        if balance <= 0.0 {
+                            balance = 0.0;
+                            broke = true;
+                        }
+                    }
+                }
+                if checkpoints.get(next) == Some(&round) {
+                    balances[next].push(balance);
+                    next += 1;
+                }
+            }
+            if broke {
+                ruined += 1;
+            }
+        }
+
+        let bands = checkpoints
+            .into_iter()
+            .zip(balances)
+            .map(|(round, mut balances)| Band::from_balances(round, &mut balances))
+            .collect();
+        Simulation {
+            bands,
+            ruined: if trials == 0 {
+                0.0
+            } else {
+                ruined as f64 / trials as f64
+            },
+        }
+    }
+
+    fn pick<R: Rng + ?Sized>(&self, balance: f64, rng: &mut R) -> Spot {
+        let spots = self.spots(balance);
+        let mut roll: f64 = rng.gen();
+        for spot in &spots {
+            if roll < spot.weight {
+                return *spot;
+            }
+            roll -= spot.weight;
+        }
+        spots.last().copied().unwrap_or(Spot {
+            bet: 0.0,
+            edge: 0.0,
+            weight: 1.0,
+        })
+    }
+}
+
+/// At most 100 evenly spaced rounds to record, ending on the last.
+fn checkpoints(rounds: usize) -> Vec<usize> {
+    let step = rounds.div_ceil(100).max(1);
+    let mut points: Vec<usize> = (1..=rounds / step).map(|i| i * step).collect();
+    if rounds > 0 && points.last() != Some(&rounds) {
+        points.push(rounds);
+    }
+    points
+}
+
+/// A draw from the standard normal distribution, by the Box–Muller
+/// transform.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+/// Percentiles of the simulated bankrolls after a number of rounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Band {
+    pub round: usize,
+    pub p5: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub p95: f64,
+}
+
+impl Band {
+    fn from_balances(round: usize, balances: &mut [f64]) -> Self {
+        balances.sort_by(|a, b| a.total_cmp(b));
+        let at = |p: f64| {
+            if balances.is_empty() {
+                return 0.0;
+            }
+            let index = (p * (balances.len() - 1) as f64).round() as usize;
+            balances[index]
+        };
+        Band {
+            round,
+            p5: at(0.05),
+            p25: at(0.25),
+            median: at(0.5),
+            p75: at(0.75),
+            p95: at(0.95),
+        }
+    }
+}
+
+/// The outcome of [`RiskModel::simulate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    /// Percentile bands at up to 100 evenly spaced rounds.
+    pub bands: Vec<Band>,
+    /// The share of bankrolls that went broke.
+    pub ruined: f64,
+}
+
+impl Simulation {
+    /// The band after the last round played.
+    pub fn last(&self) -> Option<&Band> {
+        self.bands.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn flat_betting_matches_the_formulas() {
+        let model = RiskModel::new(GameStats::new(0.01, 1.3), BettingScheme::Flat(10));
+        let report = model.report(1_000);
+        assert!((report.mean_per_round - 0.1).abs() < 1e-12);
+        assert!((report.risk_of_ruin - (-2.0 * 0.1 * 1_000.0 / 130.0_f64).exp()).abs() < 1e-3);
+        assert!((report.n0.unwrap() - 13_000.0).abs() < 1.0);
+        assert!((report.hourly_win - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_house_edge_is_certain_ruin() {
+        let model = RiskModel::new(GameStats::new(-0.005, 1.3), BettingScheme::Flat(10));
+        let report = model.report(10_000);
+        assert_eq!(report.risk_of_ruin, 1.0);
+        assert_eq!(report.n0, None);
+        assert!(report.hourly_win < 0.0);
+    }
+
+    #[test]
+    fn half_kelly_risks_an_eighth_of_halving() {
+        let model = RiskModel::new(GameStats::new(0.01, 1.0), BettingScheme::Kelly(0.5));
+        let report = model.report(10_000);
+        assert!((report.risk_of_ruin - 0.125).abs() < 1e-12);
+        // Half Kelly bets 0.5% of 10,000.
+        assert!((report.mean_per_round - 0.5).abs() < 1e-9);
+
+        let losing = RiskModel::new(GameStats::new(-0.01, 1.0), BettingScheme::Kelly(0.5));
+        assert_eq!(losing.report(10_000).mean_per_round, 0.0);
+    }
+
+    #[test]
+    fn a_ramp_turns_a_house_edge_around() {
+        let steps = vec![
+            RampStep {
+                true_count: 0,
+                bet: 10,
+                frequency: 0.8,
+            },
+            RampStep {
+                true_count: 4,
+                bet: 120,
+                frequency: 0.2,
+            },
+        ];
+        let stats = GameStats::new(-0.005, 1.3);
+        let report = RiskModel::new(stats, BettingScheme::Ramp(steps)).report(10_000);
+        // 0.8 * 10 * -0.005 + 0.2 * 120 * 0.015
+        assert!((report.mean_per_round - 0.32).abs() < 1e-9);
+        assert!(report.risk_of_ruin < 1.0);
+    }
+
+    #[test]
+    fn stats_come_from_the_ledger() {
+        let mut bank = Bank::new(100);
+        for win in [true, false, false, true, true] {
+            bank.start_round();
+            bank.wager(10).unwrap();
+            if win {
+                bank.payout(20).unwrap();
+            }
+        }
+        let stats = GameStats::from_ledger(bank.ledger()).unwrap();
+        assert!((stats.edge - 0.2).abs() < 1e-12);
+        assert!((stats.variance - 1.2).abs() < 1e-12);
+        assert_eq!(GameStats::from_results(&[1.0]), None);
+    }
+
+    #[test]
+    fn simulation_bands_are_ordered() {
+        let model = RiskModel::new(GameStats::new(0.01, 1.3), BettingScheme::Flat(10));
+        let mut rng = StdRng::seed_from_u64(7);
+        let simulation = model.simulate(200, 1_000, 200, &mut rng);
+        assert_eq!(simulation.bands.len(), 100);
+        let last = simulation.last().unwrap();
+        assert_eq!(last.round, 1_000);
+        assert!(last.p5 <= last.p25 && last.p25 <= last.median);
+        assert!(last.median <= last.p75 && last.p75 <= last.p95);
+        assert!(simulation.ruined > 0.0 && simulation.ruined < 1.0);
+    }
+
+    #[test]
+    fn checkpoints_end_on_the_last_round() {
+        assert_eq!(checkpoints(5), vec![1, 2, 3, 4, 5]);
+        assert_eq!(checkpoints(250).len(), 84);
+        assert_eq!(checkpoints(250).last(), Some(&250));
+        assert!(checkpoints(0).is_empty());
+    }
+}
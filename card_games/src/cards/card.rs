@@ -3,7 +3,7 @@
 //! Includes [`Suit`], [`Value`], and [`Card`] structs and helpers for building
 //! decks, checking card properties, and game-specific logic.
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -11,7 +11,9 @@ use strum_macros::EnumIter;
 /// Represents the suit of a playing card.
 ///
 /// Includes standard suits (`Clubs`, `Diamonds`, `Hearts`, `Spades`) and a special `Joker` variant.
-#[derive(Debug, Copy, Clone, PartialEq, EnumIter)]
+///
+/// Suits compare in bridge order (♣ < ♦ < ♥ < ♠), with the Joker last.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter)]
 pub enum Suit {
     CLUBS,
     DIAMONDS,
@@ -47,12 +49,45 @@ impl Suit {
     pub fn is_black(&self) -> bool {
         matches!(self, Suit::CLUBS | Suit::SPADES)
     }
+
+    /// Returns the one-letter notation for the suit (`c`, `d`, `h`, `s`).
+    ///
+    /// Jokers have no suit letter and return `k`, matching the `Jk` card
+    /// notation.
+    pub fn short(&self) -> char {
+        match self {
+            Suit::CLUBS => 'c',
+            Suit::DIAMONDS => 'd',
+            Suit::HEARTS => 'h',
+            Suit::SPADES => 's',
+            Suit::JOKER => 'k',
+        }
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    /// Parses a suit letter (`c`, `D`), symbol (`♥`) or name (`spades`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" => Err(ParseCardError::Empty),
+            "c" | "♣" | "clubs" => Ok(Suit::CLUBS),
+            "d" | "♦" | "diamonds" => Ok(Suit::DIAMONDS),
+            "h" | "♥" | "hearts" => Ok(Suit::HEARTS),
+            "s" | "♠" | "spades" => Ok(Suit::SPADES),
+            _ => Err(ParseCardError::InvalidSuit(s.to_string())),
+        }
+    }
 }
 
 /// Represents the face value of a playing card.
 ///
 /// Includes numbered cards, face cards, Ace, and a Joker.
-#[derive(Debug, Copy, Clone, PartialEq, EnumIter)]
+///
+/// Values compare by rank with aces low and the Joker lowest of all; use
+/// [`RankOrder`](crate::cards::order::RankOrder) for other orders.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter)]
 #[repr(u8)]
 pub enum Value {
     ACE = 1,
@@ -131,12 +166,56 @@ impl Value {
             _ => Some(*self as u8),
         }
     }
+
+    /// Returns the short notation for the value: `A`, `2`–`9`, `T`, `J`,
+    /// `Q`, `K`, or `Jk` for a Joker.
+    pub fn short(&self) -> &'static str {
+        match self {
+            Value::ACE => "A",
+            Value::TWO => "2",
+            Value::THREE => "3",
+            Value::FOUR => "4",
+            Value::FIVE => "5",
+            Value::SIX => "6",
+            Value::SEVEN => "7",
+            Value::EIGHT => "8",
+            Value::NINE => "9",
+            Value::TEN => "T",
+            Value::JACK => "J",
+            Value::QUEEN => "Q",
+            Value::KING => "K",
+            Value::JOKER => "Jk",
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseCardError;
+
+    /// Parses a short value (`A`, `7`, `T` or `10`, `q`) or its name
+    /// (`Ace`, `seven`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        if lower.is_empty() {
+            return Err(ParseCardError::Empty);
+        }
+
+        Value::iter()
+            .find(|value| {
+                lower == value.short().to_ascii_lowercase()
+                    || lower == value.to_string().to_ascii_lowercase()
+                    || (lower == "10" && *value == Value::TEN)
+            })
+            .ok_or_else(|| ParseCardError::InvalidValue(s.to_string()))
+    }
 }
 
 /// Represents a full playing card, consisting of a [`Suit`] and [`Value`].
 ///
 /// Includes helpers for creating jokers, determining card color, face cards, and rank.
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// Cards compare by suit, then by value (ace low).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Card {
     suit: Suit,
     value: Value,
@@ -195,6 +274,61 @@ impl Card {
     pub fn rank(&self) -> Option<u8> {
         self.value.rank()
     }
+
+    /// Number of distinct one-byte codes: 52 standard cards and the Joker.
+    pub const CODES: u8 = 53;
+
+    /// Encodes the card in one byte: `suit * 13 + rank - 1` for standard
+    /// cards (♣ first, aces low), and 52 for the Joker. Any card with the
+    /// joker suit or value encodes as the Joker.
+    ///
+    /// The codes follow the card's `Ord`, so sorting codes sorts cards.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{Card, Suit, Value};
+    /// let card = Card::new(Suit::HEARTS, Value::TEN);
+    /// assert_eq!(card.to_byte(), 35);
+    /// assert_eq!(Card::from_byte(35), Some(card));
+    /// ```
+    pub fn to_byte(&self) -> u8 {
+        match self.value.rank() {
+            Some(rank) if self.suit != Suit::JOKER => self.suit as u8 * 13 + rank - 1,
+            _ => Self::CODES - 1,
+        }
+    }
+
+    /// Decodes a card from [`Card::to_byte`]. Returns `None` for codes above
+    /// 52.
+    pub fn from_byte(code: u8) -> Option<Card> {
+        if code >= Self::CODES {
+            return None;
+        }
+        if code == Self::CODES - 1 {
+            return Some(Card::joker());
+        }
+
+        let suit = Suit::standard_suits().nth((code / 13) as usize)?;
+        let value = Value::standard_values().nth((code % 13) as usize)?;
+        Some(Card::new(suit, value))
+    }
+
+    /// Returns the card in short notation: value then suit letter, such as
+    /// `Th` or `As`. Jokers are written `Jk`.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{Card, Suit, Value};
+    /// assert_eq!(Card::new(Suit::HEARTS, Value::TEN).short(), "Th");
+    /// assert_eq!(Card::joker().short(), "Jk");
+    /// ```
+    pub fn short(&self) -> String {
+        if self.is_joker() {
+            Value::JOKER.short().to_string()
+        } else {
+            format!("{}{}", self.value.short(), self.suit.short())
+        }
+    }
 }
 
 impl Display for Card {
@@ -207,6 +341,108 @@ impl Display for Card {
     }
 }
 
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    /// Parses short notation (`Th`, `10h`, `T♥`, `Jk`) or the long form
+    /// produced by `Display` (`Ten of ♥`, `Joker`). A joker with a suit,
+    /// such as `Jkh`, is rejected.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{Card, Suit, Value};
+    /// let card: Card = "As".parse().unwrap();
+    /// assert_eq!(card, Card::new(Suit::SPADES, Value::ACE));
+    /// assert_eq!("Ten of ♥".parse::<Card>().unwrap().short(), "Th");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseCardError::Empty);
+        }
+        if s.eq_ignore_ascii_case("jk") || s.eq_ignore_ascii_case("joker") {
+            return Ok(Card::joker());
+        }
+
+        let (value, suit) = match s.split_once(" of ") {
+            Some(parts) => parts,
+            None => {
+                let split = s
+                    .char_indices()
+                    .last()
+                    .map(|(i, _)| i)
+                    .filter(|&i| i > 0)
+                    .ok_or_else(|| ParseCardError::InvalidCard(s.to_string()))?;
+                s.split_at(split)
+            }
+        };
+        let (suit, value): (Suit, Value) = (suit.parse()?, value.parse()?);
+        if value == Value::JOKER {
+            return Err(ParseCardError::InvalidCard(s.to_string()));
+        }
+        Ok(Card::new(suit, value))
+    }
+}
+
+/// An error from parsing a card, suit or value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    Empty,
+    InvalidSuit(String),
+    InvalidValue(String),
+    InvalidCard(String),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "No card given"),
+            ParseCardError::InvalidSuit(s) => write!(f, "Unknown suit '{}'", s),
+            ParseCardError::InvalidValue(s) => write!(f, "Unknown card value '{}'", s),
+            ParseCardError::InvalidCard(s) => write!(f, "Cannot read '{}' as a card", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// Parses a list of cards separated by spaces or commas, such as
+/// `"As Kd 7h"`. Long names such as `Ten of ♥` may be mixed in.
+///
+/// # Example
+/// ```
+/// use card_games::cards::parse_cards;
+/// let cards = parse_cards("As, Kd 7h").unwrap();
+/// assert_eq!(cards.len(), 3);
+/// assert_eq!(parse_cards("Ten of ♥, Joker Qs").unwrap().len(), 3);
+/// assert!(parse_cards("As Xx").is_err());
+/// ```
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    let mut tokens = s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .peekable();
+    let mut cards = Vec::new();
+    while let Some(token) = tokens.next() {
+        // A long name spans three tokens: value, "of" and suit.
+        let card = match tokens.next_if(|next| next.eq_ignore_ascii_case("of")) {
+            Some(_) => match tokens.next() {
+                Some(suit) => format!("{} of {}", token, suit).parse()?,
+                None => return Err(ParseCardError::InvalidCard(format!("{} of", token))),
+            },
+            None => token.parse()?,
+        };
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+/// Formats cards in short notation separated by spaces, the inverse of
+/// [`parse_cards`].
+pub fn format_cards(cards: &[Card]) -> String {
+    cards.iter().map(Card::short).collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,6 +459,105 @@ mod tests {
         assert_eq!(*card.value(), Value::QUEEN);
     }
 
+    #[test]
+    fn short_notation_round_trips_every_card() {
+        for suit in Suit::standard_suits() {
+            for value in Value::standard_values() {
+                let card = Card::new(suit, value);
+                assert_eq!(card.short().parse::<Card>(), Ok(card));
+                assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+            }
+        }
+        assert_eq!("Jk".parse::<Card>(), Ok(Card::joker()));
+    }
+
+    #[test]
+    fn suited_jokers_are_rejected() {
+        for text in ["Jkh", "Joker of ♥", "jokers"] {
+            assert_eq!(
+                text.parse::<Card>(),
+                Err(ParseCardError::InvalidCard(text.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn cards_with_a_joker_suit_encode_as_the_joker() {
+        for value in [Value::KING, Value::ACE, Value::JOKER] {
+            let card = Card::new(Suit::JOKER, value);
+            assert_eq!(card.to_byte(), Card::CODES - 1);
+        }
+        assert_eq!(Card::new(Suit::HEARTS, Value::JOKER).to_byte(), 52);
+    }
+
+    #[test]
+    fn parsing_accepts_case_tens_and_symbols() {
+        let ten_of_hearts = Card::new(Suit::HEARTS, Value::TEN);
+        assert_eq!("th".parse::<Card>(), Ok(ten_of_hearts));
+        assert_eq!("10h".parse::<Card>(), Ok(ten_of_hearts));
+        assert_eq!("T♥".parse::<Card>(), Ok(ten_of_hearts));
+        assert_eq!(" Qs ".parse::<Card>().unwrap().short(), "Qs");
+    }
+
+    #[test]
+    fn parsing_reports_what_went_wrong() {
+        assert_eq!("".parse::<Card>(), Err(ParseCardError::Empty));
+        assert_eq!(
+            "Ax".parse::<Card>(),
+            Err(ParseCardError::InvalidSuit("x".to_string()))
+        );
+        assert_eq!(
+            "1s".parse::<Card>(),
+            Err(ParseCardError::InvalidValue("1".to_string()))
+        );
+        assert_eq!(
+            "s".parse::<Card>(),
+            Err(ParseCardError::InvalidCard("s".to_string()))
+        );
+    }
+
+    #[test]
+    fn card_lists_parse_and_format() {
+        let cards = parse_cards("As Kd,7h  Jk").unwrap();
+        assert_eq!(format_cards(&cards), "As Kd 7h Jk");
+        assert_eq!(parse_cards("").unwrap(), vec![]);
+
+        let long = parse_cards("Ten of ♥, Ace OF spades  Joker 7h").unwrap();
+        assert_eq!(format_cards(&long), "Th As Jk 7h");
+        assert_eq!(
+            parse_cards("Ten of"),
+            Err(ParseCardError::InvalidCard("Ten of".to_string()))
+        );
+    }
+
+    #[test]
+    fn cards_order_by_suit_then_ace_low_value() {
+        let mut cards = parse_cards("Ks 2h Ah Jk 3c").unwrap();
+        cards.sort();
+        assert_eq!(format_cards(&cards), "3c Ah 2h Ks Jk");
+        assert!(Value::JOKER < Value::ACE);
+    }
+
+    #[test]
+    fn byte_encoding_round_trips_in_card_order() {
+        let mut previous = None;
+        for code in 0..Card::CODES {
+            let card = Card::from_byte(code).unwrap();
+            assert_eq!(card.to_byte(), code);
+            assert!(previous < Some(card));
+            previous = Some(card);
+        }
+        assert_eq!(Card::from_byte(Card::CODES), None);
+        assert_eq!(Card::joker().to_byte(), 52);
+    }
+
+    #[test]
+    fn cards_can_be_hashed() {
+        let cards: std::collections::HashSet<Card> =
+            parse_cards("As As Kd").unwrap().into_iter().collect();
+        assert_eq!(cards.len(), 2);
+    }
+
     #[test]
     fn joker_card_is_detected() {
         let joker = Card::joker();
@@ -0,0 +1,328 @@
+//! A set of distinct cards packed into a 64-bit mask.
+//!
+//! Each card occupies the bit given by its one-byte code
+//! ([`Card::to_byte`]), so insert, remove and membership tests are single bit
+//! operations and set algebra is one instruction. A `CardSet` holds at most
+//! one of each card; use [`Deck`](super::Deck) or [`Hand`](super::hand::Hand)
+//! when duplicates matter, such as in a multi-deck shoe.
+
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitOr, Not, Sub},
+};
+
+use super::card::{Card, Suit};
+
+/// Bits of a single suit, before shifting into place.
+const SUIT_BITS: u64 = (1 << 13) - 1;
+
+/// A set of distinct cards backed by a 64-bit mask.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{card_set::CardSet, parse_cards, Suit};
+/// let set: CardSet = parse_cards("As Kd 7h").unwrap().into_iter().collect();
+/// assert_eq!(set.len(), 3);
+/// assert!(set.contains(&"Kd".parse().unwrap()));
+/// assert_eq!(set.count_suit(Suit::SPADES), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSet {
+    bits: u64,
+}
+
+impl CardSet {
+    /// Every code a card can have.
+    const VALID: u64 = (1 << Card::CODES) - 1;
+
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        CardSet { bits: 0 }
+    }
+
+    /// Returns the 52 standard cards.
+    pub const fn standard52() -> Self {
+        CardSet {
+            bits: (1 << 52) - 1,
+        }
+    }
+
+    /// Creates a set from a raw mask. Bits that do not match a card code are
+    /// dropped.
+    pub const fn from_bits(bits: u64) -> Self {
+        CardSet {
+            bits: bits & Self::VALID,
+        }
+    }
+
+    /// Returns the raw mask.
+    pub const fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    fn bit(card: &Card) -> u64 {
+        1 << card.to_byte()
+    }
+
+    /// Adds a card. Returns `true` if it was not already present.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let added = !self.contains(&card);
+        self.bits |= Self::bit(&card);
+        added
+    }
+
+    /// Removes a card. Returns `true` if it was present.
+    pub fn remove(&mut self, card: &Card) -> bool {
+        let present = self.contains(card);
+        self.bits &= !Self::bit(card);
+        present
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.bits & Self::bit(card) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.bits = 0;
+    }
+
+    pub fn union(&self, other: &CardSet) -> CardSet {
+        CardSet {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    pub fn intersection(&self, other: &CardSet) -> CardSet {
+        CardSet {
+            bits: self.bits & other.bits,
+        }
+    }
+
+    /// Returns the cards in `self` that are not in `other`.
+    pub fn difference(&self, other: &CardSet) -> CardSet {
+        CardSet {
+            bits: self.bits & !other.bits,
+        }
+    }
+
+    pub fn is_subset(&self, other: &CardSet) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    pub fn is_disjoint(&self, other: &CardSet) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    /// Returns the cards of one suit. A Joker suit returns the Joker, if
+    /// present.
+    pub fn suit(&self, suit: Suit) -> CardSet {
+        let mask = match suit {
+            Suit::JOKER => 1 << (Card::CODES - 1),
+            _ => SUIT_BITS << (suit as u8 * 13),
+        };
+        CardSet {
+            bits: self.bits & mask,
+        }
+    }
+
+    /// Returns how many cards of a suit the set holds.
+    pub fn count_suit(&self, suit: Suit) -> usize {
+        self.suit(suit).len()
+    }
+
+    /// Iterates over the cards in card order (by suit, aces low).
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter { bits: self.bits }
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, rhs: CardSet) -> CardSet {
+        self.union(&rhs)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, rhs: CardSet) -> CardSet {
+        self.intersection(&rhs)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, rhs: CardSet) -> CardSet {
+        self.difference(&rhs)
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    /// Returns every card, including the Joker, that is not in the set.
+    fn not(self) -> CardSet {
+        CardSet::from_bits(!self.bits)
+    }
+}
+
+/// Iterator over the cards in a [`CardSet`], lowest code first.
+#[derive(Debug, Clone)]
+pub struct CardSetIter {
+    bits: u64,
+}
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.bits == 0 {
+            return None;
+        }
+        let code = self.bits.trailing_zeros() as u8;
+        self.bits &= self.bits - 1;
+        Card::from_byte(code)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut set = CardSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl Display for CardSet {
+    /// Formats the set in short notation, such as `As Kd 7h`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cards: Vec<Card> = self.iter().collect();
+        write!(f, "{}", super::format_cards(&cards))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{deck_builder::DeckBuilder, parse_cards};
+
+    fn set(cards: &str) -> CardSet {
+        parse_cards(cards).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn cards_with_a_joker_suit_are_stored_as_the_joker() {
+        use crate::cards::Value;
+        let mut set = CardSet::new();
+        assert!(set.insert(Card::new(Suit::JOKER, Value::KING)));
+        assert!(!set.insert(Card::new(Suit::JOKER, Value::ACE)));
+        assert!(set.contains(&Card::joker()));
+        assert!(!set.contains(&Card::new(Suit::CLUBS, Value::ACE)));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn insert_remove_and_contains() {
+        let mut cards = CardSet::new();
+        let ace: Card = "As".parse().unwrap();
+
+        assert!(cards.insert(ace));
+        assert!(!cards.insert(ace));
+        assert!(cards.contains(&ace));
+        assert_eq!(cards.len(), 1);
+
+        assert!(cards.remove(&ace));
+        assert!(!cards.remove(&ace));
+        assert!(cards.is_empty());
+    }
+
+    #[test]
+    fn standard52_matches_the_deck_builder() {
+        let deck: CardSet = DeckBuilder::new()
+            .standard52()
+            .build()
+            .into_iter()
+            .collect();
+        assert_eq!(deck, CardSet::standard52());
+        assert_eq!(deck.len(), 52);
+        for suit in Suit::standard_suits() {
+            assert_eq!(deck.count_suit(suit), 13);
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set("As Kd 7h");
+        let b = set("Kd 2c");
+
+        assert_eq!(a | b, set("As Kd 7h 2c"));
+        assert_eq!(a & b, set("Kd"));
+        assert_eq!(a - b, set("As 7h"));
+        assert!(set("Kd").is_subset(&a));
+        assert!(set("2c").is_disjoint(&a));
+        assert_eq!((!CardSet::standard52()).len(), 1);
+    }
+
+    #[test]
+    fn iterates_in_card_order() {
+        let cards = set("Ks 2h Jk Ah 3c");
+        assert_eq!(cards.to_string(), "3c Ah 2h Ks Jk");
+        assert_eq!(cards.iter().len(), 5);
+
+        let mut sorted = parse_cards("Ks 2h Jk Ah 3c").unwrap();
+        sorted.sort();
+        assert_eq!(cards.iter().collect::<Vec<_>>(), sorted);
+    }
+
+    #[test]
+    fn suit_extracts_one_suit() {
+        let cards = set("As Ks 2h Jk");
+        assert_eq!(cards.suit(Suit::SPADES), set("As Ks"));
+        assert_eq!(cards.count_suit(Suit::JOKER), 1);
+        assert!(cards.suit(Suit::CLUBS).is_empty());
+    }
+}
@@ -3,68 +3,203 @@
 //! The `Deck` struct is typically created using the [`DeckBuilder`](crate::cards::deck_builder::DeckBuilder),
 //! allowing for flexible composition of custom or standard decks.
 
-use super::card::Card;
+use super::card::{parse_cards, Card, ParseCardError};
+use super::card_set::CardSet;
 use std::collections::VecDeque;
 use std::fmt::Display;
+use std::str::FromStr;
 
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 
 use crate::player::player::Player;
 
 /// Alias for identifying players when dealing cards.
 pub type PlayerId = u8;
 
+/// Largest packet run in one pass of [`Deck::overhand`].
+pub const OVERHAND_MAX_PACKET: usize = 8;
+
+/// An error from drawing, dealing or rearranging a deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeckError {
+    /// More cards were asked for than the deck holds.
+    NotEnoughCards { requested: usize, remaining: usize },
+    /// A position below the bottom of the deck.
+    InvalidPosition { position: usize, len: usize },
+}
+
+impl Display for DeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckError::NotEnoughCards {
+                requested,
+                remaining,
+            } => write!(
+                f,
+                "Not enough cards: {} needed but {} remain",
+                requested, remaining
+            ),
+            DeckError::InvalidPosition { position, len } => write!(
+                f,
+                "Position {} is outside a deck of {} cards",
+                position, len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
+
 /// A collection of cards with functionality for shuffling, drawing, and dealing.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Deck {
     cards: VecDeque<Card>,
+    /// How many copies of each card the deck holds, by card code.
+    copies: [u32; Card::CODES as usize],
+    /// The cards the deck holds at least one copy of.
+    held: CardSet,
 }
 
 impl Deck {
     /// Creates a deck from a vector of cards.
     pub fn from_cards(cards: Vec<Card>) -> Self {
-        Self {
-            cards: cards.into(),
-        }
+        let mut deck = Self {
+            cards: VecDeque::with_capacity(cards.len()),
+            copies: [0; Card::CODES as usize],
+            held: CardSet::new(),
+        };
+        deck.return_cards(cards);
+        deck
     }
 
     /// Creates a new deck using a set of cards.
-
     pub fn new(cards: Vec<Card>) -> Self {
         Self::from_cards(cards)
     }
 
     /// Returns the number of remaining cards in the deck.
-
     pub fn remaining_cards(&self) -> usize {
         self.cards.len()
     }
 
     /// Shuffles the cards in the deck.
-
     pub fn shuffle(&mut self) {
-        let mut v: Vec<_> = self.cards.iter().cloned().collect();
-        v.shuffle(&mut thread_rng());
-        self.cards = VecDeque::from(v);
+        self.wash(&mut thread_rng());
+    }
+
+    /// Shuffles the deck uniformly at random using `rng`, as a thorough wash
+    /// (spreading the cards face down and mixing) would.
+    pub fn wash<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.make_contiguous().shuffle(rng);
+    }
+
+    /// Performs one riffle shuffle using the Gilbert–Shannon–Reeds model.
+    ///
+    /// The deck is cut into two packets with a binomial split, then cards
+    /// drop from the bottom of each packet with probability proportional to
+    /// the packet's size. About seven riffles randomise a 52-card deck.
+    pub fn riffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let cut = (0..self.cards.len()).filter(|_| rng.gen_bool(0.5)).count();
+        let mut right = self.cards.split_off(cut);
+        let mut left = std::mem::take(&mut self.cards);
+
+        let mut riffled = VecDeque::with_capacity(left.len() + right.len());
+        while !left.is_empty() || !right.is_empty() {
+            let from_left = rng.gen_range(0..left.len() + right.len()) < left.len();
+            let card = if from_left {
+                left.pop_back()
+            } else {
+                right.pop_back()
+            };
+            riffled.extend(card);
+        }
+
+        riffled.make_contiguous().reverse();
+        self.cards = riffled;
+    }
+
+    /// Performs an overhand shuffle: small packets of one to
+    /// [`OVERHAND_MAX_PACKET`] cards are run from the top onto a new pile,
+    /// reversing the order of the packets.
+    pub fn overhand<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut sizes = Vec::new();
+        let mut remaining = self.cards.len();
+        while remaining > 0 {
+            let size = rng.gen_range(1..=OVERHAND_MAX_PACKET).min(remaining);
+            sizes.push(size);
+            remaining -= size;
+        }
+        self.run_packets(&sizes);
+    }
+
+    /// Performs a strip shuffle: the deck is stripped into `packets`
+    /// packets of random size, which end up in reverse order.
+    pub fn strip<R: Rng + ?Sized>(&mut self, packets: usize, rng: &mut R) {
+        let len = self.cards.len();
+        let packets = packets.clamp(1, len.max(1));
+
+        let mut cuts: Vec<usize> = rand::seq::index::sample(rng, len.max(1) - 1, packets - 1)
+            .into_iter()
+            .map(|i| i + 1)
+            .collect();
+        cuts.sort_unstable();
+        cuts.push(len);
+
+        let mut start = 0;
+        let sizes: Vec<usize> = cuts
+            .into_iter()
+            .map(|end| {
+                let size = end - start;
+                start = end;
+                size
+            })
+            .collect();
+        self.run_packets(&sizes);
+    }
+
+    /// Moves packets of the given sizes from the top onto a new pile.
+    fn run_packets(&mut self, sizes: &[usize]) {
+        let mut pile = VecDeque::with_capacity(self.cards.len());
+        for &size in sizes {
+            let rest = self.cards.split_off(size.min(self.cards.len()));
+            let packet = std::mem::replace(&mut self.cards, rest);
+            for card in packet.into_iter().rev() {
+                pile.push_front(card);
+            }
+        }
+        pile.extend(self.cards.drain(..));
+        self.cards = pile;
+    }
+
+    /// Cuts the deck at position `depth`, moving the top `depth` cards to the
+    /// bottom. Cutting at 0 or at the deck's length leaves it unchanged.
+    ///
+    /// # Errors
+    /// Returns [`DeckError::InvalidPosition`] if `depth` is past the end of
+    /// the deck.
+    pub fn cut(&mut self, depth: usize) -> Result<(), DeckError> {
+        self.check_position(depth)?;
+        self.cards.rotate_left(depth);
+        Ok(())
     }
 
     /// Deals a number of cards to the provided players.
     ///
     /// # Errors
-    /// Returns an error if the deck runs out of cards.
-    pub fn deal<'a, I>(&mut self, num_to_deal: u8, players: I) -> Result<(), String>
+    /// Returns [`DeckError::NotEnoughCards`] if the deck cannot deal every
+    /// player `num_to_deal` cards. Nothing is dealt in that case.
+    pub fn deal<'a, I>(&mut self, num_to_deal: u8, players: I) -> Result<(), DeckError>
     where
         I: IntoIterator<Item = &'a mut Player>,
     {
         let mut players: Vec<&'a mut Player> = players.into_iter().collect();
+        self.check_available(num_to_deal as usize * players.len())?;
 
         for _ in 0..num_to_deal {
             for player in players.iter_mut() {
-                if let Some(card) = self.cards.pop_front() {
+                if let Some(card) = self.draw() {
                     player.hand.add(card);
-                } else {
-                    return Err("Deck is out of cards!".into());
                 }
             }
         }
@@ -74,20 +209,152 @@ impl Deck {
 
     /// Draws a single card from the top of the deck.
     pub fn draw(&mut self) -> Option<Card> {
-        self.cards.pop_front()
+        let card = self.cards.pop_front()?;
+        self.count_out(&card);
+        Some(card)
+    }
+
+    /// Draws a single card from the bottom of the deck.
+    pub fn draw_bottom(&mut self) -> Option<Card> {
+        let card = self.cards.pop_back()?;
+        self.count_out(&card);
+        Some(card)
+    }
+
+    /// Draws `count` cards from the top of the deck, top card first.
+    ///
+    /// # Errors
+    /// Returns [`DeckError::NotEnoughCards`] if fewer than `count` remain.
+    /// Nothing is drawn in that case.
+    pub fn draw_many(&mut self, count: usize) -> Result<Vec<Card>, DeckError> {
+        self.check_available(count)?;
+        let drawn: Vec<Card> = self.cards.drain(..count).collect();
+        drawn.iter().for_each(|card| self.count_out(card));
+        Ok(drawn)
+    }
+
+    /// Burns `count` cards from the top of the deck into `muck`.
+    ///
+    /// # Errors
+    /// Returns [`DeckError::NotEnoughCards`] if fewer than `count` remain.
+    /// Nothing is burned in that case.
+    pub fn burn(&mut self, count: usize, muck: &mut Vec<Card>) -> Result<(), DeckError> {
+        muck.extend(self.draw_many(count)?);
+        Ok(())
     }
 
     /// Returns a reference to the top card without removing it.
     pub fn peek(&self) -> Option<&Card> {
         self.cards.front()
     }
+
+    /// Returns the top `count` cards without removing them, top card first.
+    ///
+    /// # Errors
+    /// Returns [`DeckError::NotEnoughCards`] if fewer than `count` remain.
+    pub fn peek_many(&self, count: usize) -> Result<Vec<&Card>, DeckError> {
+        self.check_available(count)?;
+        Ok(self.cards.range(..count).collect())
+    }
+
     /// Adds a card to the bottom of the deck.
     pub fn add_card(&mut self, card: Card) {
+        self.count_in(card);
         self.cards.push_back(card);
     }
+
+    /// Adds a card to the top of the deck.
+    pub fn add_card_top(&mut self, card: Card) {
+        self.count_in(card);
+        self.cards.push_front(card);
+    }
+
+    /// Inserts a card so that `position` cards lie above it.
+    ///
+    /// # Errors
+    /// Returns [`DeckError::InvalidPosition`] if `position` is past the end
+    /// of the deck.
+    pub fn insert_at(&mut self, position: usize, card: Card) -> Result<(), DeckError> {
+        self.check_position(position)?;
+        self.count_in(card);
+        self.cards.insert(position, card);
+        Ok(())
+    }
+
+    /// Inserts a card at a uniformly random position, including the top and
+    /// the bottom.
+    pub fn insert_random<R: Rng + ?Sized>(&mut self, card: Card, rng: &mut R) {
+        let position = rng.gen_range(0..=self.cards.len());
+        self.count_in(card);
+        self.cards.insert(position, card);
+    }
+
+    /// Returns cards to the bottom of the deck in the order given, as when
+    /// turning over a solitaire waste pile to form a new stock.
+    pub fn return_cards<I: IntoIterator<Item = Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.add_card(card);
+        }
+    }
+
+    /// Shuffles a discard pile and places it under the remaining cards,
+    /// leaving `discards` empty.
+    pub fn reshuffle_discards<R: Rng + ?Sized>(&mut self, discards: &mut Vec<Card>, rng: &mut R) {
+        discards.shuffle(rng);
+        self.return_cards(discards.drain(..));
+    }
+
+    fn count_in(&mut self, card: Card) {
+        self.copies[card.to_byte() as usize] += 1;
+        self.held.insert(card);
+    }
+
+    fn count_out(&mut self, card: &Card) {
+        let copies = &mut self.copies[card.to_byte() as usize];
+        *copies -= 1;
+        if *copies == 0 {
+            self.held.remove(card);
+        }
+    }
+
+    fn check_available(&self, requested: usize) -> Result<(), DeckError> {
+        if requested > self.cards.len() {
+            return Err(DeckError::NotEnoughCards {
+                requested,
+                remaining: self.cards.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_position(&self, position: usize) -> Result<(), DeckError> {
+        if position > self.cards.len() {
+            return Err(DeckError::InvalidPosition {
+                position,
+                len: self.cards.len(),
+            });
+        }
+        Ok(())
+    }
+
     /// Checks if a card is contained in the deck.
     pub fn contains(&self, card: &Card) -> bool {
-        self.cards.contains(card)
+        self.held.contains(card)
+    }
+
+    /// Returns how many copies of a card the deck holds.
+    pub fn count(&self, card: &Card) -> usize {
+        self.copies[card.to_byte() as usize] as usize
+    }
+
+    /// Returns the distinct cards in the deck as a [`CardSet`].
+    pub fn card_set(&self) -> CardSet {
+        self.held
+    }
+
+    /// Iterates over the cards from the top of the deck.
+    pub fn iter(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter()
     }
 
     /// Returns `true` if the deck has no remaining cards.
@@ -103,9 +370,26 @@ impl Deck {
 
 impl Display for Deck {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.cards.iter().fold(Ok(()), |result, card| {
-            result.and_then(|_| write!(f, "|{}|", card))
-        })
+        self.cards
+            .iter()
+            .try_for_each(|card| write!(f, "|{}|", card))
+    }
+}
+
+impl FromStr for Deck {
+    type Err = ParseCardError;
+
+    /// Parses a deck from short notation; the first card listed is the top
+    /// of the deck.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{Card, Deck, Suit, Value};
+    /// let mut deck: Deck = "Kd 7h".parse().unwrap();
+    /// assert_eq!(deck.draw(), Some(Card::new(Suit::DIAMONDS, Value::KING)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_cards(parse_cards(s)?))
     }
 }
 
@@ -119,7 +403,9 @@ impl IntoIterator for Deck {
 }
 #[cfg(test)]
 mod tests {
-    use crate::cards::deck_builder::DeckBuilder;
+    use super::DeckError;
+    use crate::cards::{deck_builder::DeckBuilder, parse_cards, Deck};
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn deck_new_standard52_has_52_cards() {
@@ -127,6 +413,28 @@ mod tests {
         assert_eq!(deck.remaining_cards(), 52);
     }
 
+    #[test]
+    fn contains_follows_every_copy_of_a_card() {
+        let mut deck: Deck = "As Kd As".parse().unwrap();
+        let ace = "As".parse().unwrap();
+        assert_eq!(deck.count(&ace), 2);
+
+        deck.draw();
+        assert!(deck.contains(&ace));
+        deck.draw_bottom();
+        assert!(!deck.contains(&ace));
+        assert_eq!(
+            deck.card_set(),
+            parse_cards("Kd").unwrap().into_iter().collect()
+        );
+
+        deck.add_card_top(ace);
+        assert!(deck.contains(&ace));
+        deck.burn(2, &mut Vec::new()).unwrap();
+        assert!(deck.is_empty());
+        assert!(deck.card_set().is_empty());
+    }
+
     #[test]
     fn deck_draw_removes_card() {
         let mut deck = DeckBuilder::new().standard52().build();
@@ -154,4 +462,117 @@ mod tests {
         deck = builder.build();
         assert_eq!(deck.remaining_cards(), 52);
     }
+
+    #[test]
+    fn deck_round_trips_through_short_notation() {
+        use crate::cards::format_cards;
+
+        let deck = DeckBuilder::new().standard52().build();
+        let text = format_cards(&deck.clone().into_iter().collect::<Vec<_>>());
+        assert_eq!(text.parse::<Deck>(), Ok(deck));
+    }
+
+    #[test]
+    fn deck_cut_moves_the_top_packet_to_the_bottom() {
+        let mut deck: Deck = "Ac 2c 3c 4c 5c".parse().unwrap();
+        deck.cut(2).unwrap();
+        assert_eq!(deck, "3c 4c 5c Ac 2c".parse().unwrap());
+
+        deck.cut(5).unwrap();
+        assert_eq!(deck, "3c 4c 5c Ac 2c".parse().unwrap());
+        assert_eq!(
+            deck.cut(6),
+            Err(DeckError::InvalidPosition {
+                position: 6,
+                len: 5
+            })
+        );
+    }
+
+    #[test]
+    fn deck_riffle_keeps_every_card() {
+        let original = DeckBuilder::new().standard52().build();
+        let mut deck = original.clone();
+        deck.riffle(&mut StdRng::seed_from_u64(1));
+        assert_eq!(deck.card_set(), original.card_set());
+        assert_ne!(deck, original);
+    }
+
+    #[test]
+    fn deck_burns_and_draws_with_typed_errors() {
+        let mut deck: Deck = "Ac 2c 3c 4c 5c".parse().unwrap();
+        let mut muck = Vec::new();
+
+        deck.burn(1, &mut muck).unwrap();
+        assert_eq!(muck, parse_cards("Ac").unwrap());
+        assert_eq!(deck.draw_bottom(), Some("5c".parse().unwrap()));
+        assert_eq!(deck.draw_many(2), Ok(parse_cards("2c 3c").unwrap()));
+
+        let short = Err(DeckError::NotEnoughCards {
+            requested: 2,
+            remaining: 1,
+        });
+        assert_eq!(deck.burn(2, &mut muck), short);
+        assert_eq!(deck.draw_many(2).map(|_| ()), short);
+        assert_eq!(deck.len(), 1);
+        assert_eq!(muck.len(), 1);
+    }
+
+    #[test]
+    fn deck_peek_many_leaves_the_deck_alone() {
+        let deck: Deck = "Ac 2c 3c".parse().unwrap();
+        let top: Vec<String> = deck
+            .peek_many(2)
+            .unwrap()
+            .iter()
+            .map(|c| c.short())
+            .collect();
+        assert_eq!(top, ["Ac", "2c"]);
+        assert_eq!(deck.len(), 3);
+        assert!(deck.peek_many(4).is_err());
+    }
+
+    #[test]
+    fn deck_inserts_cards_anywhere() {
+        let mut deck: Deck = "Ac 2c".parse().unwrap();
+        deck.insert_at(1, "Kd".parse().unwrap()).unwrap();
+        deck.add_card_top("Qd".parse().unwrap());
+        assert_eq!(deck, "Qd Ac Kd 2c".parse().unwrap());
+        assert!(deck.insert_at(5, "Jd".parse().unwrap()).is_err());
+
+        let joker = "Jk".parse().unwrap();
+        deck.insert_random(joker, &mut StdRng::seed_from_u64(3));
+        assert_eq!(deck.len(), 5);
+        assert!(deck.contains(&joker));
+    }
+
+    #[test]
+    fn deck_recycles_discards_under_the_stock() {
+        let mut deck: Deck = "Ac 2c".parse().unwrap();
+        deck.return_cards(parse_cards("3c 4c").unwrap());
+        assert_eq!(deck, "Ac 2c 3c 4c".parse().unwrap());
+
+        let mut discards = parse_cards("5c 6c 7c").unwrap();
+        deck.reshuffle_discards(&mut discards, &mut StdRng::seed_from_u64(5));
+        assert!(discards.is_empty());
+        assert_eq!(deck.len(), 7);
+        assert_eq!(deck.draw_many(4), Ok(parse_cards("Ac 2c 3c 4c").unwrap()));
+    }
+
+    #[test]
+    fn deck_deal_fails_without_dealing_when_short() {
+        use crate::player::player::Player;
+
+        let mut players = [Player::new("a".into()), Player::new("b".into())];
+        let mut deck: Deck = "Ac 2c 3c".parse().unwrap();
+        assert_eq!(
+            deck.deal(2, players.iter_mut()),
+            Err(DeckError::NotEnoughCards {
+                requested: 4,
+                remaining: 3
+            })
+        );
+        assert_eq!(deck.len(), 3);
+        assert!(players.iter().all(|p| p.hand.is_empty()));
+    }
 }
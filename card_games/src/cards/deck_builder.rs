@@ -2,6 +2,8 @@
 //!
 //! Use [`DeckBuilder`] when you want to configure a deck with specific
 //! characteristics such as including jokers or repeating the deck multiple times.
+//! Presets cover the common stripped decks (piquet, euchre, pinochle, Spanish),
+//! and filters such as [`DeckBuilder::without_values`] shape any other deck.
 
 use super::{
     card::{Card, Suit, Value},
@@ -36,6 +38,89 @@ impl DeckBuilder {
         self
     }
 
+    /// Initializes the builder with a 32-card piquet deck: 7 through ace in
+    /// each suit.
+    pub fn piquet(self) -> Self {
+        self.standard52().without_values(&[
+            Value::TWO,
+            Value::THREE,
+            Value::FOUR,
+            Value::FIVE,
+            Value::SIX,
+        ])
+    }
+
+    /// Initializes the builder with a 24-card euchre deck: 9 through ace in
+    /// each suit.
+    pub fn euchre(self) -> Self {
+        self.piquet().without_values(&[Value::SEVEN, Value::EIGHT])
+    }
+
+    /// Initializes the builder with a 48-card pinochle deck: two copies of
+    /// every card from 9 through ace.
+    pub fn pinochle(mut self) -> Self {
+        self = self.euchre();
+        self.base_cards = self
+            .base_cards
+            .iter()
+            .flat_map(|card| [*card, *card])
+            .collect();
+        self
+    }
+
+    /// Initializes the builder with a 48-card Spanish deck: a standard deck
+    /// with the tens removed (face cards remain).
+    pub fn spanish48(self) -> Self {
+        self.standard52().without_values(&[Value::TEN])
+    }
+
+    /// Initializes the builder with a Spanish 21 shoe: `decks` Spanish
+    /// 48-card decks.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::deck_builder::DeckBuilder;
+    /// let shoe = DeckBuilder::new().spanish21(6).build();
+    /// assert_eq!(shoe.len(), 288);
+    /// ```
+    pub fn spanish21(self, decks: usize) -> Self {
+        self.spanish48().repeat(decks)
+    }
+
+    /// Removes every card with one of the given values from the cards chosen
+    /// so far. Jokers have the value [`Value::JOKER`].
+    pub fn without_values(mut self, values: &[Value]) -> Self {
+        self.base_cards
+            .retain(|card| !values.contains(card.value()));
+        if values.contains(&Value::JOKER) {
+            self.include_jokers = false;
+        }
+        self
+    }
+
+    /// Keeps only cards of the given suits from the cards chosen so far.
+    /// Jokers have the suit [`Suit::JOKER`], so they go unless it is listed.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{deck_builder::DeckBuilder, Suit};
+    /// let deck = DeckBuilder::new().standard52().only_suits(&[Suit::SPADES]).build();
+    /// assert_eq!(deck.len(), 13);
+    /// ```
+    pub fn only_suits(mut self, suits: &[Suit]) -> Self {
+        self.base_cards.retain(|card| suits.contains(card.suit()));
+        if !suits.contains(&Suit::JOKER) {
+            self.include_jokers = false;
+        }
+        self
+    }
+
+    /// Adds a single card to each repetition of the deck.
+    pub fn with_card(mut self, card: Card) -> Self {
+        self.base_cards.push(card);
+        self
+    }
+
     /// Includes two jokers per repetition of the deck.
     pub fn with_jokers(mut self) -> Self {
         self.include_jokers = true;
@@ -99,4 +184,72 @@ mod tests {
         let jokers = deck.into_iter().filter(|c| c.is_joker()).count();
         assert_eq!(jokers, 6);
     }
+
+    #[test]
+    fn filters_apply_to_jokers_chosen_so_far() {
+        let jokers =
+            |builder: DeckBuilder| builder.build().into_iter().filter(|c| c.is_joker()).count();
+
+        let base = DeckBuilder::new().standard52().with_jokers();
+        assert_eq!(jokers(base.clone().without_values(&[Value::JOKER])), 0);
+        assert_eq!(jokers(base.clone().without_values(&[Value::TWO])), 2);
+        assert_eq!(jokers(base.clone().only_suits(&[Suit::SPADES])), 0);
+        assert_eq!(jokers(base.only_suits(&[Suit::SPADES, Suit::JOKER])), 2);
+
+        // Jokers asked for after a filter are still added.
+        let late = DeckBuilder::new()
+            .standard52()
+            .only_suits(&[Suit::HEARTS])
+            .with_jokers();
+        assert_eq!(jokers(late), 2);
+    }
+
+    fn count_value(deck: Deck, value: Value) -> usize {
+        deck.into_iter().filter(|c| *c.value() == value).count()
+    }
+
+    #[test]
+    fn build_piquet_and_euchre() {
+        let piquet = DeckBuilder::new().piquet().build();
+        assert_eq!(piquet.len(), 32);
+        assert_eq!(count_value(piquet.clone(), Value::SIX), 0);
+        assert_eq!(count_value(piquet, Value::ACE), 4);
+
+        let euchre = DeckBuilder::new().euchre().build();
+        assert_eq!(euchre.len(), 24);
+        assert_eq!(count_value(euchre, Value::EIGHT), 0);
+    }
+
+    #[test]
+    fn build_pinochle_doubles_nine_to_ace() {
+        let deck = DeckBuilder::new().pinochle().build();
+        assert_eq!(deck.len(), 48);
+        assert_eq!(count_value(deck.clone(), Value::NINE), 8);
+        assert_eq!(deck.card_set().len(), 24);
+    }
+
+    #[test]
+    fn build_spanish_decks_without_tens() {
+        let deck = DeckBuilder::new().spanish48().build();
+        assert_eq!(deck.len(), 48);
+        assert_eq!(count_value(deck.clone(), Value::TEN), 0);
+        assert_eq!(count_value(deck, Value::KING), 4);
+
+        let shoe = DeckBuilder::new().spanish21(8).build();
+        assert_eq!(shoe.len(), 384);
+    }
+
+    #[test]
+    fn filters_compose() {
+        let deck = DeckBuilder::new()
+            .standard52()
+            .only_suits(&[Suit::HEARTS])
+            .without_values(&[Value::ACE])
+            .with_card(Card::new(Suit::SPADES, Value::QUEEN))
+            .repeat(2)
+            .build();
+        assert_eq!(deck.len(), 26);
+        assert!(deck.contains(&Card::new(Suit::SPADES, Value::QUEEN)));
+        assert!(!deck.contains(&Card::new(Suit::HEARTS, Value::ACE)));
+    }
 }
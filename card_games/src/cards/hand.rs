@@ -2,12 +2,14 @@
 //!
 //! This struct provides utility methods for managing and displaying a hand of cards,
 //! such as adding, clearing, inspecting, and printing the cards.
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
-use super::card::Card;
+use super::{
+    card::{parse_cards, Card, ParseCardError, Suit, Value},
+    order::RankOrder,
+};
 
 /// Represents a hand of playing cards.
-
 #[derive(Debug, Clone)]
 pub struct Hand {
     cards: Vec<Card>,
@@ -39,34 +41,107 @@ impl Hand {
     }
 
     /// Clears all cards from the hand.
-
     pub fn clear_hand(&mut self) {
         self.cards.clear()
     }
 
     /// Returns the number of cards in the hand.
-
     pub fn len(&self) -> usize {
         self.cards.len()
     }
 
     /// Returns `true` if the hand contains no cards.
-
     pub fn is_empty(&self) -> bool {
         self.cards.is_empty()
     }
 
     /// Returns a slice of the cards currently in the hand.
-
     pub fn cards(&self) -> &[Card] {
         &self.cards
     }
+
+    /// Removes the first card equal to `card`, returning it if present.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{Card, Suit, Value, hand::Hand};
+    /// let mut hand = Hand::new();
+    /// hand.add(Card::new(Suit::SPADES, Value::ACE));
+    /// assert!(hand.remove(&Card::new(Suit::SPADES, Value::ACE)).is_some());
+    /// assert!(hand.is_empty());
+    /// ```
+    pub fn remove(&mut self, card: &Card) -> Option<Card> {
+        let index = self.cards.iter().position(|c| c == card)?;
+        Some(self.cards.remove(index))
+    }
+
+    /// Sorts the hand in place using a key extraction function.
+    pub fn sort_by_key<K: Ord>(&mut self, f: impl FnMut(&Card) -> K) {
+        self.cards.sort_by_key(f);
+    }
+
+    /// Sorts the hand in place using a game's card order.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{hand::Hand, order::RankOrder};
+    /// let mut hand: Hand = "Ac 2c Kd".parse().unwrap();
+    /// hand.sort_by(&RankOrder::ace_high());
+    /// assert_eq!(hand.to_string(), "|Two of ♣||Ace of ♣||King of ♦|");
+    /// ```
+    pub fn sort_by(&mut self, order: &RankOrder) {
+        self.cards.sort_by(|a, b| order.compare(a, b));
+    }
+
+    /// Groups the cards by suit, in suit order. Each group keeps the order
+    /// the cards have in the hand.
+    pub fn group_by_suit(&self) -> BTreeMap<Suit, Vec<Card>> {
+        let mut groups: BTreeMap<Suit, Vec<Card>> = BTreeMap::new();
+        for card in &self.cards {
+            groups.entry(*card.suit()).or_default().push(*card);
+        }
+        groups
+    }
+
+    /// Groups the cards by value in [`Value`] order: jokers first, then aces
+    /// up to kings. Each group keeps the order the cards have in the hand.
+    pub fn group_by_rank(&self) -> BTreeMap<Value, Vec<Card>> {
+        let mut groups: BTreeMap<Value, Vec<Card>> = BTreeMap::new();
+        for card in &self.cards {
+            groups.entry(*card.value()).or_default().push(*card);
+        }
+        groups
+    }
+}
+
+impl Default for Hand {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Display for Hand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.cards.iter().fold(Ok(()), |result, card| {
-            result.and_then(|_| write!(f, "|{}|", card))
+        self.cards
+            .iter()
+            .try_for_each(|card| write!(f, "|{}|", card))
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseCardError;
+
+    /// Parses a hand from short notation, such as `"As Kd 7h"`.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::hand::Hand;
+    /// let hand: Hand = "As Kd 7h".parse().unwrap();
+    /// assert_eq!(hand.len(), 3);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Hand {
+            cards: parse_cards(s)?,
         })
     }
 }
@@ -82,7 +157,6 @@ impl std::ops::Deref for Hand {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cards::card::{Suit, Value};
 
     #[test]
     fn hand_adds_and_clears_cards() {
@@ -104,6 +178,63 @@ mod tests {
         assert!(!hand.is_empty());
     }
 
+    #[test]
+    fn hand_remove_takes_out_one_matching_card() {
+        let mut hand = Hand::new();
+        hand.add(Card::new(Suit::SPADES, Value::ACE));
+        hand.add(Card::new(Suit::SPADES, Value::ACE));
+
+        assert!(hand.remove(&Card::new(Suit::SPADES, Value::ACE)).is_some());
+        assert_eq!(hand.len(), 1);
+        assert!(hand.remove(&Card::new(Suit::HEARTS, Value::ACE)).is_none());
+    }
+
+    #[test]
+    fn hand_parses_from_short_notation() {
+        let hand: Hand = "Ah 2c".parse().unwrap();
+        assert_eq!(
+            hand.cards(),
+            &[
+                Card::new(Suit::HEARTS, Value::ACE),
+                Card::new(Suit::CLUBS, Value::TWO)
+            ]
+        );
+        assert!("Ah 2z".parse::<Hand>().is_err());
+    }
+
+    #[test]
+    fn hand_sorts_by_rank_order() {
+        let mut hand: Hand = "Kh As 2h".parse().unwrap();
+        hand.sort_by(&RankOrder::ace_high().rank_major());
+        assert_eq!(crate::cards::format_cards(&hand), "2h Kh As");
+    }
+
+    #[test]
+    fn hand_groups_by_suit_and_rank() {
+        let hand: Hand = "Kh 7s 2h 7c".parse().unwrap();
+
+        let by_suit = hand.group_by_suit();
+        assert_eq!(
+            by_suit.keys().copied().collect::<Vec<_>>(),
+            vec![Suit::CLUBS, Suit::HEARTS, Suit::SPADES]
+        );
+        assert_eq!(by_suit[&Suit::HEARTS].len(), 2);
+
+        let by_rank = hand.group_by_rank();
+        assert_eq!(by_rank[&Value::SEVEN].len(), 2);
+        assert_eq!(by_rank.keys().next(), Some(&Value::TWO));
+
+        let with_joker: Hand = "Kh Jk As".parse().unwrap();
+        assert_eq!(
+            with_joker
+                .group_by_rank()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![Value::JOKER, Value::ACE, Value::KING]
+        );
+    }
+
     #[test]
     fn hand_display_formats_all_cards() {
         let mut hand = Hand::new();
@@ -1,7 +1,12 @@
 pub mod card;
+pub mod card_set;
 pub mod deck;
 pub mod deck_builder;
 pub mod hand;
+pub mod order;
+pub mod shuffle;
 
-pub use card::{Card, Suit, Value};
-pub use deck::Deck;
+pub use card::{format_cards, parse_cards, Card, ParseCardError, Suit, Value};
+pub use card_set::CardSet;
+pub use deck::{Deck, DeckError};
+pub use order::RankOrder;
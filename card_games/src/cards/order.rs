@@ -0,0 +1,231 @@
+//! Game-specific card orderings.
+//!
+//! The derived `Ord` on [`Card`] sorts by suit and then ace-low value, which
+//! suits few games. A [`RankOrder`] describes how a particular game ranks
+//! cards: whether aces are high, how suits rank against each other, which
+//! suit (if any) is trump, and whether hands sort by suit or by rank first.
+
+use std::cmp::Ordering;
+
+use super::card::{Card, Suit, Value};
+
+/// Whether aces rank below the two or above the king.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aces {
+    Low,
+    High,
+}
+
+/// Which part of a card decides the order first when sorting a hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMajor {
+    /// Group by suit, then order by rank within each suit.
+    Suit,
+    /// Order by rank, breaking ties by suit.
+    Rank,
+}
+
+/// How a game ranks cards.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{order::RankOrder, Card, Suit, Value};
+/// let ace = Card::new(Suit::CLUBS, Value::ACE);
+/// let king = Card::new(Suit::CLUBS, Value::KING);
+/// assert!(RankOrder::ace_low().compare(&ace, &king).is_lt());
+/// assert!(RankOrder::ace_high().compare(&ace, &king).is_gt());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RankOrder {
+    aces: Aces,
+    /// Standard suits from lowest to highest.
+    suits: [Suit; 4],
+    trump: Option<Suit>,
+    major: SortMajor,
+}
+
+impl RankOrder {
+    /// Bridge suit order: ♣ < ♦ < ♥ < ♠.
+    pub const BRIDGE_SUITS: [Suit; 4] = [Suit::CLUBS, Suit::DIAMONDS, Suit::HEARTS, Suit::SPADES];
+
+    /// Aces low, suits in bridge order, grouped by suit.
+    pub fn ace_low() -> Self {
+        RankOrder {
+            aces: Aces::Low,
+            suits: Self::BRIDGE_SUITS,
+            trump: None,
+            major: SortMajor::Suit,
+        }
+    }
+
+    /// Aces high, suits in bridge order, grouped by suit.
+    pub fn ace_high() -> Self {
+        RankOrder {
+            aces: Aces::High,
+            ..Self::ace_low()
+        }
+    }
+
+    /// The bridge order: aces high with ♣ < ♦ < ♥ < ♠.
+    pub fn bridge() -> Self {
+        Self::ace_high()
+    }
+
+    /// An ace-high order in which `trump` outranks every other suit.
+    pub fn trump(trump: Suit) -> Self {
+        Self::ace_high().with_trump(trump)
+    }
+
+    /// Makes `trump` the highest suit.
+    pub fn with_trump(mut self, trump: Suit) -> Self {
+        self.trump = Some(trump);
+        self
+    }
+
+    /// Ranks suits in the given order, lowest first.
+    pub fn with_suits(mut self, suits: [Suit; 4]) -> Self {
+        self.suits = suits;
+        self
+    }
+
+    /// Sorts by rank first and suit second, as poker hands are shown.
+    pub fn rank_major(mut self) -> Self {
+        self.major = SortMajor::Rank;
+        self
+    }
+
+    pub fn aces(&self) -> Aces {
+        self.aces
+    }
+
+    pub fn trump_suit(&self) -> Option<Suit> {
+        self.trump
+    }
+
+    /// Returns the strength of a value: 1–13 with aces low, 2–14 with aces
+    /// high. Jokers rank above everything.
+    pub fn value_rank(&self, value: &Value) -> u8 {
+        match (value, self.aces) {
+            (Value::JOKER, _) => 15,
+            (Value::ACE, Aces::High) => 14,
+            _ => value.rank().unwrap_or(0),
+        }
+    }
+
+    /// Returns the strength of a suit. Trump ranks above the other suits,
+    /// and jokers above trump.
+    pub fn suit_rank(&self, suit: &Suit) -> u8 {
+        if *suit == Suit::JOKER {
+            return 6;
+        }
+        if self.trump == Some(*suit) {
+            return 5;
+        }
+        self.suits
+            .iter()
+            .position(|s| s == suit)
+            .map(|i| i as u8 + 1)
+            .unwrap_or(0)
+    }
+
+    /// Returns a key that sorts cards in this order.
+    pub fn key(&self, card: &Card) -> (u8, u8) {
+        let suit = self.suit_rank(card.suit());
+        let value = self.value_rank(card.value());
+        match self.major {
+            SortMajor::Suit => (suit, value),
+            SortMajor::Rank => (value, suit),
+        }
+    }
+
+    pub fn compare(&self, a: &Card, b: &Card) -> Ordering {
+        self.key(a).cmp(&self.key(b))
+    }
+
+    /// Returns the index of the card that wins a trick: the highest trump if
+    /// any were played, otherwise the highest card of the suit led.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::cards::{order::RankOrder, parse_cards, Suit};
+    /// let trick = parse_cards("Kh Ah 2s 9h").unwrap();
+    /// assert_eq!(RankOrder::ace_high().trick_winner(&trick), Some(1));
+    /// assert_eq!(RankOrder::trump(Suit::SPADES).trick_winner(&trick), Some(2));
+    /// ```
+    pub fn trick_winner(&self, trick: &[Card]) -> Option<usize> {
+        let led = *trick.first()?.suit();
+        let deciding = match self.trump {
+            Some(trump) if trick.iter().any(|c| *c.suit() == trump) => trump,
+            _ => led,
+        };
+
+        trick
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| *card.suit() == deciding)
+            .max_by_key(|(_, card)| self.value_rank(card.value()))
+            .map(|(i, _)| i)
+    }
+}
+
+impl Default for RankOrder {
+    fn default() -> Self {
+        Self::ace_low()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{format_cards, parse_cards};
+
+    fn sorted(cards: &str, order: RankOrder) -> String {
+        let mut cards = parse_cards(cards).unwrap();
+        cards.sort_by(|a, b| order.compare(a, b));
+        format_cards(&cards)
+    }
+
+    #[test]
+    fn ace_high_and_low_orders() {
+        assert_eq!(sorted("Kc Ac 2c", RankOrder::ace_low()), "Ac 2c Kc");
+        assert_eq!(sorted("Kc Ac 2c", RankOrder::ace_high()), "2c Kc Ac");
+    }
+
+    #[test]
+    fn bridge_order_groups_suits() {
+        assert_eq!(
+            sorted("As 2h Kc 3d Jk", RankOrder::bridge()),
+            "Kc 3d 2h As Jk"
+        );
+    }
+
+    #[test]
+    fn trump_suit_sorts_last_and_wins_tricks() {
+        let order = RankOrder::trump(Suit::DIAMONDS);
+        assert_eq!(sorted("2d As Kh", order), "Kh As 2d");
+        assert_eq!(
+            order.trick_winner(&parse_cards("As Ks 3d").unwrap()),
+            Some(2)
+        );
+        assert_eq!(order.trick_winner(&[]), None);
+    }
+
+    #[test]
+    fn rank_major_sorts_like_poker_hands() {
+        assert_eq!(
+            sorted("As Kd Ah 7c Kc", RankOrder::ace_high().rank_major()),
+            "7c Kc Kd Ah As"
+        );
+    }
+
+    #[test]
+    fn custom_suit_order() {
+        let order = RankOrder::ace_high().with_suits([
+            Suit::SPADES,
+            Suit::HEARTS,
+            Suit::DIAMONDS,
+            Suit::CLUBS,
+        ]);
+        assert_eq!(sorted("2c 2s 2h", order), "2s 2h 2c");
+    }
+}
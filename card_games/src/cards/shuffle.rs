@@ -0,0 +1,271 @@
+//! Composite shuffle procedures and shuffle-quality analysis.
+//!
+//! The individual physical shuffles live on [`Deck`] ([`Deck::riffle`],
+//! [`Deck::strip`], [`Deck::overhand`], [`Deck::cut`], [`Deck::wash`]). A
+//! [`ShuffleProcedure`] chains them the way a dealer would, and [`analyze`]
+//! measures how much of the original order survived.
+
+use std::collections::{HashMap, VecDeque};
+
+use rand::Rng;
+
+use super::{card::Card, deck::Deck};
+
+/// One step of a shuffle procedure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShuffleStep {
+    /// A uniform random shuffle.
+    Wash,
+    /// A Gilbert–Shannon–Reeds riffle.
+    Riffle,
+    Overhand,
+    Strip {
+        packets: usize,
+    },
+    /// Cut at a fixed depth from the top. Depths past the end of the deck
+    /// leave it unchanged.
+    Cut {
+        depth: usize,
+    },
+    /// Cut somewhere in the middle half of the deck.
+    RandomCut,
+}
+
+impl ShuffleStep {
+    pub fn apply<R: Rng + ?Sized>(&self, deck: &mut Deck, rng: &mut R) {
+        match *self {
+            ShuffleStep::Wash => deck.wash(rng),
+            ShuffleStep::Riffle => deck.riffle(rng),
+            ShuffleStep::Overhand => deck.overhand(rng),
+            ShuffleStep::Strip { packets } => deck.strip(packets, rng),
+            ShuffleStep::Cut { depth } => {
+                let depth = depth.min(deck.len());
+                deck.cut(depth).expect("depth is within the deck");
+            }
+            ShuffleStep::RandomCut => {
+                let len = deck.len();
+                if len >= 4 {
+                    let depth = rng.gen_range(len / 4..=len * 3 / 4);
+                    deck.cut(depth).expect("depth is within the deck");
+                }
+            }
+        }
+    }
+}
+
+/// A sequence of shuffles applied in order.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{deck_builder::DeckBuilder, shuffle::ShuffleProcedure};
+/// let mut deck = DeckBuilder::new().standard52().build();
+/// ShuffleProcedure::casino().apply(&mut deck, &mut rand::thread_rng());
+/// assert_eq!(deck.len(), 52);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShuffleProcedure {
+    steps: Vec<ShuffleStep>,
+}
+
+impl ShuffleProcedure {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a step to the end of the procedure.
+    pub fn then(mut self, step: ShuffleStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// `count` riffles in a row.
+    pub fn riffles(count: usize) -> Self {
+        ShuffleProcedure {
+            steps: vec![ShuffleStep::Riffle; count],
+        }
+    }
+
+    /// A typical casino hand shuffle: riffle, riffle, strip, riffle, cut.
+    pub fn casino() -> Self {
+        Self::new()
+            .then(ShuffleStep::Riffle)
+            .then(ShuffleStep::Riffle)
+            .then(ShuffleStep::Strip { packets: 4 })
+            .then(ShuffleStep::Riffle)
+            .then(ShuffleStep::RandomCut)
+    }
+
+    pub fn steps(&self) -> &[ShuffleStep] {
+        &self.steps
+    }
+
+    pub fn apply<R: Rng + ?Sized>(&self, deck: &mut Deck, rng: &mut R) {
+        for step in &self.steps {
+            step.apply(deck, rng);
+        }
+    }
+}
+
+/// How much order survived a shuffle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShuffleReport {
+    pub cards: usize,
+    /// Number of maximal runs of cards that appear in their original
+    /// relative order. An unshuffled deck has 1; a random deck of `n`
+    /// cards averages `(n + 1) / 2`.
+    pub rising_sequences: usize,
+    /// Originally adjacent pairs that are still adjacent, in the same order.
+    pub adjacent_pairs_kept: usize,
+    /// Cards still at their original position.
+    pub fixed_points: usize,
+}
+
+impl ShuffleReport {
+    /// Fraction of originally adjacent pairs still together. A random deck
+    /// keeps about `1 / n`.
+    pub fn adjacency_retention(&self) -> f64 {
+        if self.cards < 2 {
+            return 1.0;
+        }
+        self.adjacent_pairs_kept as f64 / (self.cards - 1) as f64
+    }
+
+    /// Average rising sequences of a uniformly random deck of this size.
+    pub fn expected_random_rising_sequences(&self) -> f64 {
+        (self.cards as f64 + 1.0) / 2.0
+    }
+}
+
+/// Compares a deck before and after shuffling.
+///
+/// Duplicate cards (as in a multi-deck shoe) are matched in order. Returns
+/// `None` if `after` is not a rearrangement of `before`.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{deck_builder::DeckBuilder, shuffle::analyze};
+/// let before = DeckBuilder::new().standard52().build();
+/// let mut after = before.clone();
+/// after.cut(20).unwrap();
+/// let report = analyze(&before, &after).unwrap();
+/// assert_eq!(report.rising_sequences, 2);
+/// assert_eq!(report.adjacent_pairs_kept, 50);
+/// ```
+pub fn analyze(before: &Deck, after: &Deck) -> Option<ShuffleReport> {
+    if before.len() != after.len() {
+        return None;
+    }
+
+    let mut original: HashMap<Card, VecDeque<usize>> = HashMap::new();
+    for (i, card) in before.iter().enumerate() {
+        original.entry(*card).or_default().push_back(i);
+    }
+
+    // new_position[k] is where the card originally at k ended up.
+    let mut new_position = vec![0; before.len()];
+    for (position, card) in after.iter().enumerate() {
+        let from = original.get_mut(card)?.pop_front()?;
+        new_position[from] = position;
+    }
+
+    let descents = new_position.windows(2).filter(|w| w[1] < w[0]).count();
+    let adjacent_pairs_kept = new_position.windows(2).filter(|w| w[1] == w[0] + 1).count();
+    let fixed_points = new_position
+        .iter()
+        .enumerate()
+        .filter(|(k, p)| k == *p)
+        .count();
+
+    Some(ShuffleReport {
+        cards: before.len(),
+        rising_sequences: if before.is_empty() { 0 } else { descents + 1 },
+        adjacent_pairs_kept,
+        fixed_points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::deck_builder::DeckBuilder;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn deck() -> Deck {
+        DeckBuilder::new().standard52().build()
+    }
+
+    fn shuffled(procedure: &ShuffleProcedure, seed: u64) -> (Deck, ShuffleReport) {
+        let before = deck();
+        let mut after = before.clone();
+        procedure.apply(&mut after, &mut StdRng::seed_from_u64(seed));
+        let report = analyze(&before, &after).expect("a permutation");
+        (after, report)
+    }
+
+    #[test]
+    fn every_step_keeps_the_same_cards() {
+        let steps = [
+            ShuffleStep::Wash,
+            ShuffleStep::Riffle,
+            ShuffleStep::Overhand,
+            ShuffleStep::Strip { packets: 5 },
+            ShuffleStep::Cut { depth: 13 },
+            ShuffleStep::RandomCut,
+        ];
+        for step in steps {
+            let (after, _) = shuffled(&ShuffleProcedure::new().then(step), 7);
+            assert_eq!(after.len(), 52);
+            assert_eq!(after.card_set(), deck().card_set(), "{:?}", step);
+        }
+    }
+
+    #[test]
+    fn one_riffle_leaves_at_most_two_rising_sequences() {
+        for seed in 0..20 {
+            let (_, report) = shuffled(&ShuffleProcedure::riffles(1), seed);
+            assert!(report.rising_sequences <= 2);
+        }
+    }
+
+    #[test]
+    fn more_riffles_destroy_more_order() {
+        let average = |riffles: usize| {
+            (0..50)
+                .map(|seed| shuffled(&ShuffleProcedure::riffles(riffles), seed).1)
+                .map(|r| r.rising_sequences as f64)
+                .sum::<f64>()
+                / 50.0
+        };
+        let (one, three, seven) = (average(1), average(3), average(7));
+        assert!(one < three && three < seven);
+        // Seven riffles come close to the 26.5 expected of a random deck.
+        assert!(seven > 20.0, "seven riffles averaged {}", seven);
+    }
+
+    #[test]
+    fn strip_keeps_pairs_inside_packets() {
+        let (_, report) = shuffled(
+            &ShuffleProcedure::new().then(ShuffleStep::Strip { packets: 4 }),
+            3,
+        );
+        assert_eq!(report.adjacent_pairs_kept, 52 - 4);
+        assert_eq!(report.rising_sequences, 4);
+    }
+
+    #[test]
+    fn wash_leaves_little_adjacency() {
+        let (_, report) = shuffled(&ShuffleProcedure::new().then(ShuffleStep::Wash), 11);
+        assert!(report.adjacency_retention() < 0.15);
+        assert!(report.rising_sequences > 15);
+    }
+
+    #[test]
+    fn analyze_matches_duplicates_and_rejects_other_decks() {
+        let shoe = DeckBuilder::new().standard52().repeat(2).build();
+        let report = analyze(&shoe, &shoe).unwrap();
+        assert_eq!(report.fixed_points, 104);
+        assert_eq!(report.rising_sequences, 1);
+
+        assert!(analyze(&deck(), &DeckBuilder::new().euchre().build()).is_none());
+    }
+}
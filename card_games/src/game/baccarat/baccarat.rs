@@ -0,0 +1,440 @@
+use crate::{
+    bank::{bank::Bank, ledger::TransactionKind, limits::TableLimits},
+    game::baccarat::{
+        rules,
+        scoreboard::{bead_plate, BigRoad, ROAD_ROWS},
+        types::{
+            BaccaratError, BaccaratState, BetKind, Commission, Coup, Outcome, Shoe, Wager,
+            CUT_CARD, DEFAULT_DECKS,
+        },
+        view::BaccaratView,
+    },
+};
+
+pub struct Baccarat {
+    state: BaccaratState,
+    shoe: Shoe,
+    commission: Commission,
+
+    bank: Bank,
+    limits: TableLimits,
+    wagers: Vec<Wager>,
+    last_coup: Option<Coup>,
+    last_payout: u32,
+
+    /// Outcomes dealt from the current shoe, oldest first.
+    history: Vec<Outcome>,
+}
+
+impl Default for Baccarat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Baccarat {
+    pub fn new() -> Self {
+        Self::with_rules(DEFAULT_DECKS, Commission::Standard)
+    }
+
+    pub fn with_rules(decks: usize, commission: Commission) -> Self {
+        Baccarat {
+            state: BaccaratState::Betting,
+            shoe: Shoe::new_shuffled(decks),
+            commission,
+
+            bank: Bank::new(1_000),
+            limits: TableLimits::default(),
+            wagers: Vec::new(),
+            last_coup: None,
+            last_payout: 0,
+
+            history: Vec::new(),
+        }
+    }
+
+    /// Plays at a table with the given limits.
+    /// Starts the player with `balance` in the bank.
+    pub fn with_bankroll(mut self, balance: u32) -> Self {
+        self.bank = Bank::new(balance);
+        self
+    }
+
+    pub fn with_limits(mut self, limits: TableLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn limits(&self) -> TableLimits {
+        self.limits
+    }
+
+    /// Places (or adds to) a wager for the next coup.
+    ///
+    /// Betting after a coup clears the previous table.
+    pub fn place_bet(&mut self, kind: BetKind, amount: u32) -> Result<(), BaccaratError> {
+        if amount == 0 {
+            return Err(BaccaratError::ZeroBet);
+        }
+        if self.state == BaccaratState::RoundOver {
+            self.reset_table();
+        }
+        if !self.bank.can_cover(amount) {
+            return Err(BaccaratError::InsufficientFunds);
+        }
+
+        // Limits apply to the total staked on each bet.
+        let staked = self
+            .wagers
+            .iter()
+            .find(|w| w.kind == kind)
+            .map_or(0, |w| w.amount);
+        self.limits
+            .check_bet(staked.saturating_add(amount))
+            .map_err(BaccaratError::Limit)?;
+
+        if self.wagers.is_empty() {
+            self.bank.start_round();
+        }
+        self.bank
+            .wager(amount)
+            .map_err(|_| BaccaratError::InsufficientFunds)?;
+
+        match self.wagers.iter_mut().find(|w| w.kind == kind) {
+            Some(wager) => wager.amount += amount,
+            None => self.wagers.push(Wager { kind, amount }),
+        }
+        Ok(())
+    }
+
+    /// Takes back all wagers that have not been dealt yet.
+    pub fn clear_bets(&mut self) {
+        if self.state != BaccaratState::Betting {
+            return;
+        }
+        for wager in self.wagers.drain(..) {
+            self.bank
+                .credit_capped(TransactionKind::Refund, wager.amount);
+        }
+    }
+
+    /// Deals a coup, settles every wager and records the result.
+    pub fn deal(&mut self) -> Result<Outcome, BaccaratError> {
+        if self.state != BaccaratState::Betting {
+            return Err(BaccaratError::RoundInProgress);
+        }
+        if self.wagers.is_empty() {
+            return Err(BaccaratError::NoBets);
+        }
+
+        if self.needs_shuffle() {
+            self.shuffle_shoe();
+        }
+
+        let coup = self.play_coup();
+        let outcome = coup.outcome;
+
+        self.last_payout = 0;
+        for wager in &self.wagers {
+            let payout = self.commission.payout(wager, outcome, coup.banker_total);
+            if payout > 0 {
+                // Player and banker bets push on a tie.
+                let kind = if outcome == Outcome::Tie && wager.kind != BetKind::Tie {
+                    TransactionKind::Refund
+                } else {
+                    TransactionKind::Payout
+                };
+                self.bank.credit_capped(kind, payout);
+            }
+            self.last_payout += payout;
+        }
+
+        self.history.push(outcome);
+        self.last_coup = Some(coup);
+        self.state = BaccaratState::RoundOver;
+
+        Ok(outcome)
+    }
+
+    /// Clears the settled coup and returns the table to betting.
+    fn reset_table(&mut self) {
+        self.wagers.clear();
+        self.last_coup = None;
+        self.last_payout = 0;
+        self.state = BaccaratState::Betting;
+    }
+
+    pub fn needs_shuffle(&self) -> bool {
+        self.shoe.remaining() < CUT_CARD
+    }
+
+    /// Replaces the shoe and starts a fresh scoreboard.
+    pub fn shuffle_shoe(&mut self) {
+        self.shoe = Shoe::new_shuffled(self.shoe.decks());
+        self.history.clear();
+    }
+
+    pub fn history(&self) -> &[Outcome] {
+        &self.history
+    }
+
+    fn play_coup(&mut self) -> Coup {
+        let mut player = vec![self.shoe.draw()];
+        let mut banker = vec![self.shoe.draw()];
+        player.push(self.shoe.draw());
+        banker.push(self.shoe.draw());
+
+        let natural = rules::is_natural(&player) || rules::is_natural(&banker);
+
+        if !natural {
+            if rules::player_draws(&player) {
+                player.push(self.shoe.draw());
+            }
+            if rules::banker_draws(&banker, player.get(2)) {
+                banker.push(self.shoe.draw());
+            }
+        }
+
+        let player_total = rules::hand_total(&player);
+        let banker_total = rules::hand_total(&banker);
+
+        Coup {
+            player_cards: player,
+            banker_cards: banker,
+            player_total,
+            banker_total,
+            outcome: Outcome::determine(player_total, banker_total),
+            natural,
+        }
+    }
+
+    /// The player's bankroll and its ledger.
+    pub fn bank(&self) -> &Bank {
+        &self.bank
+    }
+
+    pub fn view(&self) -> BaccaratView {
+        let coup = self.last_coup.as_ref();
+
+        BaccaratView {
+            phase: self.state,
+            commission: self.commission,
+
+            player_cards: coup.map(|c| c.player_cards.clone()).unwrap_or_default(),
+            banker_cards: coup.map(|c| c.banker_cards.clone()).unwrap_or_default(),
+            player_total: coup.map(|c| c.player_total),
+            banker_total: coup.map(|c| c.banker_total),
+
+            outcome: coup.map(|c| c.outcome),
+            natural: coup.is_some_and(|c| c.natural),
+
+            wagers: self.wagers.clone(),
+            total_bet: self.wagers.iter().map(|w| w.amount).sum(),
+            last_payout: self.last_payout,
+            bank_balance: self.bank.balance(),
+
+            bead_plate: bead_plate(&self.history, ROAD_ROWS),
+            big_road: BigRoad::from_results(&self.history, ROAD_ROWS),
+            cards_remaining: self.shoe.remaining(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bank::limits::LimitError,
+        cards::{Card, Suit, Value},
+    };
+
+    fn rigged(cards: Vec<Card>) -> Baccarat {
+        let mut padded = cards;
+        padded.extend(std::iter::repeat_n(
+            Card::new(Suit::CLUBS, Value::KING),
+            CUT_CARD,
+        ));
+
+        let mut game = Baccarat::new();
+        game.shoe = Shoe::rigged(padded);
+        game
+    }
+
+    #[test]
+    fn an_empty_shoe_is_dealt_from_one_deck() {
+        let mut game = Baccarat::with_rules(0, Commission::Standard);
+        assert_eq!(game.shoe.decks(), 1);
+        game.place_bet(BetKind::Player, 10).unwrap();
+        assert!(game.deal().is_ok());
+    }
+
+    #[test]
+    fn place_bet_withdraws_from_bank() {
+        let mut game = Baccarat::new();
+        game.place_bet(BetKind::Banker, 50).unwrap();
+        game.place_bet(BetKind::Banker, 25).unwrap();
+
+        let view = game.view();
+        assert_eq!(view.bank_balance, 1_000 - 75);
+        assert_eq!(view.wagers.len(), 1);
+        assert_eq!(view.total_bet, 75);
+    }
+
+    #[test]
+    fn bets_beyond_balance_are_rejected() {
+        let mut game = Baccarat::new();
+        assert_eq!(
+            game.place_bet(BetKind::Player, 5_000),
+            Err(BaccaratError::InsufficientFunds)
+        );
+        assert_eq!(
+            game.place_bet(BetKind::Player, 0),
+            Err(BaccaratError::ZeroBet)
+        );
+    }
+
+    #[test]
+    fn bets_are_held_to_the_table_limits() {
+        let mut game = Baccarat::new().with_limits(TableLimits::new(25, 100));
+        assert_eq!(
+            game.place_bet(BetKind::Player, 10),
+            Err(BaccaratError::Limit(LimitError::BelowMinimum {
+                bet: 10,
+                min: 25
+            }))
+        );
+
+        game.place_bet(BetKind::Player, 75).unwrap();
+        assert_eq!(
+            game.place_bet(BetKind::Player, 50),
+            Err(BaccaratError::Limit(LimitError::AboveMaximum {
+                bet: 125,
+                max: 100
+            }))
+        );
+        assert_eq!(game.view().bank_balance, 1_000 - 75);
+    }
+
+    #[test]
+    fn deal_without_bets_is_an_error() {
+        let mut game = Baccarat::new();
+        assert_eq!(game.deal(), Err(BaccaratError::NoBets));
+    }
+
+    #[test]
+    fn natural_stops_the_draw() {
+        let mut game = rigged(vec![
+            Card::new(Suit::SPADES, Value::FOUR),  // p1
+            Card::new(Suit::HEARTS, Value::TWO),   // b1
+            Card::new(Suit::CLUBS, Value::FIVE),   // p2 -> 9
+            Card::new(Suit::DIAMONDS, Value::TWO), // b2 -> 4
+            Card::new(Suit::SPADES, Value::THREE), // never drawn
+        ]);
+        game.place_bet(BetKind::Player, 10).unwrap();
+
+        assert_eq!(game.deal(), Ok(Outcome::PlayerWin));
+
+        let view = game.view();
+        assert!(view.natural);
+        assert_eq!(view.player_cards.len(), 2);
+        assert_eq!(view.banker_cards.len(), 2);
+        assert_eq!(view.bank_balance, 1_000 + 10);
+    }
+
+    #[test]
+    fn banker_uses_player_third_card() {
+        let mut game = rigged(vec![
+            Card::new(Suit::SPADES, Value::KING),  // p1
+            Card::new(Suit::HEARTS, Value::TWO),   // b1
+            Card::new(Suit::CLUBS, Value::FOUR),   // p2 -> 4, draws
+            Card::new(Suit::DIAMONDS, Value::ACE), // b2 -> 3
+            Card::new(Suit::SPADES, Value::EIGHT), // p3 -> 2; banker 3 stands on an 8
+        ]);
+        game.place_bet(BetKind::Banker, 100).unwrap();
+
+        assert_eq!(game.deal(), Ok(Outcome::BankerWin));
+
+        let view = game.view();
+        assert_eq!(view.player_cards.len(), 3);
+        assert_eq!(view.banker_cards.len(), 2);
+        assert_eq!(view.bank_balance, 1_000 + 95);
+    }
+
+    #[test]
+    fn tie_pushes_main_bets_and_pays_tie() {
+        let mut game = rigged(vec![
+            Card::new(Suit::SPADES, Value::SIX),
+            Card::new(Suit::HEARTS, Value::SIX),
+            Card::new(Suit::CLUBS, Value::KING),
+            Card::new(Suit::DIAMONDS, Value::QUEEN),
+        ]);
+        game.place_bet(BetKind::Player, 10).unwrap();
+        game.place_bet(BetKind::Tie, 10).unwrap();
+
+        assert_eq!(game.deal(), Ok(Outcome::Tie));
+        assert_eq!(game.view().bank_balance, 1_000 - 20 + 10 + 90);
+
+        let round = game.bank().ledger().round_summary(game.bank().round());
+        assert_eq!(round.wagered, 20);
+        assert_eq!(round.refunded, 10);
+        assert_eq!(round.paid, 90);
+    }
+
+    #[test]
+    fn no_commission_banker_six_pays_half() {
+        let mut game = rigged(vec![
+            Card::new(Suit::SPADES, Value::TWO),
+            Card::new(Suit::HEARTS, Value::THREE),
+            Card::new(Suit::CLUBS, Value::THREE), // player 5, draws
+            Card::new(Suit::DIAMONDS, Value::THREE), // banker 6
+            Card::new(Suit::SPADES, Value::KING), // player stays 5; banker 6 stands on 0
+        ]);
+        game.commission = Commission::NoCommission;
+        game.place_bet(BetKind::Banker, 100).unwrap();
+
+        assert_eq!(game.deal(), Ok(Outcome::BankerWin));
+        assert_eq!(game.view().bank_balance, 1_000 + 50);
+    }
+
+    #[test]
+    fn results_feed_the_scoreboards() {
+        let mut game = rigged(vec![
+            Card::new(Suit::SPADES, Value::FOUR),
+            Card::new(Suit::HEARTS, Value::TWO),
+            Card::new(Suit::CLUBS, Value::FIVE),
+            Card::new(Suit::DIAMONDS, Value::TWO),
+        ]);
+        game.place_bet(BetKind::Player, 10).unwrap();
+        game.deal().unwrap();
+
+        let view = game.view();
+        assert_eq!(game.history(), &[Outcome::PlayerWin]);
+        assert_eq!(view.bead_plate, vec![vec![Outcome::PlayerWin]]);
+        assert_eq!(view.big_road.width(), 1);
+    }
+
+    #[test]
+    fn betting_after_a_coup_starts_a_new_round() {
+        let mut game = rigged(vec![
+            Card::new(Suit::SPADES, Value::FOUR),
+            Card::new(Suit::HEARTS, Value::TWO),
+            Card::new(Suit::CLUBS, Value::FIVE),
+            Card::new(Suit::DIAMONDS, Value::TWO),
+        ]);
+        game.place_bet(BetKind::Player, 10).unwrap();
+        game.deal().unwrap();
+
+        game.place_bet(BetKind::Banker, 20).unwrap();
+
+        let view = game.view();
+        assert_eq!(view.phase, BaccaratState::Betting);
+        assert!(view.outcome.is_none());
+        assert_eq!(
+            view.wagers,
+            vec![Wager {
+                kind: BetKind::Banker,
+                amount: 20
+            }]
+        );
+    }
+}
@@ -0,0 +1,9 @@
+#[allow(clippy::module_inception)]
+pub mod baccarat;
+pub mod rules;
+pub mod scoreboard;
+pub mod types;
+pub mod view;
+
+pub use baccarat::Baccarat;
+pub use types::{BaccaratError, BetKind, Commission, Outcome};
@@ -0,0 +1,136 @@
+//! Punto Banco scoring and the fixed third-card drawing table.
+//!
+//! Baccarat values differ from blackjack's:
+//! - Aces count 1, two through nine count their pip value
+//! - Tens and face cards count 0
+//! - A hand's total is the sum of its cards modulo 10
+
+use crate::cards::{Card, Value};
+
+/// Returns the baccarat value of a card.
+pub fn card_value(card: &Card) -> u8 {
+    match *card.value() {
+        Value::TEN | Value::JACK | Value::QUEEN | Value::KING | Value::JOKER => 0,
+        value => value as u8,
+    }
+}
+
+/// Calculates the total of a baccarat hand (sum of card values modulo 10).
+///
+/// # Example
+/// ```
+/// use card_games::cards::{Card, Suit, Value};
+/// use card_games::game::baccarat::rules::hand_total;
+/// let hand = vec![
+///     Card::new(Suit::HEARTS, Value::SEVEN),
+///     Card::new(Suit::CLUBS, Value::EIGHT),
+/// ];
+/// assert_eq!(hand_total(&hand), 5);
+/// ```
+pub fn hand_total(hand: &[Card]) -> u8 {
+    hand.iter().map(card_value).sum::<u8>() % 10
+}
+
+/// Returns `true` if a two-card hand totals 8 or 9.
+pub fn is_natural(hand: &[Card]) -> bool {
+    hand.len() == 2 && hand_total(hand) >= 8
+}
+
+/// Returns `true` if the Player hand draws a third card (totals 0–5).
+pub fn player_draws(hand: &[Card]) -> bool {
+    hand.len() == 2 && hand_total(hand) <= 5
+}
+
+/// Returns `true` if the Banker hand draws a third card.
+///
+/// When the Player stood, the Banker draws on 0–5. Otherwise the decision
+/// depends on the Banker total and the value of the Player's third card:
+///
+/// | Banker | Draws when Player's third card is |
+/// |--------|-----------------------------------|
+/// | 0–2    | anything                          |
+/// | 3      | anything but 8                    |
+/// | 4      | 2–7                               |
+/// | 5      | 4–7                               |
+/// | 6      | 6–7                               |
+/// | 7      | never                             |
+pub fn banker_draws(hand: &[Card], player_third: Option<&Card>) -> bool {
+    if hand.len() != 2 {
+        return false;
+    }
+
+    let total = hand_total(hand);
+    let Some(third) = player_third else {
+        return total <= 5;
+    };
+
+    let third = card_value(third);
+    match total {
+        0..=2 => true,
+        3 => third != 8,
+        4 => (2..=7).contains(&third),
+        5 => (4..=7).contains(&third),
+        6 => (6..=7).contains(&third),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Suit;
+
+    fn hand(values: &[Value]) -> Vec<Card> {
+        values.iter().map(|v| Card::new(Suit::SPADES, *v)).collect()
+    }
+
+    #[test]
+    fn tens_and_faces_count_zero() {
+        assert_eq!(hand_total(&hand(&[Value::KING, Value::TEN])), 0);
+        assert_eq!(hand_total(&hand(&[Value::ACE, Value::QUEEN])), 1);
+    }
+
+    #[test]
+    fn total_wraps_past_nine() {
+        assert_eq!(hand_total(&hand(&[Value::NINE, Value::SIX])), 5);
+        assert_eq!(
+            hand_total(&hand(&[Value::NINE, Value::NINE, Value::NINE])),
+            7
+        );
+    }
+
+    #[test]
+    fn naturals_are_eight_or_nine() {
+        assert!(is_natural(&hand(&[Value::FOUR, Value::FOUR])));
+        assert!(is_natural(&hand(&[Value::KING, Value::NINE])));
+        assert!(!is_natural(&hand(&[Value::THREE, Value::FOUR])));
+    }
+
+    #[test]
+    fn player_draws_on_five_stands_on_six() {
+        assert!(player_draws(&hand(&[Value::TWO, Value::THREE])));
+        assert!(!player_draws(&hand(&[Value::TWO, Value::FOUR])));
+    }
+
+    #[test]
+    fn banker_draws_on_five_or_less_when_player_stands() {
+        assert!(banker_draws(&hand(&[Value::TWO, Value::THREE]), None));
+        assert!(!banker_draws(&hand(&[Value::TWO, Value::FOUR]), None));
+    }
+
+    #[test]
+    fn banker_follows_drawing_table() {
+        let third = |v| Card::new(Suit::HEARTS, v);
+        let three = hand(&[Value::ACE, Value::TWO]);
+        let six = hand(&[Value::TWO, Value::FOUR]);
+
+        assert!(!banker_draws(&three, Some(&third(Value::EIGHT))));
+        assert!(banker_draws(&three, Some(&third(Value::NINE))));
+        assert!(banker_draws(&six, Some(&third(Value::SIX))));
+        assert!(!banker_draws(&six, Some(&third(Value::FIVE))));
+        assert!(!banker_draws(
+            &hand(&[Value::THREE, Value::FOUR]),
+            Some(&third(Value::SEVEN))
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+//! Baccarat scoreboards ("roads") built from the history of coup outcomes.
+//!
+//! - The **bead plate** records every result in order, filling each column
+//!   top to bottom.
+//! - The **big road** starts a new column each time the winner changes, and
+//!   marks ties on the previous cell instead of giving them a cell of their
+//!   own. A streak that reaches the bottom row, or runs into an occupied
+//!   cell, turns right (the "dragon tail").
+//!
+//! The roads are library-only for now: the CLI does not host baccarat, so
+//! nothing renders them yet.
+
+use super::types::Outcome;
+
+/// Rows on a standard scoreboard.
+pub const ROAD_ROWS: usize = 6;
+
+/// Lays out results as a bead plate: a list of columns, each holding up to
+/// `rows` outcomes from top to bottom.
+///
+/// # Example
+/// ```
+/// use card_games::game::baccarat::{scoreboard::bead_plate, Outcome};
+/// let plate = bead_plate(&[Outcome::PlayerWin, Outcome::Tie, Outcome::BankerWin], 2);
+/// assert_eq!(plate, vec![vec![Outcome::PlayerWin, Outcome::Tie], vec![Outcome::BankerWin]]);
+/// ```
+pub fn bead_plate(results: &[Outcome], rows: usize) -> Vec<Vec<Outcome>> {
+    results
+        .chunks(rows.max(1))
+        .map(|column| column.to_vec())
+        .collect()
+}
+
+/// One mark on the big road.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigRoadCell {
+    /// Either [`Outcome::PlayerWin`] or [`Outcome::BankerWin`].
+    pub outcome: Outcome,
+    /// Ties dealt directly after this result.
+    pub ties: u32,
+}
+
+/// The big road scoreboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigRoad {
+    columns: Vec<Vec<Option<BigRoadCell>>>,
+    rows: usize,
+    /// Ties dealt before the first Player or Banker win of the shoe.
+    leading_ties: u32,
+}
+
+impl BigRoad {
+    /// Builds the big road for a sequence of results.
+    pub fn from_results(results: &[Outcome], rows: usize) -> Self {
+        let mut road = Self {
+            columns: Vec::new(),
+            rows: rows.max(1),
+            leading_ties: 0,
+        };
+
+        // (column, row) of the last mark, the column its streak began in, and
+        // whether the streak has turned right.
+        let mut last: Option<(usize, usize)> = None;
+        let mut streak_column = 0;
+        let mut turned = false;
+
+        for outcome in results {
+            if *outcome == Outcome::Tie {
+                match last {
+                    Some((col, row)) => {
+                        if let Some(cell) = road.columns[col][row].as_mut() {
+                            cell.ties += 1;
+                        }
+                    }
+                    None => road.leading_ties += 1,
+                }
+                continue;
+            }
+
+            let next = match last {
+                None => (0, 0),
+                Some((col, row)) if road.get(col, row).map(|c| c.outcome) == Some(*outcome) => {
+                    if !turned && row + 1 < road.rows && road.get(col, row + 1).is_none() {
+                        (col, row + 1)
+                    } else {
+                        turned = true;
+                        (col + 1, row)
+                    }
+                }
+                Some(_) => {
+                    turned = false;
+                    streak_column += 1;
+                    (streak_column, 0)
+                }
+            };
+
+            road.place(
+                next,
+                BigRoadCell {
+                    outcome: *outcome,
+                    ties: 0,
+                },
+            );
+            last = Some(next);
+        }
+
+        road
+    }
+
+    /// Number of rows on the board.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns used so far.
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Ties dealt before the first Player or Banker win.
+    pub fn leading_ties(&self) -> u32 {
+        self.leading_ties
+    }
+
+    /// Returns the mark at a column and row, if any.
+    pub fn get(&self, column: usize, row: usize) -> Option<&BigRoadCell> {
+        self.columns.get(column)?.get(row)?.as_ref()
+    }
+
+    fn place(&mut self, (column, row): (usize, usize), cell: BigRoadCell) {
+        while self.columns.len() <= column {
+            self.columns.push(vec![None; self.rows]);
+        }
+        self.columns[column][row] = Some(cell);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use Outcome::{BankerWin as B, PlayerWin as P, Tie as T};
+
+    #[test]
+    fn bead_plate_fills_columns_top_to_bottom() {
+        let plate = bead_plate(&[P, B, T, P, P, B, B], 6);
+        assert_eq!(plate.len(), 2);
+        assert_eq!(plate[0], vec![P, B, T, P, P, B]);
+        assert_eq!(plate[1], vec![B]);
+    }
+
+    #[test]
+    fn big_road_starts_new_column_when_winner_changes() {
+        let road = BigRoad::from_results(&[B, B, P, B], 6);
+        assert_eq!(road.width(), 3);
+        assert_eq!(road.get(0, 1).map(|c| c.outcome), Some(B));
+        assert_eq!(road.get(1, 0).map(|c| c.outcome), Some(P));
+        assert_eq!(road.get(2, 0).map(|c| c.outcome), Some(B));
+    }
+
+    #[test]
+    fn big_road_marks_ties_on_previous_cell() {
+        let road = BigRoad::from_results(&[T, P, T, T, P], 6);
+        assert_eq!(road.leading_ties(), 1);
+        assert_eq!(road.get(0, 0).map(|c| c.ties), Some(2));
+        assert_eq!(road.get(0, 1).map(|c| c.ties), Some(0));
+    }
+
+    #[test]
+    fn long_streak_turns_into_dragon_tail() {
+        let road = BigRoad::from_results(&[B; 8], 6);
+        assert!(road.get(0, 5).is_some());
+        assert!(road.get(1, 5).is_some());
+        assert!(road.get(2, 5).is_some());
+        assert!(road.get(1, 0).is_none());
+    }
+
+    #[test]
+    fn dragon_tail_keeps_going_right_once_turned() {
+        let mut results = vec![B; 8];
+        results.extend([P; 8]);
+        let road = BigRoad::from_results(&results, 6);
+        // The player streak meets the banker tail at row 5, turns at row 4
+        // and must not drop back down once it is past the tail.
+        assert_eq!(road.get(1, 4).map(|c| c.outcome), Some(P));
+        assert_eq!(road.get(2, 4).map(|c| c.outcome), Some(P));
+        assert_eq!(road.get(4, 4).map(|c| c.outcome), Some(P));
+        assert!(road.get(3, 5).is_none());
+        assert_eq!(road.width(), 5);
+    }
+
+    #[test]
+    fn next_streak_starts_beside_previous_streak_start() {
+        let road = BigRoad::from_results(&[B, B, B, B, B, B, B, P], 6);
+        // The banker streak tailed into column 1, but the player result
+        // still starts the next logical column.
+        assert_eq!(road.get(1, 0).map(|c| c.outcome), Some(P));
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::Display;
+
+use crate::{
+    bank::limits::LimitError,
+    cards::{deck_builder::DeckBuilder, Card, Deck},
+};
+
+/// Number of decks in a standard Punto Banco shoe.
+pub const DEFAULT_DECKS: usize = 8;
+
+/// The shoe is reshuffled once fewer than this many cards remain.
+pub const CUT_CARD: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaccaratState {
+    Betting,
+    RoundOver,
+}
+
+impl Display for BaccaratState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaccaratState::Betting => write!(f, "Place your bets"),
+            BaccaratState::RoundOver => write!(f, "Round over"),
+        }
+    }
+}
+
+/// A multi-deck baccarat shoe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shoe {
+    deck: Deck,
+    decks: usize,
+}
+
+impl Shoe {
+    /// A shuffled shoe of `decks` decks, at least one.
+    pub fn new_shuffled(decks: usize) -> Self {
+        let decks = decks.max(1);
+        let mut deck = DeckBuilder::new().standard52().repeat(decks).build();
+        deck.shuffle();
+        Self { deck, decks }
+    }
+
+    pub fn decks(&self) -> usize {
+        self.decks
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.deck.len()
+    }
+
+    pub fn draw(&mut self) -> Card {
+        self.deck.draw().expect("Shoe exhausted")
+    }
+
+    #[cfg(test)]
+    pub fn rigged(draw_order: Vec<Card>) -> Self {
+        Self {
+            deck: Deck::from_cards(draw_order),
+            decks: 1,
+        }
+    }
+}
+
+/// The three main Punto Banco wagers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BetKind {
+    Player,
+    Banker,
+    Tie,
+}
+
+impl Display for BetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BetKind::Player => write!(f, "Player"),
+            BetKind::Banker => write!(f, "Banker"),
+            BetKind::Tie => write!(f, "Tie"),
+        }
+    }
+}
+
+/// A wager placed on one of the [`BetKind`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wager {
+    pub kind: BetKind,
+    pub amount: u32,
+}
+
+/// Which hand won a coup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    PlayerWin,
+    BankerWin,
+    Tie,
+}
+
+impl Outcome {
+    pub fn determine(player_total: u8, banker_total: u8) -> Self {
+        match player_total.cmp(&banker_total) {
+            std::cmp::Ordering::Greater => Outcome::PlayerWin,
+            std::cmp::Ordering::Less => Outcome::BankerWin,
+            std::cmp::Ordering::Equal => Outcome::Tie,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::PlayerWin => write!(f, "Player wins"),
+            Outcome::BankerWin => write!(f, "Banker wins"),
+            Outcome::Tie => write!(f, "Tie"),
+        }
+    }
+}
+
+/// How winning Banker bets are paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Commission {
+    /// Banker wins pay 19:20 (a 5% commission on the win).
+    #[default]
+    Standard,
+    /// Banker wins pay 1:1, except a win on a total of 6 pays 1:2.
+    NoCommission,
+}
+
+/// Pay table for the Tie bet (8 to 1).
+pub const TIE_PAYS: u32 = 8;
+
+impl Commission {
+    /// Returns the amount handed back for a wager, stake included.
+    ///
+    /// Player and Banker bets push on a tie. Fractional payouts are rounded
+    /// down.
+    pub fn payout(&self, wager: &Wager, outcome: Outcome, banker_total: u8) -> u32 {
+        let amount = wager.amount;
+        match (wager.kind, outcome) {
+            (BetKind::Player, Outcome::PlayerWin) => amount * 2,
+            (BetKind::Banker, Outcome::BankerWin) => match self {
+                Commission::Standard => amount + amount * 19 / 20,
+                Commission::NoCommission if banker_total == 6 => amount + amount / 2,
+                Commission::NoCommission => amount * 2,
+            },
+            (BetKind::Tie, Outcome::Tie) => amount * (TIE_PAYS + 1),
+            (BetKind::Player | BetKind::Banker, Outcome::Tie) => amount,
+            _ => 0,
+        }
+    }
+}
+
+/// The cards and result of one coup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coup {
+    pub player_cards: Vec<Card>,
+    pub banker_cards: Vec<Card>,
+    pub player_total: u8,
+    pub banker_total: u8,
+    pub outcome: Outcome,
+    pub natural: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaccaratError {
+    ZeroBet,
+    InsufficientFunds,
+    NoBets,
+    RoundInProgress,
+    Limit(LimitError),
+}
+
+impl Display for BaccaratError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaccaratError::ZeroBet => write!(f, "Bets must be greater than zero"),
+            BaccaratError::InsufficientFunds => write!(f, "Not enough funds for that bet"),
+            BaccaratError::NoBets => write!(f, "Place a bet before dealing"),
+            BaccaratError::RoundInProgress => write!(f, "The round is still in progress"),
+            BaccaratError::Limit(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for BaccaratError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banker_win_pays_less_commission() {
+        let wager = Wager {
+            kind: BetKind::Banker,
+            amount: 100,
+        };
+        assert_eq!(
+            Commission::Standard.payout(&wager, Outcome::BankerWin, 7),
+            195
+        );
+    }
+
+    #[test]
+    fn no_commission_banker_six_pays_half() {
+        let wager = Wager {
+            kind: BetKind::Banker,
+            amount: 100,
+        };
+        assert_eq!(
+            Commission::NoCommission.payout(&wager, Outcome::BankerWin, 6),
+            150
+        );
+        assert_eq!(
+            Commission::NoCommission.payout(&wager, Outcome::BankerWin, 7),
+            200
+        );
+    }
+
+    #[test]
+    fn tie_pushes_player_and_banker_and_pays_tie() {
+        let bet = |kind| Wager { kind, amount: 10 };
+        let c = Commission::Standard;
+        assert_eq!(c.payout(&bet(BetKind::Player), Outcome::Tie, 5), 10);
+        assert_eq!(c.payout(&bet(BetKind::Banker), Outcome::Tie, 5), 10);
+        assert_eq!(c.payout(&bet(BetKind::Tie), Outcome::Tie, 5), 90);
+        assert_eq!(c.payout(&bet(BetKind::Tie), Outcome::PlayerWin, 5), 0);
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    cards::Card,
+    game::baccarat::{
+        scoreboard::BigRoad,
+        types::{BaccaratState, Commission, Outcome, Wager},
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct BaccaratView {
+    pub phase: BaccaratState,
+    pub commission: Commission,
+
+    pub player_cards: Vec<Card>,
+    pub banker_cards: Vec<Card>,
+    pub player_total: Option<u8>,
+    pub banker_total: Option<u8>,
+
+    pub outcome: Option<Outcome>,
+    pub natural: bool,
+
+    pub wagers: Vec<Wager>,
+    pub total_bet: u32,
+    pub last_payout: u32,
+    pub bank_balance: u32,
+
+    pub bead_plate: Vec<Vec<Outcome>>,
+    pub big_road: BigRoad,
+    pub cards_remaining: usize,
+}
@@ -0,0 +1,465 @@
+//! Exact expected values for blackjack decisions.
+//!
+//! Given the player's cards, the dealer's upcard and the composition of
+//! the cards not yet seen, [`Analyzer`] works out the expected return of
+//! standing, hitting, doubling, splitting and surrendering by weighing
+//! every way the remaining cards can fall. Hands are scored with
+//! [`rules::hand_score`] and the dealer draws by [`rules::dealer_should_hit`]
+//! (stands on all 17s) unless the analyzer is told the dealer hits soft 17.
+//!
+//! The engine settles dealer blackjacks before the player acts, so values
+//! are conditioned on the dealer not holding one. Split hands are valued
+//! independently from the same shoe and may not be split again; like the
+//! engine, the analyzer lets them double unless told otherwise, and offers
+//! no surrender unless told to.
+
+use std::collections::HashMap;
+
+use crate::cards::{hand::Hand, Card, Suit, Value};
+
+use super::{
+    dealer::{DealerCache, DealerOutcomes, DrawSource},
+    rules::{self, SplitContext},
+};
+
+/// Number of distinct blackjack ranks: ace, two to nine, and tens.
+pub(super) const RANKS: usize = 10;
+pub(super) const TEN: usize = 9;
+pub(super) const ACE: usize = 0;
+
+/// Counts of each blackjack rank left in the shoe. Tens, jacks, queens
+/// and kings all count as tens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Composition {
+    pub(super) counts: [u32; RANKS],
+}
+
+impl Composition {
+    /// A shoe of `decks` full 52-card decks.
+    pub fn standard(decks: u32) -> Self {
+        let mut counts = [4 * decks; RANKS];
+        counts[TEN] = 16 * decks;
+        Composition { counts }
+    }
+
+    /// Counts the given cards. Jokers are ignored.
+    pub fn from_cards<'a, I: IntoIterator<Item = &'a Card>>(cards: I) -> Self {
+        let mut composition = Composition::default();
+        for card in cards {
+            if let Some(rank) = rank_index(card) {
+                composition.counts[rank] += 1;
+            }
+        }
+        composition
+    }
+
+    /// Removes seen cards. Cards that are not in the shoe are ignored.
+    pub fn without(mut self, cards: &[Card]) -> Self {
+        for rank in cards.iter().filter_map(rank_index) {
+            self.counts[rank] = self.counts[rank].saturating_sub(1);
+        }
+        self
+    }
+
+    /// How many cards of the card's blackjack rank remain.
+    pub fn count(&self, card: &Card) -> u32 {
+        rank_index(card).map_or(0, |rank| self.counts[rank])
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+}
+
+pub(super) fn rank_index(card: &Card) -> Option<usize> {
+    match card.rank()? {
+        rank @ 1..=9 => Some(rank as usize - 1),
+        _ => Some(TEN),
+    }
+}
+
+/// Each rank that can come next from `shoe`, with its chance.
+fn draws(shoe: &Composition) -> impl Iterator<Item = (usize, f64)> {
+    shoe.chances()
+        .into_iter()
+        .enumerate()
+        .filter(|(_, p)| *p > 0.0)
+}
+
+/// A card standing in for a blackjack rank.
+pub(super) fn card_of(rank: usize) -> Card {
+    let value = match rank {
+        ACE => Value::ACE,
+        1 => Value::TWO,
+        2 => Value::THREE,
+        3 => Value::FOUR,
+        4 => Value::FIVE,
+        5 => Value::SIX,
+        6 => Value::SEVEN,
+        7 => Value::EIGHT,
+        8 => Value::NINE,
+        _ => Value::TEN,
+    };
+    Card::new(Suit::SPADES, value)
+}
+
+/// A choice open to the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Stand,
+    Hit,
+    Double,
+    Split,
+    Surrender,
+}
+
+/// Expected return, per unit of the original bet, of each decision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActionValues {
+    pub stand: f64,
+    pub hit: f64,
+    /// `None` when the hand cannot be doubled.
+    pub double: Option<f64>,
+    /// `None` when the hand cannot be split.
+    pub split: Option<f64>,
+    /// `None` when the table does not offer surrender or the hand is past
+    /// its first two cards.
+    pub surrender: Option<f64>,
+}
+
+impl ActionValues {
+    /// The value of one decision, or `None` if it is not available.
+    pub fn get(&self, decision: Decision) -> Option<f64> {
+        match decision {
+            Decision::Stand => Some(self.stand),
+            Decision::Hit => Some(self.hit),
+            Decision::Double => self.double,
+            Decision::Split => self.split,
+            Decision::Surrender => self.surrender,
+        }
+    }
+
+    /// The decision with the highest expected return.
+    pub fn best(&self) -> (Decision, f64) {
+        [
+            Decision::Stand,
+            Decision::Hit,
+            Decision::Double,
+            Decision::Split,
+            Decision::Surrender,
+        ]
+        .into_iter()
+        .filter_map(|d| self.get(d).map(|ev| (d, ev)))
+        .fold((Decision::Stand, f64::NEG_INFINITY), |best, next| {
+            if next.1 > best.1 {
+                next
+            } else {
+                best
+            }
+        })
+    }
+}
+
+/// Computes exact decision values, caching the dealer's outcome
+/// probabilities between calls.
+///
+/// # Example
+/// ```
+/// use card_games::cards::parse_cards;
+/// use card_games::game::blackjack::analysis::{Analyzer, Composition, Decision};
+/// use card_games::game::blackjack::rules::SplitContext;
+///
+/// let player = parse_cards("6h 5c").unwrap();
+/// let up = "6d".parse().unwrap();
+/// let shoe = Composition::standard(6).without(&player).without(&[up]);
+///
+/// let values = Analyzer::new().analyze(&player, &up, &shoe, SplitContext::NoPreviousSplit);
+/// assert_eq!(values.best().0, Decision::Double);
+/// ```
+#[derive(Debug)]
+pub struct Analyzer {
+    dealer: DealerCache<Composition>,
+    /// Best play for hands already valued, keyed by the cards left, the
+    /// player's cards by rank, the upcard and whether doubling is open.
+    played: HashMap<(Composition, [u8; RANKS], usize, bool), f64>,
+    double_after_split: bool,
+    surrender: bool,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Analyzer {
+            dealer: DealerCache::default(),
+            played: HashMap::new(),
+            double_after_split: true,
+            surrender: false,
+        }
+    }
+}
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the dealer hits soft 17. Values already worked out for the
+    /// other rule are dropped.
+    pub fn with_dealer_hits_soft_17(mut self, hits: bool) -> Self {
+        self.dealer = DealerCache::new(hits);
+        self.played.clear();
+        self
+    }
+
+    /// Whether split hands may be doubled. The engine allows it.
+    pub fn with_double_after_split(mut self, allowed: bool) -> Self {
+        self.double_after_split = allowed;
+        self
+    }
+
+    /// Whether late surrender is offered. The engine does not offer it.
+    pub fn with_surrender(mut self, allowed: bool) -> Self {
+        self.surrender = allowed;
+        self
+    }
+
+    /// Values every decision for the player's hand against `upcard`.
+    ///
+    /// `shoe` holds the cards the player has not seen: it must not include
+    /// the player's cards or the upcard, but does include the dealer's hole
+    /// card.
+    pub fn analyze(
+        &mut self,
+        player: &[Card],
+        upcard: &Card,
+        shoe: &Composition,
+        context: SplitContext,
+    ) -> ActionValues {
+        let hand: Vec<Card> = player.iter().filter_map(rank_index).map(card_of).collect();
+        let up = rank_index(upcard).unwrap_or(TEN);
+
+        let double = rules::can_double(&hand).then(|| self.double(&hand, up, shoe));
+        let mut dealt = Hand::new();
+        player.iter().for_each(|card| dealt.add(*card));
+        let split =
+            rules::can_split(&dealt, context).then(|| 2.0 * self.split_hand(hand[0], up, shoe));
+        let surrender = (self.surrender
+            && player.len() == 2
+            && matches!(context, SplitContext::NoPreviousSplit))
+        .then_some(-0.5);
+
+        ActionValues {
+            stand: self.stand(&hand, up, shoe),
+            hit: self.hit(&hand, up, shoe, false),
+            double,
+            split,
+            surrender,
+        }
+    }
+
+    /// Chances of each dealer result for `upcard`, given that the dealer
+    /// has already checked for blackjack.
+    pub fn dealer_outcomes(&mut self, upcard: &Card, shoe: &Composition) -> DealerOutcomes {
+        self.dealer
+            .after_peek(rank_index(upcard).unwrap_or(TEN), shoe)
+    }
+
+    fn stand(&mut self, hand: &[Card], up: usize, shoe: &Composition) -> f64 {
+        let total = rules::hand_score(hand);
+        if total > 21 {
+            return -1.0;
+        }
+
+        let dealer = self.dealer.after_peek(up, shoe);
+        let mut ev = dealer.bust;
+        for dealer_total in 17..=21 {
+            let p = dealer.total(dealer_total);
+            ev += match total.cmp(&dealer_total) {
+                std::cmp::Ordering::Greater => p,
+                std::cmp::Ordering::Less => -p,
+                std::cmp::Ordering::Equal => 0.0,
+            };
+        }
+        ev
+    }
+
+    /// Takes one card, then plays on as well as possible.
+    fn hit(&mut self, hand: &[Card], up: usize, shoe: &Composition, may_double: bool) -> f64 {
+        let draws = draws(shoe);
+        let mut ev = 0.0;
+        for (rank, p) in draws {
+            let mut next = hand.to_vec();
+            next.push(card_of(rank));
+            ev += p * self.best(&next, up, &shoe.after(rank), may_double);
+        }
+        ev
+    }
+
+    fn double(&mut self, hand: &[Card], up: usize, shoe: &Composition) -> f64 {
+        let draws = draws(shoe);
+        let mut ev = 0.0;
+        for (rank, p) in draws {
+            let mut next = hand.to_vec();
+            next.push(card_of(rank));
+            ev += p * self.stand(&next, up, &shoe.after(rank));
+        }
+        2.0 * ev
+    }
+
+    /// The best of standing, hitting and (if allowed) doubling.
+    fn best(&mut self, hand: &[Card], up: usize, shoe: &Composition, may_double: bool) -> f64 {
+        let total = rules::hand_score(hand);
+        if total > 21 {
+            return -1.0;
+        }
+
+        let may_double = may_double && rules::can_double(hand);
+        let mut counts = [0; RANKS];
+        hand.iter()
+            .filter_map(rank_index)
+            .for_each(|rank| counts[rank] += 1);
+        let key = (*shoe, counts, up, may_double);
+        if let Some(best) = self.played.get(&key) {
+            return *best;
+        }
+
+        let mut best = self.stand(hand, up, shoe);
+        if total < 21 {
+            best = best.max(self.hit(hand, up, shoe, false));
+            if may_double {
+                best = best.max(self.double(hand, up, shoe));
+            }
+        }
+        self.played.insert(key, best);
+        best
+    }
+
+    /// Value of one hand after a split: it receives a second card and is
+    /// played on.
+    fn split_hand(&mut self, card: Card, up: usize, shoe: &Composition) -> f64 {
+        let draws = draws(shoe);
+        let mut ev = 0.0;
+        for (rank, p) in draws {
+            let hand = [card, card_of(rank)];
+            ev += p * self.best(&hand, up, &shoe.after(rank), self.double_after_split);
+        }
+        ev
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::parse_cards;
+
+    fn values(player: &str, up: &str, decks: u32) -> ActionValues {
+        let player = parse_cards(player).unwrap();
+        let up: Card = up.parse().unwrap();
+        let shoe = Composition::standard(decks).without(&player).without(&[up]);
+        Analyzer::new().analyze(&player, &up, &shoe, SplitContext::NoPreviousSplit)
+    }
+
+    #[test]
+    fn composition_counts_tens_together() {
+        let shoe = Composition::from_cards(&parse_cards("Kh Qs Tc 5d Jk").unwrap());
+        assert_eq!(shoe.total(), 4);
+        assert_eq!(shoe.count(&"Jd".parse().unwrap()), 3);
+        assert_eq!(Composition::standard(2).total(), 104);
+        assert_eq!(shoe.without(&parse_cards("Kh 9c").unwrap()).total(), 3);
+    }
+
+    #[test]
+    fn dealer_outcomes_sum_to_one() {
+        let mut analyzer = Analyzer::new();
+        let shoe = Composition::standard(1);
+        for up in ["As", "2s", "6s", "Ts"] {
+            let outcomes = analyzer.dealer_outcomes(&up.parse().unwrap(), &shoe);
+            let sum: f64 = outcomes.totals.iter().sum::<f64>() + outcomes.bust;
+            assert!((sum - 1.0).abs() < 1e-9, "{} sums to {}", up, sum);
+        }
+    }
+
+    #[test]
+    fn dealer_busts_most_often_showing_a_six() {
+        let mut analyzer = Analyzer::new();
+        let shoe = Composition::standard(6);
+        let six = analyzer.dealer_outcomes(&"6s".parse().unwrap(), &shoe).bust;
+        let ten = analyzer.dealer_outcomes(&"Ts".parse().unwrap(), &shoe).bust;
+        assert!((six - 0.42).abs() < 0.01, "six busts {}", six);
+        assert!((ten - 0.23).abs() < 0.01, "ten busts {}", ten);
+    }
+
+    #[test]
+    fn basic_strategy_decisions() {
+        assert_eq!(values("Th Qs", "6d", 6).best().0, Decision::Stand);
+        assert_eq!(values("6h 5c", "6d", 6).best().0, Decision::Double);
+        assert_eq!(values("Th 6c", "7d", 6).best().0, Decision::Hit);
+        assert_eq!(values("8h 8c", "Td", 6).best().0, Decision::Split);
+        assert_eq!(values("Th 3c", "4d", 6).best().0, Decision::Stand);
+    }
+
+    #[test]
+    fn hard_twenty_against_a_six_is_a_strong_favourite() {
+        let twenty = values("Th Qs", "6d", 6);
+        assert!(
+            twenty.stand > 0.6 && twenty.stand < 0.75,
+            "{}",
+            twenty.stand
+        );
+        assert!(twenty.hit < 0.0);
+        assert_eq!(twenty.split, None);
+    }
+
+    #[test]
+    fn splits_and_doubles_follow_the_rules() {
+        let player = parse_cards("8h 8c").unwrap();
+        let up: Card = "Td".parse().unwrap();
+        let shoe = Composition::standard(6).without(&player).without(&[up]);
+        let values = Analyzer::new().analyze(&player, &up, &shoe, SplitContext::AlreadySplit);
+        assert_eq!(values.split, None);
+
+        let three = values_for_three_cards();
+        assert_eq!(three.double, None);
+    }
+
+    #[test]
+    fn surrender_is_valued_only_when_offered() {
+        let player = parse_cards("Th 6c").unwrap();
+        let up: Card = "Td".parse().unwrap();
+        let shoe = Composition::standard(6).without(&player).without(&[up]);
+
+        let values = Analyzer::new().analyze(&player, &up, &shoe, SplitContext::NoPreviousSplit);
+        assert_eq!(values.surrender, None);
+        assert_eq!(values.best().0, Decision::Hit);
+
+        let mut offered = Analyzer::new().with_surrender(true);
+        let values = offered.analyze(&player, &up, &shoe, SplitContext::NoPreviousSplit);
+        assert_eq!(values.surrender, Some(-0.5));
+        assert_eq!(values.best().0, Decision::Surrender);
+
+        let split = offered.analyze(&player, &up, &shoe, SplitContext::AlreadySplit);
+        assert_eq!(split.surrender, None);
+        assert_eq!(values_for_three_cards().surrender, None);
+    }
+
+    #[test]
+    fn eleven_against_an_ace_doubles_when_the_dealer_hits_soft_17() {
+        let player = parse_cards("6h 5c").unwrap();
+        let up: Card = "Ad".parse().unwrap();
+        let shoe = Composition::standard(6).without(&player).without(&[up]);
+
+        let stands = Analyzer::new().analyze(&player, &up, &shoe, SplitContext::NoPreviousSplit);
+        assert_eq!(stands.best().0, Decision::Hit);
+
+        let hits = Analyzer::new().with_dealer_hits_soft_17(true).analyze(
+            &player,
+            &up,
+            &shoe,
+            SplitContext::NoPreviousSplit,
+        );
+        assert_eq!(hits.best().0, Decision::Double);
+    }
+
+    fn values_for_three_cards() -> ActionValues {
+        values("2h 3c 4d", "Td", 6)
+    }
+}
@@ -0,0 +1,162 @@
+//! Automated rounds of blackjack.
+//!
+//! [`play_rounds`] lets a [`BettingSystem`] size every bet and a decision
+//! function play every hand, then reports what the session won or lost
+//! against the total staked. Over enough rounds the loss per dollar
+//! staked settles on the game's edge whatever the progression.
+
+use std::fmt::Display;
+
+use crate::bank::betting::BettingSystem;
+
+use super::{blackjack::Blackjack, types::PlayerAction, BlackjackState};
+
+/// The results of a run of automated rounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoplayReport {
+    pub system: &'static str,
+    pub rounds: usize,
+    /// Every stake placed, side bets and insurance included.
+    pub wagered: u64,
+    pub net: i64,
+    pub lowest_balance: u32,
+    pub highest_balance: u32,
+    /// Whether play stopped because the bank could not cover a bet.
+    pub ran_out: bool,
+}
+
+impl AutoplayReport {
+    /// Net result per dollar staked, or `None` if nothing was staked.
+    pub fn edge(&self) -> Option<f64> {
+        (self.wagered > 0).then(|| self.net as f64 / self.wagered as f64)
+    }
+}
+
+impl Display for AutoplayReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} rounds, ${} wagered, net {}${}",
+            self.system,
+            self.rounds,
+            self.wagered,
+            if self.net < 0 { "-" } else { "" },
+            self.net.unsigned_abs()
+        )?;
+        if let Some(edge) = self.edge() {
+            write!(f, " ({:+.2}% of action)", edge * 100.0)?;
+        }
+        write!(
+            f,
+            ", balance ${}-${}",
+            self.lowest_balance, self.highest_balance
+        )?;
+        if self.ran_out {
+            write!(f, ", ran out of money")?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays up to `rounds` rounds, betting what `system` asks for within the
+/// table limits and playing each hand with `decide`. An action the game
+/// refuses is replaced by standing. Stops early when the bank cannot
+/// cover the next bet.
+///
+/// # Example
+/// ```
+/// use card_games::bank::betting::Martingale;
+/// use card_games::game::blackjack::{autoplay::play_rounds, blackjack::Blackjack};
+/// use card_games::game::blackjack::types::PlayerAction;
+/// let mut game = Blackjack::new();
+/// let mut system = Martingale::new(10);
+/// // Draw like the dealer does.
+/// let report = play_rounds(&mut game, &mut system, 100, |game| {
+///     let view = game.view();
+///     match view.player_hands[view.active_hand_index].score {
+///         score if score < 17 => PlayerAction::Hit,
+///         _ => PlayerAction::Stay,
+///     }
+/// });
+/// assert!(report.rounds <= 100);
+/// println!("{}", report);
+/// ```
+pub fn play_rounds(
+    game: &mut Blackjack,
+    system: &mut dyn BettingSystem,
+    rounds: usize,
+    mut decide: impl FnMut(&Blackjack) -> PlayerAction,
+) -> AutoplayReport {
+    let balance = game.bank().balance();
+    let mut report = AutoplayReport {
+        system: system.name(),
+        rounds: 0,
+        wagered: 0,
+        net: 0,
+        lowest_balance: balance,
+        highest_balance: balance,
+        ran_out: false,
+    };
+
+    for _ in 0..rounds {
+        let bet = game.limits().clamp(system.next_bet(game.bank()));
+        if game.set_bet(bet).is_err() {
+            report.ran_out = true;
+            break;
+        }
+
+        game.start_round();
+        while let BlackjackState::PlayerTurn { .. } = game.state() {
+            let action = decide(game);
+            if game.try_apply(action).is_err() {
+                game.apply(PlayerAction::Stay);
+            }
+        }
+        system.record_round(game.bank());
+
+        let bank = game.bank();
+        let summary = bank.ledger().round_summary(bank.round());
+        report.rounds += 1;
+        report.wagered += summary.wagered + summary.insurance;
+        report.net += summary.net();
+        report.lowest_balance = report.lowest_balance.min(bank.balance());
+        report.highest_balance = report.highest_balance.max(bank.balance());
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bank::betting::{Flat, Martingale};
+
+    fn stand_on_twelve(game: &Blackjack) -> PlayerAction {
+        let view = game.view();
+        match view.player_hands[view.active_hand_index].score {
+            score if score < 12 => PlayerAction::Hit,
+            _ => PlayerAction::Stay,
+        }
+    }
+
+    #[test]
+    fn report_matches_the_bank() {
+        let mut game = Blackjack::new();
+        let report = play_rounds(&mut game, &mut Flat::new(10), 50, stand_on_twelve);
+        assert_eq!(report.rounds, 50);
+        assert_eq!(report.system, "Flat");
+        assert_eq!(game.bank().balance() as i64, 1_000 + report.net);
+        assert!(report.wagered >= 500);
+        assert!(report.lowest_balance <= game.bank().balance());
+        assert!(report.highest_balance >= game.bank().balance());
+    }
+
+    #[test]
+    fn play_stops_when_the_bank_runs_dry() {
+        let mut game = Blackjack::new();
+        let mut system = Martingale::new(400);
+        let report = play_rounds(&mut game, &mut system, 10_000, |_| PlayerAction::Hit);
+        assert!(report.ran_out);
+        assert!(report.rounds < 10_000);
+        assert!(game.bank().balance() < game.limits().min_bet);
+    }
+}
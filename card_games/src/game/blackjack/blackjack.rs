@@ -1,39 +1,221 @@
+use rand::{rngs::StdRng, SeedableRng};
+
 use crate::{
-    bank::bank::Bank,
+    bank::{bank::Bank, chips::PayoutRule, ledger::TransactionKind, limits::TableLimits},
     cards::{hand::Hand, Card},
     game::blackjack::{
+        analysis::{ActionValues, Analyzer, Composition, Decision},
+        counting::HiLo,
+        deviations::{Advice, Advisor},
         rules::{self, SplitContext},
-        types::{BlackjackState, PlayerAction, PlayerHand, Shoe, Table},
+        side_bets::{SideBetKind, SideBetResult, SideBetRules},
+        types::{
+            BlackjackError, BlackjackState, HandResult, PlayerAction, PlayerHand, Shoe, Table,
+        },
         view::{BlackjackView, PlayerHandView, VisibleCard},
         GameResult,
     },
 };
 
+/// The opening bet before the player chooses one.
+const DEFAULT_BET: u32 = 10;
+
+/// The shoe is never dealt below this many cards.
+const MIN_CARDS_BEFORE_SHUFFLE: usize = 15;
+
 pub struct Blackjack {
     state: BlackjackState,
     shoe: Shoe,
+    decks: usize,
+    rng: StdRng,
+    dealer_hits_soft_17: bool,
     table: Table,
 
     bank: Bank,
+    payout_rule: PayoutRule,
+    limits: TableLimits,
+    bet: u32,
+
+    side_bet_rules: SideBetRules,
+    /// Side bets to place with each round's opening bet.
+    side_bets: Vec<(SideBetKind, u32)>,
+    /// Side bets staked on the current round.
+    staked_side_bets: Vec<(SideBetKind, u32)>,
+    side_bet_results: Vec<SideBetResult>,
+
     result: GameResult,
+    /// How each of the player's hands was settled this round.
+    hand_results: Vec<HandResult>,
+}
+impl Default for Blackjack {
+    fn default() -> Self {
+        Self::new()
+    }
 }
+
 impl Blackjack {
     pub fn new() -> Self {
+        let mut rng = StdRng::from_entropy();
+        let mut shoe = Shoe::shuffled(1, &mut rng);
+        shoe.burn();
         Blackjack {
             state: BlackjackState::Dealing,
-            shoe: Shoe::new_shuffled(),
+            shoe,
+            decks: 1,
+            rng,
+            dealer_hits_soft_17: false,
             table: Table {
                 player_hands: vec![PlayerHand::new(10)],
                 dealer_hand: Hand::new(),
             },
 
             bank: Bank::new(1_000),
+            payout_rule: PayoutRule::default(),
+            limits: TableLimits::default(),
+            bet: DEFAULT_BET,
+
+            side_bet_rules: SideBetRules::default(),
+            side_bets: Vec::new(),
+            staked_side_bets: Vec::new(),
+            side_bet_results: Vec::new(),
 
             result: GameResult::Pending,
+            hand_results: Vec::new(),
+        }
+    }
+
+    /// Deals from a shoe of `decks` decks.
+    pub fn with_decks(mut self, decks: usize) -> Self {
+        self.decks = decks.max(1);
+        self.shuffle_shoe();
+        self
+    }
+
+    /// Shuffles every shoe from `seed`, so that the same seed deals the
+    /// same cards.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.shuffle_shoe();
+        self
+    }
+
+    /// Starts the player with `balance` in the bank.
+    pub fn with_bankroll(mut self, balance: u32) -> Self {
+        self.bank = Bank::new(balance);
+        self
+    }
+
+    /// Has the dealer hit soft 17 instead of standing on it. The exact
+    /// analysis behind [`Blackjack::action_values`] follows the same rule.
+    pub fn with_dealer_hits_soft_17(mut self, hits: bool) -> Self {
+        self.dealer_hits_soft_17 = hits;
+        self
+    }
+
+    pub fn decks(&self) -> usize {
+        self.decks
+    }
+
+    pub fn dealer_hits_soft_17(&self) -> bool {
+        self.dealer_hits_soft_17
+    }
+
+    /// Rounds odd payouts, such as 3:2 on a $5 bet, with `payout_rule`.
+    /// Tables default to whole dollars, rounding down.
+    pub fn with_payout_rule(mut self, payout_rule: PayoutRule) -> Self {
+        self.payout_rule = payout_rule;
+        self
+    }
+
+    pub fn payout_rule(&self) -> PayoutRule {
+        self.payout_rule
+    }
+
+    /// Plays at a table with the given limits. The opening bet is moved
+    /// into the table's range.
+    pub fn with_limits(mut self, limits: TableLimits) -> Self {
+        self.limits = limits;
+        self.bet = limits.clamp(self.bet);
+        self
+    }
+
+    /// Pays side bets from the given pay tables.
+    pub fn with_side_bet_rules(mut self, side_bet_rules: SideBetRules) -> Self {
+        self.side_bet_rules = side_bet_rules;
+        self
+    }
+
+    pub fn limits(&self) -> TableLimits {
+        self.limits
+    }
+
+    /// The opening bet for each round.
+    pub fn bet(&self) -> u32 {
+        self.bet
+    }
+
+    /// Sets the opening bet for the next round.
+    ///
+    /// # Errors
+    /// Fails if a round is being played, the bet is outside the table
+    /// limits, or the bank cannot cover it.
+    pub fn set_bet(&mut self, amount: u32) -> Result<(), BlackjackError> {
+        if matches!(
+            self.state,
+            BlackjackState::PlayerTurn { .. } | BlackjackState::DealerTurn
+        ) {
+            return Err(BlackjackError::RoundInProgress);
+        }
+        self.limits
+            .check_bet(amount)
+            .map_err(BlackjackError::Limit)?;
+        if !self.bank.can_cover(amount) {
+            return Err(BlackjackError::InsufficientFunds);
         }
+        self.bet = amount;
+        Ok(())
+    }
+
+    /// Sets a side bet to place alongside each opening bet. An amount of
+    /// zero removes the side bet.
+    ///
+    /// # Errors
+    /// Fails if a round is being played or the bank cannot cover the
+    /// opening bet and every side bet together.
+    pub fn set_side_bet(&mut self, kind: SideBetKind, amount: u32) -> Result<(), BlackjackError> {
+        if matches!(
+            self.state,
+            BlackjackState::PlayerTurn { .. } | BlackjackState::DealerTurn
+        ) {
+            return Err(BlackjackError::RoundInProgress);
+        }
+
+        let others: u32 = self
+            .side_bets
+            .iter()
+            .filter(|(k, _)| *k != kind)
+            .map(|(_, a)| a)
+            .sum();
+        if !self
+            .bank
+            .can_cover(self.bet.saturating_add(others).saturating_add(amount))
+        {
+            return Err(BlackjackError::InsufficientFunds);
+        }
+
+        self.side_bets.retain(|(k, _)| *k != kind);
+        if amount > 0 {
+            self.side_bets.push((kind, amount));
+        }
+        Ok(())
+    }
+
+    pub fn side_bets(&self) -> &[(SideBetKind, u32)] {
+        &self.side_bets
     }
 
     fn deal_initial_cards(&mut self) {
+        self.ensure_cards(4);
         let hand = &mut self.table.player_hands[0];
         hand.hand.add(self.shoe.draw());
         self.table.dealer_hand.add(self.shoe.draw());
@@ -43,11 +225,33 @@ impl Blackjack {
         self.table.dealer_hand.add(self.shoe.draw());
     }
 
+    /// Settles the side bets from the player's first two cards and the
+    /// dealer's hole card and upcard.
+    fn settle_side_bets(&mut self) {
+        let player = self.table.player_hands[0].hand.cards();
+        let dealer = self.table.dealer_hand.cards();
+        let (player, hole, up) = ([player[0], player[1]], dealer[0], dealer[1]);
+
+        for &(kind, wager) in &self.staked_side_bets {
+            let outcome = kind.evaluate(player, hole, up);
+            let payout = self.side_bet_rules.pay_table(kind).payout(wager, outcome);
+            if payout > 0 {
+                self.bank.credit_capped(TransactionKind::Payout, payout);
+            }
+            self.side_bet_results.push(SideBetResult {
+                kind,
+                wager,
+                outcome,
+                payout,
+            });
+        }
+    }
+
     fn resolve_blackjack_or_continue(&mut self) {
         let player = &self.table.player_hands[0];
 
-        let player_blackjack = rules::is_blackjack(&player.hand.cards());
-        let dealer_blackjack = rules::is_blackjack(&self.table.dealer_hand.cards());
+        let player_blackjack = rules::is_blackjack(player.hand.cards());
+        let dealer_blackjack = rules::is_blackjack(self.table.dealer_hand.cards());
 
         #[cfg(test)]
         {
@@ -59,17 +263,32 @@ impl Blackjack {
                 dealer_blackjack
             );
         }
+        let bet = player.bet.amount;
+        let settled = |result, net| HandResult {
+            result,
+            bet,
+            net,
+            blackjack: player_blackjack,
+            doubled: false,
+            split: false,
+        };
         match (player_blackjack, dealer_blackjack) {
             (true, true) => {
-                self.bank.deposit(self.table.player_hands[0].bet.amount);
+                self.bank.credit_capped(TransactionKind::Refund, bet);
+                self.hand_results.push(settled(GameResult::Push, 0));
                 self.end_round(GameResult::Push);
             }
             (true, false) => {
-                let bet = self.table.player_hands[0].bet.amount;
-                self.bank.deposit(bet + bet * 3 / 2);
+                let winnings = self.payout_rule.winnings(bet, 3, 2);
+                self.bank
+                    .credit_capped(TransactionKind::Payout, bet.saturating_add(winnings));
+                self.hand_results
+                    .push(settled(GameResult::PlayerWin, winnings as i64));
                 self.end_round(GameResult::PlayerWin);
             }
             (false, true) => {
+                self.hand_results
+                    .push(settled(GameResult::DealerWin, -(bet as i64)));
                 self.end_round(GameResult::DealerWin);
             }
             (false, false) => {
@@ -90,17 +309,27 @@ impl Blackjack {
             return;
         }
 
-        self.table.player_hands = vec![PlayerHand::new(10)];
+        self.table.player_hands = vec![PlayerHand::new(self.bet)];
         self.table.dealer_hand.clear_hand();
+        self.staked_side_bets.clear();
+        self.side_bet_results.clear();
+        self.hand_results.clear();
         self.result = GameResult::Pending;
 
         self.state = BlackjackState::Dealing;
 
+        self.bank.start_round();
         let bet = self.table.player_hands[0].bet.amount;
-        if !self.bank.withdraw(bet) {
+        if self.bank.wager(bet).is_err() {
             // TODO: out-of-money state
             return;
         }
+        for &(kind, amount) in &self.side_bets {
+            // Side bets the bank cannot cover are skipped this round.
+            if self.bank.wager(amount).is_ok() {
+                self.staked_side_bets.push((kind, amount));
+            }
+        }
 
         self.run_automatic();
         #[cfg(test)]
@@ -125,26 +354,59 @@ impl Blackjack {
         }
     }
 
+    /// Whether the shoe is down to its last quarter, or to the last
+    /// 15 cards of a single deck.
     pub fn needs_shuffle(&self) -> bool {
-        self.shoe.remaining() < 15
+        self.shoe.remaining() < MIN_CARDS_BEFORE_SHUFFLE.max(self.shoe.size() / 4)
     }
 
+    /// Replaces the shoe with a freshly shuffled one and burns its first
+    /// card.
     pub fn shuffle_shoe(&mut self) {
-        self.shoe = Shoe::new_shuffled(); //TODO expand shoe to handle multiple shuffles
+        self.shoe = Shoe::shuffled(self.decks, &mut self.rng);
+        self.shoe.burn();
+    }
+
+    /// Makes sure the shoe holds `count` more cards, shuffling the discards
+    /// back in if it is running out in the middle of a round.
+    fn ensure_cards(&mut self, count: usize) {
+        if self.shoe.remaining() >= count {
+            return;
+        }
+        let in_play: Vec<Card> = self
+            .table
+            .player_hands
+            .iter()
+            .flat_map(|hand| hand.hand.iter())
+            .chain(self.table.dealer_hand.iter())
+            .copied()
+            .collect();
+        self.shoe.reshuffle_discards(&in_play, &mut self.rng);
     }
 
+    /// Applies a player action, ignoring actions that are not allowed.
     pub fn apply(&mut self, action: PlayerAction) {
+        let _ = self.try_apply(action);
+    }
+
+    /// Applies a player action.
+    ///
+    /// # Errors
+    /// Fails if it is not the player's turn, or a double or split is not
+    /// allowed by the cards, the bank or the table limits.
+    pub fn try_apply(&mut self, action: PlayerAction) -> Result<(), BlackjackError> {
         let BlackjackState::PlayerTurn { hand_index } = self.state else {
-            return;
+            return Err(BlackjackError::NotPlayerTurn);
         };
 
-        self.apply_to_player_hand(hand_index, action);
+        self.apply_to_player_hand(hand_index, action)?;
 
         if self.table.player_hands[hand_index].is_complete {
             self.advance_after_hand_complete(hand_index);
         }
 
         self.run_automatic();
+        Ok(())
     }
 
     // pub fn apply(&mut self, action: PlayerAction) {
@@ -165,6 +427,7 @@ impl Blackjack {
             match self.state {
                 BlackjackState::Dealing => {
                     self.deal_initial_cards();
+                    self.settle_side_bets();
                     self.resolve_blackjack_or_continue();
                 }
                 BlackjackState::DealerTurn => self.play_dealer(),
@@ -194,14 +457,23 @@ impl Blackjack {
         }
     }
 
-    fn apply_to_player_hand(&mut self, idx: usize, action: PlayerAction) {
+    fn apply_to_player_hand(
+        &mut self,
+        idx: usize,
+        action: PlayerAction,
+    ) -> Result<(), BlackjackError> {
+        self.ensure_cards(match action {
+            PlayerAction::Hit | PlayerAction::Double => 1,
+            PlayerAction::Split => 2,
+            PlayerAction::Stay => 0,
+        });
         let hand = &mut self.table.player_hands[idx];
 
         match action {
             PlayerAction::Hit => {
                 hand.hand.add(self.shoe.draw());
 
-                if rules::is_bust(&hand.hand.cards()) {
+                if rules::is_bust(hand.hand.cards()) {
                     hand.is_complete = true;
                 }
             }
@@ -212,16 +484,20 @@ impl Blackjack {
 
             PlayerAction::Double => {
                 // allowed only on the current hand
-                if !rules::can_double(&hand.hand.cards()) {
-                    return;
+                if !rules::can_double(hand.hand.cards()) {
+                    return Err(BlackjackError::CannotDouble);
                 }
 
                 let bet = hand.bet.amount;
-                if !self.bank.withdraw(bet) {
-                    return;
-                }
+                self.limits
+                    .check_double(bet)
+                    .map_err(BlackjackError::Limit)?;
+                self.bank
+                    .wager(bet)
+                    .map_err(|_| BlackjackError::InsufficientFunds)?;
 
                 hand.bet.amount *= 2;
+                hand.doubled = true;
                 hand.hand.add(self.shoe.draw());
                 hand.is_complete = true;
 
@@ -235,17 +511,17 @@ impl Blackjack {
                 {
                     let hand = &self.table.player_hands[idx];
                     if !rules::can_split(&hand.hand, split_context) {
-                        return;
-                    }
-                    if self.bank.balance() < hand.bet.amount {
-                        return;
+                        return Err(BlackjackError::CannotSplit);
                     }
                 }
 
                 let bet = self.table.player_hands[idx].bet.amount;
-                if !self.bank.withdraw(bet) {
-                    return;
-                }
+                self.limits
+                    .check_split(bet)
+                    .map_err(BlackjackError::Limit)?;
+                self.bank
+                    .wager(bet)
+                    .map_err(|_| BlackjackError::InsufficientFunds)?;
 
                 // Extract the two original cards without holding a borrow over self.draw_card()
                 let cards = self.table.player_hands[idx].hand.cards().to_vec();
@@ -271,10 +547,17 @@ impl Blackjack {
                 self.state = BlackjackState::PlayerTurn { hand_index: idx };
             }
         }
+
+        Ok(())
     }
 
     fn play_dealer(&mut self) {
-        while rules::dealer_should_hit(&self.table.dealer_hand.cards()) {
+        let should_hit = match self.dealer_hits_soft_17 {
+            true => rules::dealer_should_hit_soft_17,
+            false => rules::dealer_should_hit,
+        };
+        while should_hit(self.table.dealer_hand.cards()) {
+            self.ensure_cards(1);
             self.table.dealer_hand.add(self.shoe.draw());
         }
 
@@ -282,16 +565,17 @@ impl Blackjack {
     }
 
     fn resolve_round(&mut self) {
-        let dealer_score = rules::hand_score(&self.table.dealer_hand.cards());
-        let dealer_bust = rules::is_bust(&self.table.dealer_hand.cards());
+        let dealer_score = rules::hand_score(self.table.dealer_hand.cards());
+        let dealer_bust = rules::is_bust(self.table.dealer_hand.cards());
 
         let mut any_win = false;
         let mut any_push = false;
         let mut any_loss = false;
+        let split = self.table.player_hands.len() > 1;
 
         for hand in &self.table.player_hands {
-            let player_score = rules::hand_score(&hand.hand.cards());
-            let player_bust = rules::is_bust(&hand.hand.cards());
+            let player_score = rules::hand_score(hand.hand.cards());
+            let player_bust = rules::is_bust(hand.hand.cards());
 
             let result = if player_bust {
                 GameResult::DealerWin
@@ -301,20 +585,33 @@ impl Blackjack {
                 GameResult::determine(player_score, dealer_score)
             };
 
-            match result {
+            let bet = hand.bet.amount;
+            let net = match result {
                 GameResult::PlayerWin => {
-                    self.bank.deposit(hand.bet.amount * 2);
+                    self.bank
+                        .credit_capped(TransactionKind::Payout, bet.saturating_mul(2));
                     any_win = true;
+                    bet as i64
                 }
                 GameResult::Push => {
-                    self.bank.deposit(hand.bet.amount);
+                    self.bank.credit_capped(TransactionKind::Refund, bet);
                     any_push = true;
+                    0
                 }
                 GameResult::DealerWin => {
                     any_loss = true;
+                    -(bet as i64)
                 }
-                _ => {}
-            }
+                GameResult::Pending => 0,
+            };
+            self.hand_results.push(HandResult {
+                result,
+                bet,
+                net,
+                blackjack: false,
+                doubled: hand.doubled,
+                split,
+            });
         }
 
         // Collapse multiple results into one display result for now
@@ -344,30 +641,135 @@ impl Blackjack {
     fn available_actions(&self) -> Vec<PlayerAction> {
         let mut controls = vec![];
 
-        match self.state {
-            BlackjackState::PlayerTurn { .. } => {
-                let idx = self.current_hand_idx();
-                let hand = &self.table.player_hands[idx];
+        if let BlackjackState::PlayerTurn { .. } = self.state {
+            let idx = self.current_hand_idx();
+            let hand = &self.table.player_hands[idx];
 
-                controls.insert(0, PlayerAction::Stay);
-                controls.insert(0, PlayerAction::Hit);
+            controls.insert(0, PlayerAction::Stay);
+            controls.insert(0, PlayerAction::Hit);
 
-                if rules::can_double(&hand.hand) && self.bank.balance() >= hand.bet.amount {
-                    controls.insert(0, PlayerAction::Double);
-                }
+            if rules::can_double(&hand.hand)
+                && self.bank.can_cover(hand.bet.amount)
+                && self.limits.check_double(hand.bet.amount).is_ok()
+            {
+                controls.insert(0, PlayerAction::Double);
+            }
 
-                if rules::can_split(&hand.hand, self.split_context())
-                    && self.bank.balance() >= hand.bet.amount
-                {
-                    controls.insert(0, PlayerAction::Split);
-                }
+            if rules::can_split(&hand.hand, self.split_context())
+                && self.bank.can_cover(hand.bet.amount)
+                && self.limits.check_split(hand.bet.amount).is_ok()
+            {
+                controls.insert(0, PlayerAction::Split);
             }
-            _ => {}
         }
 
         controls
     }
 
+    /// Exact expected values of each decision on the hand in play, from
+    /// the cards the player has not seen. Returns `None` outside the
+    /// player's turn.
+    pub fn action_values(&self) -> Option<ActionValues> {
+        let BlackjackState::PlayerTurn { .. } = self.state else {
+            return None;
+        };
+        let hand = &self.table.player_hands[self.current_hand_idx()];
+        let (hole, up) = (self.table.dealer_hand[0], self.table.dealer_hand[1]);
+        let unseen =
+            Composition::from_cards(self.shoe.cards().chain(self.shoe.burned()).chain([&hole]));
+
+        let mut analyzer = Analyzer::new().with_dealer_hits_soft_17(self.dealer_hits_soft_17);
+        Some(analyzer.analyze(&hand.hand, &up, &unseen, self.split_context()))
+    }
+
+    /// The Hi-Lo true count of the cards the player has seen since the
+    /// shuffle. The dealer's hole card counts once it is turned over.
+    pub fn true_count(&self) -> f64 {
+        let mut seen = self.shoe.dealt().to_vec();
+        let hole_hidden = matches!(
+            self.state,
+            BlackjackState::Dealing | BlackjackState::PlayerTurn { .. }
+        );
+        if let Some(hole) = self.table.dealer_hand.first().filter(|_| hole_hidden) {
+            if let Some(position) = seen.iter().rposition(|card| card == hole) {
+                seen.remove(position);
+            }
+        }
+
+        let mut count = HiLo::new();
+        count.observe_all(&seen);
+        let unseen = self.shoe.size() - seen.len();
+        count.true_count(unseen)
+    }
+
+    /// The share of the shoe dealt since the last shuffle, from 0 to 1.
+    pub fn penetration(&self) -> f64 {
+        let total = self.shoe.size();
+        let dealt = total - self.shoe.remaining();
+        if total == 0 {
+            return 0.0;
+        }
+        dealt as f64 / total as f64
+    }
+
+    /// The advisor's play for the hand in play at the current true count.
+    /// Returns `None` outside the player's turn.
+    pub fn advice(&self, advisor: &Advisor) -> Option<Advice> {
+        let BlackjackState::PlayerTurn { .. } = self.state else {
+            return None;
+        };
+        let hand = &self.table.player_hands[self.current_hand_idx()];
+        let up = self.table.dealer_hand[1];
+        Some(advisor.advise(&hand.hand, &up, self.true_count(), self.split_context()))
+    }
+
+    /// How each of the player's hands was settled, in the order they were
+    /// played. Empty until the round is over.
+    pub fn hand_results(&self) -> &[HandResult] {
+        &self.hand_results
+    }
+
+    /// The expected value of each available action, from
+    /// [`Blackjack::action_values`]. Returns `None` outside the player's
+    /// turn.
+    pub fn available_action_values(&self) -> Option<Vec<(PlayerAction, f64)>> {
+        let values = self.action_values()?;
+        let available = self
+            .available_actions()
+            .into_iter()
+            .filter_map(|action| {
+                let decision = match action {
+                    PlayerAction::Hit => Decision::Hit,
+                    PlayerAction::Stay => Decision::Stand,
+                    PlayerAction::Double => Decision::Double,
+                    PlayerAction::Split => Decision::Split,
+                };
+                Some((action, values.get(decision)?))
+            })
+            .collect();
+        Some(available)
+    }
+
+    /// The available action with the highest expected value, from
+    /// [`Blackjack::action_values`]. Returns `None` outside the player's
+    /// turn.
+    pub fn best_action(&self) -> Option<PlayerAction> {
+        self.available_action_values()?
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(action, _)| action)
+    }
+
+    /// The stage the round has reached.
+    pub fn state(&self) -> BlackjackState {
+        self.state
+    }
+
+    /// The player's bankroll and its ledger.
+    pub fn bank(&self) -> &Bank {
+        &self.bank
+    }
+
     pub fn view(&self) -> BlackjackView {
         let active_hand_index = self.current_hand_idx();
 
@@ -410,7 +812,7 @@ impl Blackjack {
                         if i == 0 {
                             VisibleCard::FaceDown
                         } else {
-                            VisibleCard::FaceUp(card.clone())
+                            VisibleCard::FaceUp(*card)
                         }
                     })
                     .collect();
@@ -449,6 +851,8 @@ impl Blackjack {
 
             bank_balance: self.bank.balance(),
             total_bet,
+            payout_rule: self.payout_rule,
+            side_bets: self.side_bet_results.clone(),
 
             result: self.result,
 
@@ -462,8 +866,12 @@ impl Blackjack {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bank::bank::Bank;
+    use crate::bank::{
+        bank::Bank,
+        limits::{LimitError, TablePreset},
+    };
     use crate::cards::{Card, Suit, Value};
+    use crate::game::blackjack::side_bets::SideBetOutcome;
 
     #[test]
     fn start_round_withdraws_initial_bet() {
@@ -514,6 +922,47 @@ mod tests {
         assert_eq!(view.phase, BlackjackState::RoundOver);
         assert_eq!(view.result, GameResult::PlayerWin);
         assert_eq!(view.bank_balance, 1_000 - 10 + 25);
+
+        let round = game.bank().ledger().round_summary(game.bank().round());
+        assert_eq!((round.wagered, round.paid), (10, 25));
+    }
+
+    #[test]
+    fn a_full_bankroll_caps_winnings_instead_of_panicking() {
+        let mut game = Blackjack::new().with_bankroll(u32::MAX);
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::SPADES, Value::ACE),
+            Card::new(Suit::CLUBS, Value::NINE),
+            Card::new(Suit::HEARTS, Value::TEN),
+            Card::new(Suit::DIAMONDS, Value::SEVEN),
+        ]);
+
+        game.start_round();
+        let view = game.view();
+
+        assert_eq!(view.result, GameResult::PlayerWin);
+        assert_eq!(view.bank_balance, u32::MAX);
+    }
+
+    #[test]
+    fn odd_naturals_round_with_the_payout_rule() {
+        use crate::bank::chips::Rounding;
+        for (rounding, paid) in [(Rounding::Down, 37), (Rounding::Up, 38)] {
+            let mut game = Blackjack::new().with_payout_rule(PayoutRule::new(1, rounding));
+            game.set_bet(15).unwrap();
+            game.shoe = Shoe::rigged(vec![
+                Card::new(Suit::SPADES, Value::ACE),
+                Card::new(Suit::CLUBS, Value::NINE),
+                Card::new(Suit::HEARTS, Value::TEN),
+                Card::new(Suit::DIAMONDS, Value::SEVEN),
+            ]);
+
+            game.start_round();
+            let view = game.view();
+
+            assert_eq!(view.payout_rule.rounding(), rounding);
+            assert_eq!(view.bank_balance, 1_000 - 15 + paid);
+        }
     }
 
     #[test]
@@ -571,6 +1020,92 @@ mod tests {
         assert_eq!(game.view().player_hands[0].cards.len(), before + 1);
     }
 
+    #[test]
+    fn action_values_are_offered_on_the_players_turn() {
+        let mut game = Blackjack::new();
+        assert_eq!(game.action_values(), None);
+
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::SPADES, Value::FIVE),  // p1
+            Card::new(Suit::HEARTS, Value::SIX),   // d hole
+            Card::new(Suit::CLUBS, Value::FIVE),   // p2 -> 10
+            Card::new(Suit::DIAMONDS, Value::SIX), // d up
+            Card::new(Suit::SPADES, Value::TEN),   // next
+            Card::new(Suit::HEARTS, Value::KING),  // next
+        ]);
+        game.start_round();
+
+        let values = game.action_values().unwrap();
+        // Two of the three unseen cards are tens, so doubling 10 is strong.
+        assert!(values.double.unwrap() > values.stand);
+        assert!(values.split.is_some());
+
+        let available = game.available_action_values().unwrap();
+        let actions: Vec<PlayerAction> = available.iter().map(|(action, _)| *action).collect();
+        assert_eq!(actions, game.available_actions());
+    }
+
+    #[test]
+    fn true_count_skips_the_hidden_hole_card() {
+        let mut game = Blackjack::new();
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::SPADES, Value::TWO),     // p1
+            Card::new(Suit::HEARTS, Value::FIVE),    // d hole
+            Card::new(Suit::CLUBS, Value::THREE),    // p2
+            Card::new(Suit::DIAMONDS, Value::SEVEN), // d up
+            Card::new(Suit::SPADES, Value::KING),    // dealer draws
+        ]);
+        game.start_round();
+
+        // Two low cards seen, with two cards (the hole and the king)
+        // unseen: half a deck at the least.
+        assert_eq!(game.true_count(), 4.0);
+
+        // The short shoe is replaced after the round, and the count
+        // starts again.
+        game.apply(PlayerAction::Stay);
+        assert_eq!(game.true_count(), 0.0);
+    }
+
+    #[test]
+    fn penetration_counts_the_dealt_cards() {
+        // The card burned after the shuffle is gone from the start.
+        let mut game = Blackjack::new();
+        assert_eq!(game.penetration(), 1.0 / 52.0);
+        assert_eq!(game.shoe.burned().len(), 1);
+        game.start_round();
+        assert_eq!(game.penetration(), 5.0 / 52.0);
+    }
+
+    #[test]
+    fn an_empty_shoe_is_refilled_from_the_discards_mid_round() {
+        let mut game = Blackjack::new();
+        let ten = Card::new(Suit::SPADES, Value::TEN);
+        game.shoe = Shoe::rigged(vec![
+            ten,
+            ten,
+            ten,
+            ten,
+            Card::new(Suit::SPADES, Value::EIGHT), // p1
+            Card::new(Suit::HEARTS, Value::FIVE),  // d hole
+            Card::new(Suit::CLUBS, Value::EIGHT),  // p2 -> pair
+            Card::new(Suit::DIAMONDS, Value::SIX), // d up
+        ]);
+        // Earlier rounds dealt the tens.
+        for _ in 0..4 {
+            game.shoe.draw();
+        }
+
+        game.start_round();
+        game.apply(PlayerAction::Split);
+
+        // One ten is burned and two go to the split hands.
+        let view = game.view();
+        assert_eq!(view.player_hands.len(), 2);
+        assert_eq!(game.shoe.remaining(), 1);
+        assert_eq!(game.shoe.burned(), &[ten]);
+    }
+
     #[test]
     fn stay_runs_dealer_and_ends_round() {
         let mut game = Blackjack::new();
@@ -613,6 +1148,128 @@ mod tests {
         assert_eq!(view.phase, BlackjackState::RoundOver);
     }
 
+    #[test]
+    fn hand_results_record_doubles_and_blackjacks() {
+        let mut game = Blackjack::new();
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::SPADES, Value::FIVE),    // p1
+            Card::new(Suit::HEARTS, Value::SIX),     // d hole
+            Card::new(Suit::CLUBS, Value::SIX),      // p2 -> 11
+            Card::new(Suit::DIAMONDS, Value::SEVEN), // d up
+            Card::new(Suit::SPADES, Value::TEN),     // double draw -> 21
+            Card::new(Suit::CLUBS, Value::TEN),      // dealer busts
+        ]);
+        game.start_round();
+        assert!(game.hand_results().is_empty());
+        game.apply(PlayerAction::Double);
+        let results = game.hand_results();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].result, GameResult::PlayerWin);
+        assert_eq!(results[0].net, 20);
+        assert!(results[0].doubled && !results[0].split && !results[0].blackjack);
+
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::SPADES, Value::ACE),
+            Card::new(Suit::HEARTS, Value::SIX),
+            Card::new(Suit::CLUBS, Value::KING),
+            Card::new(Suit::DIAMONDS, Value::SEVEN),
+        ]);
+        game.start_round();
+        let results = game.hand_results();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].blackjack);
+        assert_eq!(results[0].net, 15);
+    }
+
+    #[test]
+    fn bets_are_held_to_the_table_limits() {
+        let mut game = Blackjack::new().with_limits(TablePreset::TwentyFiveDollar.limits());
+        assert_eq!(game.bet(), 25);
+        assert_eq!(
+            game.set_bet(3_000),
+            Err(BlackjackError::Limit(LimitError::AboveMaximum {
+                bet: 3_000,
+                max: 2_500
+            }))
+        );
+        assert_eq!(game.set_bet(2_000), Err(BlackjackError::InsufficientFunds));
+        assert_eq!(game.set_bet(100), Ok(()));
+
+        game.start_round();
+        assert_eq!(game.view().total_bet, 100);
+    }
+
+    #[test]
+    fn side_bets_settle_on_the_initial_deal() {
+        let mut game = Blackjack::new();
+        game.set_side_bet(SideBetKind::PerfectPairs, 5).unwrap();
+        game.set_side_bet(SideBetKind::LuckyLadies, 5).unwrap();
+        game.set_side_bet(SideBetKind::TwentyOnePlusThree, 5)
+            .unwrap();
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::HEARTS, Value::QUEEN),  // p1
+            Card::new(Suit::CLUBS, Value::KING),    // d hole
+            Card::new(Suit::HEARTS, Value::QUEEN),  // p2 -> 20
+            Card::new(Suit::DIAMONDS, Value::FIVE), // d up
+        ]);
+
+        game.start_round();
+        let view = game.view();
+        let outcomes: Vec<_> = view.side_bets.iter().map(|r| r.outcome).collect();
+        assert_eq!(
+            outcomes,
+            [
+                Some(SideBetOutcome::PerfectPair),
+                Some(SideBetOutcome::QueenOfHeartsPair),
+                None
+            ]
+        );
+        assert_eq!(view.bank_balance, 1_000 - 10 - 15 + 5 * 26 + 5 * 201);
+        assert_eq!(view.phase, BlackjackState::PlayerTurn { hand_index: 0 });
+    }
+
+    #[test]
+    fn side_bets_need_funds_and_an_idle_table() {
+        let mut game = Blackjack::new();
+        game.bank = Bank::new(20);
+        assert_eq!(
+            game.set_side_bet(SideBetKind::PerfectPairs, 15),
+            Err(BlackjackError::InsufficientFunds)
+        );
+        game.set_side_bet(SideBetKind::PerfectPairs, 10).unwrap();
+        game.set_side_bet(SideBetKind::PerfectPairs, 0).unwrap();
+        assert!(game.side_bets().is_empty());
+    }
+
+    #[test]
+    fn double_above_the_cap_is_refused() {
+        let mut game = Blackjack::new().with_limits(TableLimits::new(5, 500).with_max_double(5));
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::SPADES, Value::SIX),     // p1
+            Card::new(Suit::HEARTS, Value::SIX),     // d hole
+            Card::new(Suit::CLUBS, Value::FIVE),     // p2 -> 11
+            Card::new(Suit::DIAMONDS, Value::SEVEN), // d up
+        ]);
+
+        game.start_round();
+        assert!(!game
+            .view()
+            .available_actions
+            .contains(&PlayerAction::Double));
+        assert_eq!(
+            game.try_apply(PlayerAction::Double),
+            Err(BlackjackError::Limit(LimitError::DoubleAboveMaximum {
+                stake: 10,
+                max: 5
+            }))
+        );
+        assert_eq!(
+            game.try_apply(PlayerAction::Split),
+            Err(BlackjackError::CannotSplit)
+        );
+        assert_eq!(game.view().total_bet, 10);
+    }
+
     #[test]
     fn split_creates_two_hands_and_withdraws_second_bet() {
         let mut game = Blackjack::new();
@@ -728,6 +1385,40 @@ mod tests {
         assert_eq!(view.phase, BlackjackState::RoundOver);
     }
 
+    #[test]
+    fn dealer_can_hit_soft_17() {
+        let mut game = Blackjack::new().with_dealer_hits_soft_17(true);
+        game.shoe = Shoe::rigged(vec![
+            Card::new(Suit::SPADES, Value::TEN),   // p1
+            Card::new(Suit::HEARTS, Value::ACE),   // d hole
+            Card::new(Suit::CLUBS, Value::SEVEN),  // p2
+            Card::new(Suit::DIAMONDS, Value::SIX), // d up -> soft 17
+            Card::new(Suit::CLUBS, Value::TWO),    // dealer hits -> 19
+        ]);
+
+        game.start_round();
+        game.apply(PlayerAction::Stay);
+
+        assert_eq!(game.table.dealer_hand.len(), 3);
+        assert_eq!(game.view().result, GameResult::DealerWin);
+    }
+
+    #[test]
+    fn seeded_shoes_deal_the_same_cards() {
+        let deal = |seed| {
+            let mut game = Blackjack::new().with_decks(6).with_seed(seed);
+            game.start_round();
+            game.shoe.dealt().to_vec()
+        };
+        assert_eq!(deal(42), deal(42));
+        assert_ne!(deal(42), deal(43));
+
+        let game = Blackjack::new().with_decks(6).with_bankroll(250);
+        assert_eq!(game.shoe.remaining(), 311);
+        assert_eq!(game.bank().balance(), 250);
+        assert_eq!(game.decks(), 6);
+    }
+
     #[test]
     fn dealer_hits_on_16() {
         let mut game = Blackjack::new();
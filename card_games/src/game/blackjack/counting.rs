@@ -0,0 +1,98 @@
+//! Card counting.
+//!
+//! The Hi-Lo count tags low cards +1 and tens and aces −1, so a high
+//! running count means the cards still to come are rich in tens and aces.
+//! Dividing by the decks left gives the true count that index plays and
+//! bet ramps are keyed on.
+
+use crate::cards::Card;
+
+/// The Hi-Lo tag of a card: +1 for two to six, 0 for seven to nine and −1
+/// for tens and aces. Jokers count 0.
+pub fn hi_lo(card: &Card) -> i32 {
+    match card.rank() {
+        Some(2..=6) => 1,
+        Some(7..=9) | None => 0,
+        Some(_) => -1,
+    }
+}
+
+/// A running Hi-Lo count.
+///
+/// # Example
+/// ```
+/// use card_games::cards::parse_cards;
+/// use card_games::game::blackjack::counting::HiLo;
+/// let mut count = HiLo::new();
+/// count.observe_all(&parse_cards("2h 5c 6d Ks 8c").unwrap());
+/// assert_eq!(count.running(), 2);
+/// // Two more decks to come.
+/// assert_eq!(count.true_count(104), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HiLo {
+    running: i32,
+    seen: u32,
+}
+
+impl HiLo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, card: &Card) {
+        self.running += hi_lo(card);
+        self.seen += 1;
+    }
+
+    pub fn observe_all<'a, I: IntoIterator<Item = &'a Card>>(&mut self, cards: I) {
+        cards.into_iter().for_each(|card| self.observe(card));
+    }
+
+    pub fn running(&self) -> i32 {
+        self.running
+    }
+
+    /// Number of cards counted.
+    pub fn seen(&self) -> u32 {
+        self.seen
+    }
+
+    /// The running count per deck still to be dealt, given how many cards
+    /// are unseen. Less than half a deck counts as half a deck.
+    pub fn true_count(&self, unseen_cards: usize) -> f64 {
+        let decks = (unseen_cards as f64 / 52.0).max(0.5);
+        self.running as f64 / decks
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{deck_builder::DeckBuilder, parse_cards};
+
+    #[test]
+    fn a_full_deck_counts_to_zero() {
+        let deck = DeckBuilder::new().standard52().build();
+        let mut count = HiLo::new();
+        count.observe_all(deck.iter());
+        assert_eq!(count.running(), 0);
+        assert_eq!(count.seen(), 52);
+    }
+
+    #[test]
+    fn true_count_divides_by_decks_left() {
+        let mut count = HiLo::new();
+        count.observe_all(&parse_cards("As Kd Qc Jh Ts 9d").unwrap());
+        assert_eq!(count.running(), -5);
+        assert_eq!(count.true_count(260), -1.0);
+        assert_eq!(count.true_count(10), -10.0);
+
+        count.reset();
+        assert_eq!(count, HiLo::new());
+    }
+}
@@ -0,0 +1,383 @@
+//! Probabilities of the dealer's final hand.
+//!
+//! The dealer has no choices to make: it draws by
+//! [`rules::dealer_should_hit`], or [`rules::dealer_should_hit_soft_17`] at
+//! tables where it hits soft 17, until it stands or busts. Given its upcard
+//! and what is left in the shoe, every finish can therefore be weighed
+//! exactly. [`DealerTable`] lays the results out for every upcard, as
+//! printed in strategy books.
+
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use crate::cards::Card;
+
+use super::{
+    analysis::{card_of, rank_index, Composition, ACE, RANKS, TEN},
+    rules,
+};
+
+/// The dealer's cards, counted by rank.
+type DealerHand = [u8; RANKS];
+
+/// What decides how a drawing hand finishes: its hard total, whether it
+/// holds an ace and whether it is still one card that could become a
+/// blackjack. Hands that agree on these share a cache entry even when
+/// their cards differ.
+type DrawState = (u8, bool, bool);
+
+/// The [`DrawState`] of a hand.
+fn draw_state(hand: &DealerHand) -> DrawState {
+    let hard: u32 = hand
+        .iter()
+        .enumerate()
+        .map(|(rank, count)| (rank as u32 + 1) * *count as u32)
+        .sum();
+    let cards: u8 = hand.iter().sum();
+    (hard as u8, hand[ACE] > 0, cards == 1)
+}
+
+/// Scores the hand with [`rules`]: whether it is a blackjack, whether the
+/// dealer must draw, and its total.
+fn score(hand: &DealerHand, hits_soft_17: bool) -> (bool, bool, u8) {
+    // No hand the dealer draws to holds more than 21 cards.
+    let mut cards = [card_of(ACE); 21];
+    let mut len = 0;
+    for (rank, count) in hand.iter().enumerate() {
+        for card in &mut cards[len..len + *count as usize] {
+            *card = card_of(rank);
+        }
+        len += *count as usize;
+    }
+    let cards = &cards[..len];
+    (
+        rules::is_blackjack(cards),
+        match hits_soft_17 {
+            true => rules::dealer_should_hit_soft_17(cards),
+            false => rules::dealer_should_hit(cards),
+        },
+        rules::hand_score(cards),
+    )
+}
+
+/// How the dealer's hand finishes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DealerOutcomes {
+    /// Chance of finishing on 17, 18, 19, 20 and 21, not counting
+    /// blackjacks.
+    pub totals: [f64; 5],
+    pub blackjack: f64,
+    pub bust: f64,
+}
+
+impl DealerOutcomes {
+    /// Chance the dealer finishes on `total`, for totals 17–21.
+    pub fn total(&self, total: u8) -> f64 {
+        match total {
+            17..=21 => self.totals[total as usize - 17],
+            _ => 0.0,
+        }
+    }
+
+    fn add(&mut self, other: &DealerOutcomes, weight: f64) {
+        for (mine, theirs) in self.totals.iter_mut().zip(other.totals) {
+            *mine += weight * theirs;
+        }
+        self.blackjack += weight * other.blackjack;
+        self.bust += weight * other.bust;
+    }
+}
+
+/// Where the dealer's next card comes from.
+pub(super) trait DrawSource: Copy + Eq + Hash {
+    /// The chance of drawing each rank next.
+    fn chances(&self) -> [f64; RANKS];
+
+    /// The source after `rank` has been drawn.
+    fn after(&self, rank: usize) -> Self;
+}
+
+impl DrawSource for Composition {
+    fn chances(&self) -> [f64; RANKS] {
+        let total = self.total().max(1) as f64;
+        self.counts.map(|count| count as f64 / total)
+    }
+
+    fn after(&self, rank: usize) -> Self {
+        let mut next = *self;
+        next.counts[rank] -= 1;
+        next
+    }
+}
+
+/// A shoe so large that drawing a card does not change the odds of the
+/// next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct InfiniteDeck;
+
+impl DrawSource for InfiniteDeck {
+    fn chances(&self) -> [f64; RANKS] {
+        let mut chances = [1.0 / 13.0; RANKS];
+        chances[TEN] = 4.0 / 13.0;
+        chances
+    }
+
+    fn after(&self, _rank: usize) -> Self {
+        *self
+    }
+}
+
+/// Memoized dealer outcomes for every hand reached from a source.
+#[derive(Debug)]
+pub(super) struct DealerCache<S> {
+    finishes: HashMap<(S, DrawState), DealerOutcomes>,
+    hits_soft_17: bool,
+}
+
+impl<S> Default for DealerCache<S> {
+    /// A dealer who stands on all 17s.
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl<S> DealerCache<S> {
+    pub(super) fn new(hits_soft_17: bool) -> Self {
+        DealerCache {
+            finishes: HashMap::new(),
+            hits_soft_17,
+        }
+    }
+}
+
+impl<S: DrawSource> DealerCache<S> {
+    /// Outcomes for an upcard, counting dealer blackjacks.
+    pub(super) fn showing(&mut self, up: usize, source: &S) -> DealerOutcomes {
+        let mut hand = [0; RANKS];
+        hand[up] = 1;
+        self.finish(source, hand)
+    }
+
+    /// Outcomes for an upcard once the dealer has checked its hole card
+    /// and found no blackjack.
+    pub(super) fn after_peek(&mut self, up: usize, source: &S) -> DealerOutcomes {
+        let excluded = match up {
+            ACE => Some(TEN),
+            TEN => Some(ACE),
+            _ => None,
+        };
+        let mut holes = source.chances();
+        if let Some(excluded) = excluded {
+            holes[excluded] = 0.0;
+        }
+        let allowed: f64 = holes.iter().sum();
+
+        let mut outcomes = DealerOutcomes::default();
+        for (rank, p) in holes.into_iter().enumerate().filter(|(_, p)| *p > 0.0) {
+            let mut hand = [0; RANKS];
+            hand[up] = 1;
+            hand[rank] += 1;
+            let finished = self.finish(&source.after(rank), hand);
+            outcomes.add(&finished, p / allowed);
+        }
+        outcomes
+    }
+
+    fn finish(&mut self, source: &S, hand: DealerHand) -> DealerOutcomes {
+        let (blackjack, should_hit, total) = score(&hand, self.hits_soft_17);
+        let chances = source.chances();
+        let mut outcomes = DealerOutcomes::default();
+        if blackjack {
+            outcomes.blackjack = 1.0;
+            return outcomes;
+        }
+        if !should_hit || chances.iter().all(|p| *p == 0.0) {
+            match total {
+                17..=21 => outcomes.totals[total as usize - 17] = 1.0,
+                // A dealer stranded below 17 by an empty shoe is beaten
+                // by any standing hand; count it with the busts.
+                _ => outcomes.bust = 1.0,
+            }
+            return outcomes;
+        }
+
+        let key = (*source, draw_state(&hand));
+        if let Some(cached) = self.finishes.get(&key) {
+            return *cached;
+        }
+        for (rank, p) in chances.into_iter().enumerate().filter(|(_, p)| *p > 0.0) {
+            let mut next = hand;
+            next[rank] += 1;
+            let finished = self.finish(&source.after(rank), next);
+            outcomes.add(&finished, p);
+        }
+        self.finishes.insert(key, outcomes);
+        outcomes
+    }
+}
+
+/// Chances of each dealer finish for `upcard` drawing from `shoe`,
+/// blackjacks included.
+///
+/// # Example
+/// ```
+/// use card_games::game::blackjack::analysis::Composition;
+/// use card_games::game::blackjack::dealer::dealer_outcomes;
+/// let up = "As".parse().unwrap();
+/// let outcomes = dealer_outcomes(&up, &Composition::standard(1).without(&[up]));
+/// // Sixteen of the remaining 51 cards are tens.
+/// assert!((outcomes.blackjack - 16.0 / 51.0).abs() < 1e-12);
+/// ```
+pub fn dealer_outcomes(upcard: &Card, shoe: &Composition) -> DealerOutcomes {
+    DealerCache::default().showing(rank_index(upcard).unwrap_or(TEN), shoe)
+}
+
+/// Chances of each dealer finish for `upcard` from an infinite deck,
+/// blackjacks included.
+pub fn infinite_deck_outcomes(upcard: &Card) -> DealerOutcomes {
+    DealerCache::default().showing(rank_index(upcard).unwrap_or(TEN), &InfiniteDeck)
+}
+
+/// Dealer outcomes for every upcard, two through ace.
+///
+/// # Example
+/// ```
+/// use card_games::game::blackjack::dealer::DealerTable;
+/// let table = DealerTable::infinite_deck();
+/// assert_eq!(table.rows().len(), 10);
+/// println!("{}", table);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DealerTable {
+    title: String,
+    rows: Vec<(Card, DealerOutcomes)>,
+}
+
+impl DealerTable {
+    pub fn infinite_deck() -> Self {
+        let mut cache = DealerCache::default();
+        Self::build("Infinite deck".to_string(), |up| {
+            cache.showing(up, &InfiniteDeck)
+        })
+    }
+
+    /// Outcomes with each upcard taken from `shoe` before the dealer
+    /// draws.
+    pub fn for_shoe(shoe: &Composition) -> Self {
+        let mut cache = DealerCache::default();
+        let title = format!("{}-card shoe", shoe.total());
+        Self::build(title, |up| {
+            if shoe.counts[up] == 0 {
+                return DealerOutcomes::default();
+            }
+            cache.showing(up, &shoe.after(up))
+        })
+    }
+
+    /// A table for `decks` full decks.
+    pub fn for_decks(decks: u32) -> Self {
+        let mut table = Self::for_shoe(&Composition::standard(decks));
+        table.title = match decks {
+            1 => "Single deck".to_string(),
+            _ => format!("{} decks", decks),
+        };
+        table
+    }
+
+    fn build(title: String, mut outcomes: impl FnMut(usize) -> DealerOutcomes) -> Self {
+        let order = (1..RANKS).chain([ACE]);
+        let rows = order.map(|up| (card_of(up), outcomes(up))).collect();
+        DealerTable { title, rows }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Each upcard with its outcomes, two first and ace last.
+    pub fn rows(&self) -> &[(Card, DealerOutcomes)] {
+        &self.rows
+    }
+}
+
+impl Display for DealerTable {
+    /// One row per upcard, with each finish as a percentage.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.title)?;
+        write!(f, "Up")?;
+        for heading in ["17", "18", "19", "20", "21", "BJ", "Bust"] {
+            write!(f, " {:>7}", heading)?;
+        }
+        writeln!(f)?;
+
+        for (up, outcomes) in &self.rows {
+            write!(f, "{:<2}", up.value().short())?;
+            for p in outcomes
+                .totals
+                .iter()
+                .chain([&outcomes.blackjack, &outcomes.bust])
+            {
+                write!(f, " {:>6.2}%", p * 100.0)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum(outcomes: &DealerOutcomes) -> f64 {
+        outcomes.totals.iter().sum::<f64>() + outcomes.blackjack + outcomes.bust
+    }
+
+    #[test]
+    fn every_row_sums_to_one() {
+        for table in [DealerTable::infinite_deck(), DealerTable::for_decks(1)] {
+            for (up, outcomes) in table.rows() {
+                assert!((sum(outcomes) - 1.0).abs() < 1e-9, "{}", up);
+            }
+        }
+    }
+
+    #[test]
+    fn infinite_deck_matches_published_figures() {
+        let six = infinite_deck_outcomes(&"6h".parse().unwrap());
+        assert!((six.bust - 0.4232).abs() < 1e-4, "{}", six.bust);
+        assert_eq!(six.blackjack, 0.0);
+
+        let ace = infinite_deck_outcomes(&"Ah".parse().unwrap());
+        assert!((ace.blackjack - 4.0 / 13.0).abs() < 1e-12);
+        assert!((ace.bust - 0.1153).abs() < 1e-4, "{}", ace.bust);
+    }
+
+    #[test]
+    fn peeking_removes_blackjacks() {
+        let mut cache = DealerCache::default();
+        let peeked = cache.after_peek(TEN, &InfiniteDeck);
+        assert_eq!(peeked.blackjack, 0.0);
+        assert!((sum(&peeked) - 1.0).abs() < 1e-9);
+        assert!((peeked.bust - 0.2304).abs() < 1e-3, "{}", peeked.bust);
+    }
+
+    #[test]
+    fn hitting_soft_17_busts_more_often() {
+        let stands = DealerCache::new(false).showing(5, &InfiniteDeck);
+        let hits = DealerCache::new(true).showing(5, &InfiniteDeck);
+        assert!(hits.bust > stands.bust);
+        assert!(hits.total(17) < stands.total(17));
+        assert!((sum(&hits) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn table_prints_a_row_per_upcard() {
+        let printed = DealerTable::for_decks(6).to_string();
+        let lines: Vec<&str> = printed.lines().collect();
+        assert_eq!(lines[0], "6 decks");
+        assert!(lines[1].starts_with("Up"));
+        assert!(lines[2].starts_with("2 "));
+        assert!(lines[11].starts_with("A "));
+        assert_eq!(lines.len(), 12);
+    }
+}
@@ -0,0 +1,828 @@
+//! Count-based index plays.
+//!
+//! Basic strategy is the best play off the top of the shoe, but a handful
+//! of close decisions flip once the true count moves far enough. An
+//! [`IndexTable`] lists those plays with the count at which each takes
+//! over: the Illustrious 18 and the Fab 4 surrenders ship built in, and
+//! other tables can be loaded from a file. An [`Advisor`] looks a hand up
+//! in a [`StrategyChart`] and says when a deviation overrides it.
+//!
+//! Tables are plain text, one play per line, with `#` starting a comment:
+//!
+//! ```text
+//! # hand  upcard  play       true count
+//! 16      T       stand      >=0
+//! 13      2       hit        <-1
+//! T,T     5       split      >=5
+//! insurance A     insure     >=3
+//! ```
+//!
+//! Hands are written as in the chart: a hard total, `A,7` for soft hands
+//! and `8,8` for pairs.
+
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use crate::cards::{hand::Hand, Card, Value};
+
+use super::{
+    rules::{self, SplitContext},
+    strategy::{ChartAction, StrategyChart},
+    types::PlayerAction,
+};
+
+/// The hand an index play applies to. Ranks run from 1 (ace) to 10 (any
+/// ten-value card).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexHand {
+    Hard(u8),
+    Soft(u8),
+    Pair(u8),
+    /// The insurance bet offered against an ace.
+    Insurance,
+}
+
+impl IndexHand {
+    /// Classifies the player's cards, treating a pair as a pair only when
+    /// it may still be split.
+    fn of(cards: &[Card], context: SplitContext) -> Self {
+        let mut hand = Hand::new();
+        cards.iter().for_each(|card| hand.add(*card));
+        if rules::can_split(&hand, context) {
+            return IndexHand::Pair(blackjack_rank(&cards[0]));
+        }
+        Self::total_of(cards)
+    }
+
+    /// Classifies the player's cards by total alone.
+    fn total_of(cards: &[Card]) -> Self {
+        let total = rules::hand_score(cards);
+        let hard: u8 = cards.iter().map(blackjack_rank).sum();
+        if hard != total {
+            IndexHand::Soft(total)
+        } else {
+            IndexHand::Hard(total)
+        }
+    }
+}
+
+impl Display for IndexHand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexHand::Hard(total) => write!(f, "{}", total),
+            IndexHand::Soft(total) => write!(f, "A,{}", rank_label(total.saturating_sub(11))),
+            IndexHand::Pair(rank) => write!(f, "{0},{0}", rank_label(*rank)),
+            IndexHand::Insurance => write!(f, "insurance"),
+        }
+    }
+}
+
+impl IndexHand {
+    fn parse(s: &str) -> Option<Self> {
+        if s.eq_ignore_ascii_case("insurance") {
+            return Some(IndexHand::Insurance);
+        }
+        if let Some((first, second)) = s.split_once(',') {
+            let (first, second) = (parse_rank(first)?, parse_rank(second)?);
+            return match (first, second) {
+                _ if first == second => Some(IndexHand::Pair(first)),
+                (1, other) | (other, 1) => Some(IndexHand::Soft(11 + other)),
+                _ => None,
+            };
+        }
+        match s.parse() {
+            Ok(total @ 4..=21) => Some(IndexHand::Hard(total)),
+            _ => None,
+        }
+    }
+}
+
+/// The card's rank counting tens, jacks, queens and kings as 10.
+fn blackjack_rank(card: &Card) -> u8 {
+    card.rank().unwrap_or(0).min(10)
+}
+
+fn rank_label(rank: u8) -> &'static str {
+    match rank {
+        1 => "A",
+        2 => "2",
+        3 => "3",
+        4 => "4",
+        5 => "5",
+        6 => "6",
+        7 => "7",
+        8 => "8",
+        9 => "9",
+        _ => "T",
+    }
+}
+
+fn parse_rank(s: &str) -> Option<u8> {
+    let value: Value = s.parse().ok()?;
+    value.rank().map(|rank| rank.min(10))
+}
+
+/// A play an index can call for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Play {
+    Hit,
+    Stand,
+    Double,
+    Split,
+    Surrender,
+    /// Take insurance against a dealer ace.
+    Insure,
+}
+
+impl Play {
+    pub const ALL: [Play; 6] = [
+        Play::Hit,
+        Play::Stand,
+        Play::Double,
+        Play::Split,
+        Play::Surrender,
+        Play::Insure,
+    ];
+
+    /// The engine action for this play, if the engine offers it.
+    pub fn action(&self) -> Option<PlayerAction> {
+        match self {
+            Play::Hit => Some(PlayerAction::Hit),
+            Play::Stand => Some(PlayerAction::Stay),
+            Play::Double => Some(PlayerAction::Double),
+            Play::Split => Some(PlayerAction::Split),
+            Play::Surrender | Play::Insure => None,
+        }
+    }
+}
+
+impl Display for Play {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Play::Hit => "hit",
+            Play::Stand => "stand",
+            Play::Double => "double",
+            Play::Split => "split",
+            Play::Surrender => "surrender",
+            Play::Insure => "insure",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Play {
+    fn parse(s: &str) -> Option<Self> {
+        Play::ALL
+            .into_iter()
+            .find(|play| play.to_string().eq_ignore_ascii_case(s))
+    }
+}
+
+/// The true counts at which an index play is made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Threshold {
+    AtOrAbove(i32),
+    Below(i32),
+}
+
+impl Threshold {
+    pub fn applies(&self, true_count: f64) -> bool {
+        match *self {
+            Threshold::AtOrAbove(index) => true_count >= index as f64,
+            Threshold::Below(index) => true_count < index as f64,
+        }
+    }
+}
+
+impl Display for Threshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Threshold::AtOrAbove(index) => write!(f, ">={}", index),
+            Threshold::Below(index) => write!(f, "<{}", index),
+        }
+    }
+}
+
+impl Threshold {
+    fn parse(s: &str) -> Option<Self> {
+        if let Some(index) = s.strip_prefix(">=") {
+            index.trim().parse().ok().map(Threshold::AtOrAbove)
+        } else if let Some(index) = s.strip_prefix('<') {
+            index.trim().parse().ok().map(Threshold::Below)
+        } else {
+            None
+        }
+    }
+}
+
+/// One count-based deviation: make `play` with `hand` against `upcard`
+/// when the true count passes `threshold`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndexPlay {
+    pub hand: IndexHand,
+    /// The dealer's upcard rank, 1 (ace) to 10.
+    pub upcard: u8,
+    pub play: Play,
+    pub threshold: Threshold,
+}
+
+impl IndexPlay {
+    pub fn new(hand: IndexHand, upcard: u8, play: Play, threshold: Threshold) -> Self {
+        IndexPlay {
+            hand,
+            upcard,
+            play,
+            threshold,
+        }
+    }
+}
+
+impl Display for IndexPlay {
+    /// Formats the play as a line of an index file: `16 T stand >=0`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.hand,
+            rank_label(self.upcard),
+            self.play,
+            self.threshold
+        )
+    }
+}
+
+/// A list of index plays, checked in order.
+///
+/// # Example
+/// ```
+/// use card_games::game::blackjack::deviations::IndexTable;
+/// let table: IndexTable = "16 T stand >=0\n12 4 hit <0".parse().unwrap();
+/// assert_eq!(table.plays().len(), 2);
+/// assert_eq!(IndexTable::illustrious_18().plays().len(), 18);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexTable {
+    plays: Vec<IndexPlay>,
+}
+
+impl IndexTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The eighteen Hi-Lo indices that gain the most, for a multi-deck
+    /// game where the dealer stands on soft 17.
+    pub fn illustrious_18() -> Self {
+        use IndexHand::*;
+        use Play::*;
+        use Threshold::*;
+
+        let plays = [
+            (Insurance, 1, Insure, AtOrAbove(3)),
+            (Hard(16), 10, Stand, AtOrAbove(0)),
+            (Hard(15), 10, Stand, AtOrAbove(4)),
+            (Pair(10), 5, Split, AtOrAbove(5)),
+            (Pair(10), 6, Split, AtOrAbove(4)),
+            (Hard(10), 10, Double, AtOrAbove(4)),
+            (Hard(12), 3, Stand, AtOrAbove(2)),
+            (Hard(12), 2, Stand, AtOrAbove(3)),
+            (Hard(11), 1, Double, AtOrAbove(1)),
+            (Hard(9), 2, Double, AtOrAbove(1)),
+            (Hard(10), 1, Double, AtOrAbove(4)),
+            (Hard(9), 7, Double, AtOrAbove(3)),
+            (Hard(16), 9, Stand, AtOrAbove(5)),
+            (Hard(13), 2, Hit, Below(-1)),
+            (Hard(12), 4, Hit, Below(0)),
+            (Hard(12), 5, Hit, Below(-2)),
+            (Hard(12), 6, Hit, Below(-1)),
+            (Hard(13), 3, Hit, Below(-2)),
+        ];
+        Self::from_plays(&plays)
+    }
+
+    /// The four surrender indices that matter most.
+    pub fn fab_4() -> Self {
+        use IndexHand::*;
+        use Play::*;
+        use Threshold::*;
+
+        let plays = [
+            (Hard(14), 10, Surrender, AtOrAbove(3)),
+            (Hard(15), 10, Surrender, AtOrAbove(0)),
+            (Hard(15), 9, Surrender, AtOrAbove(2)),
+            (Hard(15), 1, Surrender, AtOrAbove(1)),
+        ];
+        Self::from_plays(&plays)
+    }
+
+    /// The Fab 4 followed by the Illustrious 18, so that surrender is
+    /// preferred where it is offered.
+    pub fn standard() -> Self {
+        let mut table = Self::fab_4();
+        table.plays.extend(Self::illustrious_18().plays);
+        table
+    }
+
+    fn from_plays(plays: &[(IndexHand, u8, Play, Threshold)]) -> Self {
+        IndexTable {
+            plays: plays
+                .iter()
+                .map(|(hand, up, play, threshold)| IndexPlay::new(*hand, *up, *play, *threshold))
+                .collect(),
+        }
+    }
+
+    pub fn with_play(mut self, play: IndexPlay) -> Self {
+        self.plays.push(play);
+        self
+    }
+
+    pub fn plays(&self) -> &[IndexPlay] {
+        &self.plays
+    }
+
+    /// Reads a table from a file in the format described in the module
+    /// documentation.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, IndexTableError> {
+        std::fs::read_to_string(path)
+            .map_err(IndexTableError::Io)?
+            .parse()
+    }
+
+    /// Writes the table to a file that [`IndexTable::load`] reads back.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), IndexTableError> {
+        std::fs::write(path, self.to_string()).map_err(IndexTableError::Io)
+    }
+
+    /// The index plays for a hand and upcard that apply at `true_count`.
+    fn applying(
+        &self,
+        hand: IndexHand,
+        upcard: u8,
+        true_count: f64,
+    ) -> impl Iterator<Item = &IndexPlay> {
+        self.plays.iter().filter(move |play| {
+            play.hand == hand && play.upcard == upcard && play.threshold.applies(true_count)
+        })
+    }
+}
+
+impl Display for IndexTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# hand upcard play true-count")?;
+        for play in &self.plays {
+            writeln!(f, "{}", play)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for IndexTable {
+    type Err = IndexTableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table = IndexTable::new();
+        for (number, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || IndexTableError::InvalidLine {
+                line: number + 1,
+                text: line.to_string(),
+            };
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [hand, upcard, play, threshold] = fields[..] else {
+                return Err(invalid());
+            };
+            table.plays.push(IndexPlay {
+                hand: IndexHand::parse(hand).ok_or_else(invalid)?,
+                upcard: parse_rank(upcard).ok_or_else(invalid)?,
+                play: Play::parse(play).ok_or_else(invalid)?,
+                threshold: Threshold::parse(threshold).ok_or_else(invalid)?,
+            });
+        }
+        Ok(table)
+    }
+}
+
+/// A failure reading or writing an index table.
+#[derive(Debug)]
+pub enum IndexTableError {
+    Io(std::io::Error),
+    /// A line that is not `hand upcard play threshold`.
+    InvalidLine {
+        line: usize,
+        text: String,
+    },
+}
+
+impl Display for IndexTableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexTableError::Io(e) => write!(f, "Cannot read the index table: {}", e),
+            IndexTableError::InvalidLine { line, text } => write!(
+                f,
+                "Line {} is not `hand upcard play threshold`: '{}'",
+                line, text
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IndexTableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IndexTableError::Io(e) => Some(e),
+            IndexTableError::InvalidLine { .. } => None,
+        }
+    }
+}
+
+/// What the [`Advisor`] recommends for one hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Advice {
+    pub play: Play,
+    /// What basic strategy alone would do.
+    pub basic: Play,
+    /// The index play that overrides basic strategy, if any.
+    pub deviation: Option<IndexPlay>,
+    /// The best play among the engine's actions, for when `play` is a
+    /// surrender the table does not offer.
+    pub action: PlayerAction,
+}
+
+impl Advice {
+    pub fn is_deviation(&self) -> bool {
+        self.deviation.is_some()
+    }
+}
+
+impl Display for Advice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.deviation {
+            None => write!(f, "Basic strategy: {}", self.play),
+            Some(index) => write!(
+                f,
+                "Deviation: {} instead of {} ({} vs {} at true count {})",
+                self.play,
+                self.basic,
+                index.hand,
+                rank_label(index.upcard),
+                index.threshold
+            ),
+        }
+    }
+}
+
+/// Recommends plays from a basic-strategy chart and a table of index
+/// plays.
+///
+/// # Example
+/// ```no_run
+/// use card_games::cards::parse_cards;
+/// use card_games::game::blackjack::deviations::{Advisor, IndexTable, Play};
+/// use card_games::game::blackjack::rules::SplitContext;
+/// use card_games::game::blackjack::strategy::{StrategyChart, StrategyRules};
+///
+/// let chart = StrategyChart::generate(&StrategyRules::new(6));
+/// let advisor = Advisor::new(chart).with_indices(IndexTable::illustrious_18());
+///
+/// let hand = parse_cards("Th 6c").unwrap();
+/// let ten = "Kd".parse().unwrap();
+/// let advice = advisor.advise(&hand, &ten, 1.0, SplitContext::NoPreviousSplit);
+/// assert_eq!(advice.play, Play::Stand);
+/// assert!(advice.is_deviation());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Advisor {
+    chart: StrategyChart,
+    indices: IndexTable,
+}
+
+impl Advisor {
+    /// An advisor that plays basic strategy only.
+    pub fn new(chart: StrategyChart) -> Self {
+        Advisor {
+            chart,
+            indices: IndexTable::new(),
+        }
+    }
+
+    pub fn with_indices(mut self, indices: IndexTable) -> Self {
+        self.indices = indices;
+        self
+    }
+
+    pub fn chart(&self) -> &StrategyChart {
+        &self.chart
+    }
+
+    pub fn indices(&self) -> &IndexTable {
+        &self.indices
+    }
+
+    /// Advises on the player's hand against `upcard` at `true_count`.
+    pub fn advise(
+        &self,
+        hand: &[Card],
+        upcard: &Card,
+        true_count: f64,
+        context: SplitContext,
+    ) -> Advice {
+        let may_surrender = self.chart.rules().surrender
+            && hand.len() == 2
+            && context == SplitContext::NoPreviousSplit;
+        let (play, basic, deviation) =
+            self.decide(hand, upcard, true_count, context, may_surrender);
+
+        let action = match play.action() {
+            Some(action) => action,
+            None => {
+                let (fallback, _, _) = self.decide(hand, upcard, true_count, context, false);
+                fallback.action().unwrap_or(PlayerAction::Hit)
+            }
+        };
+
+        Advice {
+            play,
+            basic,
+            deviation,
+            action,
+        }
+    }
+
+    /// Whether to take insurance against an ace at `true_count`.
+    pub fn should_insure(&self, true_count: f64) -> bool {
+        self.indices
+            .applying(IndexHand::Insurance, 1, true_count)
+            .any(|index| index.play == Play::Insure)
+    }
+
+    fn decide(
+        &self,
+        cards: &[Card],
+        upcard: &Card,
+        true_count: f64,
+        context: SplitContext,
+        may_surrender: bool,
+    ) -> (Play, Play, Option<IndexPlay>) {
+        let may_double = rules::can_double(cards);
+        let hand = IndexHand::of(cards, context);
+        let basic = self.basic(hand, cards, upcard, may_double, may_surrender);
+
+        // A pair that basic strategy does not split, such as 5,5, plays
+        // by its total.
+        let mut hands = vec![hand];
+        if matches!(hand, IndexHand::Pair(_)) && basic != Play::Split {
+            hands.push(IndexHand::total_of(cards));
+        }
+
+        let available = |play: &Play| match play {
+            Play::Hit | Play::Stand => true,
+            Play::Double => may_double,
+            Play::Split => matches!(hand, IndexHand::Pair(_)),
+            Play::Surrender => may_surrender,
+            Play::Insure => false,
+        };
+        let up = blackjack_rank(upcard);
+        let index = hands
+            .into_iter()
+            .flat_map(|hand| self.indices.applying(hand, up, true_count))
+            .find(|index| available(&index.play));
+
+        match index {
+            Some(index) if index.play != basic => (index.play, basic, Some(*index)),
+            _ => (basic, basic, None),
+        }
+    }
+
+    fn basic(
+        &self,
+        hand: IndexHand,
+        cards: &[Card],
+        upcard: &Card,
+        may_double: bool,
+        may_surrender: bool,
+    ) -> Play {
+        let charted = match hand {
+            IndexHand::Pair(_) => self.chart.pair(&cards[0], upcard),
+            IndexHand::Soft(total @ 13..=20) => self.chart.soft(total, upcard),
+            IndexHand::Soft(total) | IndexHand::Hard(total) if total >= 21 => {
+                Some(ChartAction::Stand)
+            }
+            IndexHand::Soft(_) => Some(ChartAction::Hit),
+            IndexHand::Hard(total) => self.chart.hard(total, upcard),
+            IndexHand::Insurance => None,
+        };
+
+        match charted.unwrap_or(ChartAction::Hit) {
+            ChartAction::Hit => Play::Hit,
+            ChartAction::Stand => Play::Stand,
+            ChartAction::Split => Play::Split,
+            ChartAction::DoubleOrHit if may_double => Play::Double,
+            ChartAction::DoubleOrHit => Play::Hit,
+            ChartAction::DoubleOrStand if may_double => Play::Double,
+            ChartAction::DoubleOrStand => Play::Stand,
+            ChartAction::SurrenderOrHit if may_surrender => Play::Surrender,
+            ChartAction::SurrenderOrHit => Play::Hit,
+            ChartAction::SurrenderOrStand if may_surrender => Play::Surrender,
+            ChartAction::SurrenderOrStand => Play::Stand,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::parse_cards;
+    use crate::game::blackjack::strategy::{ChartRow, StrategyRules};
+
+    /// The six-deck chart for the engine's rules, as generated.
+    const SIX_DECKS: [&str; 31] = [
+        "H H H H H H H H H H",
+        "H H H H H H H H H H",
+        "H H H H H H H H H H",
+        "H H H H H H H H H H",
+        "H D D D D H H H H H",
+        "D D D D D D D D H H",
+        "D D D D D D D D D H",
+        "H H S S S H H H H H",
+        "S S S S S H H H H H",
+        "S S S S S H H H H H",
+        "S S S S S H H H H H",
+        "S S S S S H H H H H",
+        "S S S S S S S S S S",
+        "H H H D D H H H H H",
+        "H H H D D H H H H H",
+        "H H D D D H H H H H",
+        "H H D D D H H H H H",
+        "H D D D D H H H H H",
+        "S Ds Ds Ds Ds S S H H H",
+        "S S S S S S S S S S",
+        "S S S S S S S S S S",
+        "P P P P P P H H H H",
+        "P P P P P P H H H H",
+        "H H H P P H H H H H",
+        "D D D D D D D D H H",
+        "P P P P P H H H H H",
+        "P P P P P P H H H H",
+        "P P P P P P P P P P",
+        "P P P P P S P P S S",
+        "S S S S S S S S S S",
+        "P P P P P P P P P P",
+    ];
+
+    fn chart(rules: StrategyRules) -> StrategyChart {
+        let rows: Vec<ChartRow> = SIX_DECKS
+            .iter()
+            .map(|codes| {
+                let mut actions = [ChartAction::Hit; 10];
+                for (cell, code) in actions.iter_mut().zip(codes.split(' ')) {
+                    *cell = ChartAction::ALL
+                        .into_iter()
+                        .find(|a| a.code() == code)
+                        .unwrap();
+                }
+                ChartRow {
+                    label: String::new(),
+                    actions,
+                }
+            })
+            .collect();
+        StrategyChart::from_rows(
+            rules,
+            rows[..13].to_vec(),
+            rows[13..21].to_vec(),
+            rows[21..].to_vec(),
+        )
+        .unwrap()
+    }
+
+    fn advisor() -> Advisor {
+        Advisor::new(chart(StrategyRules::new(6))).with_indices(IndexTable::standard())
+    }
+
+    fn advise(advisor: &Advisor, hand: &str, up: &str, true_count: f64) -> Advice {
+        let hand = parse_cards(hand).unwrap();
+        advisor.advise(
+            &hand,
+            &up.parse().unwrap(),
+            true_count,
+            SplitContext::NoPreviousSplit,
+        )
+    }
+
+    #[test]
+    fn basic_strategy_at_a_neutral_count() {
+        let advisor = advisor();
+        let advice = advise(&advisor, "Th 2c", "5d", 0.0);
+        assert_eq!(advice.play, Play::Stand);
+        assert!(!advice.is_deviation());
+        assert_eq!(advise(&advisor, "9h 7c", "Td", -1.0).play, Play::Hit);
+        assert_eq!(advise(&advisor, "8h 8c", "Td", 6.0).play, Play::Split);
+    }
+
+    #[test]
+    fn indices_override_basic_strategy() {
+        let advisor = advisor();
+
+        let stand = advise(&advisor, "9h 7c", "Kd", 0.0);
+        assert_eq!((stand.play, stand.basic), (Play::Stand, Play::Hit));
+        assert_eq!(
+            stand.to_string(),
+            "Deviation: stand instead of hit (16 vs T at true count >=0)"
+        );
+
+        assert_eq!(advise(&advisor, "Th 2c", "5d", -2.5).play, Play::Hit);
+        assert_eq!(advise(&advisor, "Th 2c", "4d", 0.0).play, Play::Stand);
+        let hit = advise(&advisor, "Th 2c", "4d", -0.5);
+        assert_eq!((hit.play, hit.basic), (Play::Hit, Play::Stand));
+        assert!(hit.is_deviation());
+        assert_eq!(advise(&advisor, "Th Tc", "6d", 4.0).play, Play::Split);
+        assert_eq!(advise(&advisor, "Th Tc", "6d", 3.9).play, Play::Stand);
+        // Five-five plays as a ten.
+        assert_eq!(advise(&advisor, "5h 5c", "Td", 4.0).play, Play::Double);
+        // Eight-eight is always split, never played as a sixteen.
+        assert_eq!(advise(&advisor, "8h 8c", "Td", 0.0).play, Play::Split);
+    }
+
+    #[test]
+    fn deviations_need_the_play_to_be_available() {
+        let advisor = advisor();
+        // Doubling needs two cards.
+        let three = advise(&advisor, "2h 3c 6d", "Ad", 2.0);
+        assert_eq!(three.play, Play::Hit);
+
+        let hand = parse_cards("Th Tc").unwrap();
+        let split_done = advisor.advise(
+            &hand,
+            &"6d".parse().unwrap(),
+            5.0,
+            SplitContext::AlreadySplit,
+        );
+        assert_eq!(split_done.play, Play::Stand);
+    }
+
+    #[test]
+    fn surrender_is_advised_only_where_offered() {
+        let advisor = advisor();
+        let advice = advise(&advisor, "9h 6c", "Td", 5.0);
+        assert_eq!(advice.play, Play::Stand);
+
+        let surrendering = Advisor::new(chart(StrategyRules::new(6).with_surrender(true)))
+            .with_indices(IndexTable::standard());
+        let advice = advise(&surrendering, "9h 6c", "Td", 5.0);
+        assert_eq!(advice.play, Play::Surrender);
+        assert_eq!(advice.action, PlayerAction::Stay);
+    }
+
+    #[test]
+    fn insurance_at_plus_three() {
+        let advisor = advisor();
+        assert!(!advisor.should_insure(2.9));
+        assert!(advisor.should_insure(3.0));
+        assert!(!Advisor::new(chart(StrategyRules::new(6))).should_insure(10.0));
+    }
+
+    #[test]
+    fn tables_round_trip_through_text() {
+        let table = IndexTable::standard();
+        let text = table.to_string();
+        assert!(text.contains("\n16 T stand >=0\n"));
+        assert!(text.contains("\nT,T 5 split >=5\n"));
+        assert!(text.contains("\ninsurance A insure >=3\n"));
+        assert_eq!(text.parse::<IndexTable>().unwrap(), table);
+
+        let custom: IndexTable = "# soft doubles\nA,8 6 double >=1 # late\n".parse().unwrap();
+        assert_eq!(
+            custom.plays(),
+            [IndexPlay::new(
+                IndexHand::Soft(19),
+                6,
+                Play::Double,
+                Threshold::AtOrAbove(1)
+            )]
+        );
+    }
+
+    #[test]
+    fn bad_lines_are_reported() {
+        let err = "16 T stand >=0\n16 T dance >=0".parse::<IndexTable>();
+        assert!(matches!(
+            err,
+            Err(IndexTableError::InvalidLine { line: 2, .. })
+        ));
+        assert!("17 T stand".parse::<IndexTable>().is_err());
+        assert!("7,2 T hit <0".parse::<IndexTable>().is_err());
+        assert!(IndexTable::load("/nonexistent/indices.txt").is_err());
+    }
+
+    #[test]
+    fn tables_save_and_load() {
+        let path = std::env::temp_dir().join(format!("indices-{}.txt", std::process::id()));
+        IndexTable::fab_4().save(&path).unwrap();
+        assert_eq!(IndexTable::load(&path).unwrap(), IndexTable::fab_4());
+        std::fs::remove_file(path).unwrap();
+    }
+}
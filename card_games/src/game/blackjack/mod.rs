@@ -1,5 +1,13 @@
+pub mod analysis;
+pub mod autoplay;
+#[allow(clippy::module_inception)]
 pub mod blackjack;
+pub mod counting;
+pub mod dealer;
+pub mod deviations;
 pub mod rules;
+pub mod side_bets;
+pub mod strategy;
 pub mod types;
 pub mod view;
 
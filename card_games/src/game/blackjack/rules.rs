@@ -8,14 +8,11 @@
 
 use crate::cards::{hand::Hand, Card, Value};
 
-/// Blackjack-specific rules for scoring and win conditions.
-
 /// Returns the Blackjack value of a given card.
 ///
 /// Face cards are worth 10, aces are worth 11, and jokers are worth 0.
 ///
 /// This function does not apply Ace-adjustment logic — see [`hand_score`] for that.
-
 fn card_value(card: &Card) -> u8 {
     match *card.value() {
         Value::ACE => 11,
@@ -67,6 +64,36 @@ pub fn dealer_should_hit(hand: &[Card]) -> bool {
     hand_score(hand) < 17
 }
 
+/// Returns `true` if the hand holds an ace still counted as 11.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{Card, Suit, Value};
+/// use card_games::game::blackjack::rules::is_soft;
+/// let hand = vec![
+///     Card::new(Suit::SPADES, Value::ACE),
+///     Card::new(Suit::HEARTS, Value::SIX),
+/// ];
+/// assert!(is_soft(&hand));
+/// ```
+pub fn is_soft(hand: &[Card]) -> bool {
+    let has_ace = hand.iter().any(|card| *card.value() == Value::ACE);
+    let hard: u8 = hand
+        .iter()
+        .map(|card| match *card.value() {
+            Value::ACE => 1,
+            _ => card_value(card),
+        })
+        .sum();
+    has_ace && hard + 10 <= 21
+}
+
+/// The dealer's drawing rule at tables where the dealer hits soft 17.
+pub fn dealer_should_hit_soft_17(hand: &[Card]) -> bool {
+    let score = hand_score(hand);
+    score < 17 || (score == 17 && is_soft(hand))
+}
+
 /// Returns `true` if the hand is a bust (score > 21).
 ///
 /// # Example
@@ -105,6 +132,7 @@ pub fn can_double(hand: &[Card]) -> bool {
     hand.len() == 2
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SplitContext {
     NoPreviousSplit,
     AlreadySplit,
@@ -184,6 +212,23 @@ mod tests {
         assert!(!is_blackjack(hand.cards()));
     }
 
+    #[test]
+    fn soft_17_is_hit_only_under_h17() {
+        let soft = [
+            Card::new(Suit::SPADES, Value::ACE),
+            Card::new(Suit::HEARTS, Value::SIX),
+        ];
+        let hard = [
+            Card::new(Suit::SPADES, Value::ACE),
+            Card::new(Suit::HEARTS, Value::SIX),
+            Card::new(Suit::CLUBS, Value::TEN),
+        ];
+        assert!(!dealer_should_hit(&soft));
+        assert!(dealer_should_hit_soft_17(&soft));
+        assert!(!is_soft(&hard));
+        assert!(!dealer_should_hit_soft_17(&hard));
+    }
+
     #[test]
     fn two_cards_can_bust_and_not_be_blackjack() {
         let mut hand = Hand::new();
@@ -0,0 +1,490 @@
+//! Optional blackjack side bets, settled from the initial deal.
+//!
+//! - **Perfect Pairs** pays when the player's first two cards are a pair:
+//!   a mixed pair (one red, one black), a coloured pair (same colour,
+//!   different suits) or a perfect pair (same suit).
+//! - **21+3** pays when the player's two cards and the dealer's upcard form
+//!   a three-card poker hand: flush, straight, three of a kind, straight
+//!   flush or suited trips.
+//! - **Lucky Ladies** pays when the player's first two cards total 20, with
+//!   the top awards for a pair of queens of hearts.
+//!
+//! Each bet pays from a [`PayTable`], and [`expected_value`] prices a bet
+//! against the cards left in a shoe.
+
+use std::fmt::Display;
+
+use crate::cards::{Card, Suit, Value};
+
+use super::rules;
+
+/// A side bet offered at the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SideBetKind {
+    PerfectPairs,
+    TwentyOnePlusThree,
+    LuckyLadies,
+}
+
+impl SideBetKind {
+    pub const ALL: [SideBetKind; 3] = [
+        SideBetKind::PerfectPairs,
+        SideBetKind::TwentyOnePlusThree,
+        SideBetKind::LuckyLadies,
+    ];
+
+    /// Decides the bet from the player's first two cards and the dealer's
+    /// hole card and upcard. Returns `None` if the bet loses.
+    pub fn evaluate(&self, player: [Card; 2], hole: Card, up: Card) -> Option<SideBetOutcome> {
+        match self {
+            SideBetKind::PerfectPairs => perfect_pairs(player),
+            SideBetKind::TwentyOnePlusThree => twenty_one_plus_three([player[0], player[1], up]),
+            SideBetKind::LuckyLadies => lucky_ladies(player, rules::is_blackjack(&[hole, up])),
+        }
+    }
+}
+
+impl Display for SideBetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SideBetKind::PerfectPairs => write!(f, "Perfect Pairs"),
+            SideBetKind::TwentyOnePlusThree => write!(f, "21+3"),
+            SideBetKind::LuckyLadies => write!(f, "Lucky Ladies"),
+        }
+    }
+}
+
+/// A winning side-bet hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SideBetOutcome {
+    MixedPair,
+    ColoredPair,
+    PerfectPair,
+
+    Flush,
+    Straight,
+    ThreeOfAKind,
+    StraightFlush,
+    SuitedTrips,
+
+    Twenty,
+    SuitedTwenty,
+    MatchedTwenty,
+    QueenOfHeartsPair,
+    QueenOfHeartsPairWithDealerBlackjack,
+}
+
+impl Display for SideBetOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SideBetOutcome::MixedPair => "Mixed pair",
+            SideBetOutcome::ColoredPair => "Coloured pair",
+            SideBetOutcome::PerfectPair => "Perfect pair",
+            SideBetOutcome::Flush => "Flush",
+            SideBetOutcome::Straight => "Straight",
+            SideBetOutcome::ThreeOfAKind => "Three of a kind",
+            SideBetOutcome::StraightFlush => "Straight flush",
+            SideBetOutcome::SuitedTrips => "Suited trips",
+            SideBetOutcome::Twenty => "Twenty",
+            SideBetOutcome::SuitedTwenty => "Suited twenty",
+            SideBetOutcome::MatchedTwenty => "Matched twenty",
+            SideBetOutcome::QueenOfHeartsPair => "Queen of hearts pair",
+            SideBetOutcome::QueenOfHeartsPairWithDealerBlackjack => {
+                "Queen of hearts pair with dealer blackjack"
+            }
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Odds paid, as `n` to 1, for each winning outcome of a side bet.
+///
+/// # Example
+/// ```
+/// use card_games::game::blackjack::side_bets::{PayTable, SideBetOutcome};
+/// let table = PayTable::perfect_pairs().with_pay(SideBetOutcome::MixedPair, 5);
+/// assert_eq!(table.odds(SideBetOutcome::MixedPair), Some(5));
+/// assert_eq!(table.odds(SideBetOutcome::PerfectPair), Some(25));
+/// assert_eq!(table.odds(SideBetOutcome::Flush), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayTable {
+    pays: Vec<(SideBetOutcome, u32)>,
+}
+
+impl PayTable {
+    /// Mixed 6:1, coloured 12:1, perfect 25:1.
+    pub fn perfect_pairs() -> Self {
+        PayTable {
+            pays: vec![
+                (SideBetOutcome::MixedPair, 6),
+                (SideBetOutcome::ColoredPair, 12),
+                (SideBetOutcome::PerfectPair, 25),
+            ],
+        }
+    }
+
+    /// Flush 5:1, straight 10:1, trips 30:1, straight flush 40:1, suited
+    /// trips 100:1.
+    pub fn twenty_one_plus_three() -> Self {
+        PayTable {
+            pays: vec![
+                (SideBetOutcome::Flush, 5),
+                (SideBetOutcome::Straight, 10),
+                (SideBetOutcome::ThreeOfAKind, 30),
+                (SideBetOutcome::StraightFlush, 40),
+                (SideBetOutcome::SuitedTrips, 100),
+            ],
+        }
+    }
+
+    /// Any 20 4:1, suited 10:1, matched 25:1, Q♥ pair 200:1, Q♥ pair
+    /// against a dealer blackjack 1000:1.
+    pub fn lucky_ladies() -> Self {
+        PayTable {
+            pays: vec![
+                (SideBetOutcome::Twenty, 4),
+                (SideBetOutcome::SuitedTwenty, 10),
+                (SideBetOutcome::MatchedTwenty, 25),
+                (SideBetOutcome::QueenOfHeartsPair, 200),
+                (SideBetOutcome::QueenOfHeartsPairWithDealerBlackjack, 1_000),
+            ],
+        }
+    }
+
+    /// The standard table for a side bet.
+    pub fn standard(kind: SideBetKind) -> Self {
+        match kind {
+            SideBetKind::PerfectPairs => Self::perfect_pairs(),
+            SideBetKind::TwentyOnePlusThree => Self::twenty_one_plus_three(),
+            SideBetKind::LuckyLadies => Self::lucky_ladies(),
+        }
+    }
+
+    /// Sets the odds for one outcome.
+    pub fn with_pay(mut self, outcome: SideBetOutcome, odds: u32) -> Self {
+        match self.pays.iter_mut().find(|(o, _)| *o == outcome) {
+            Some(entry) => entry.1 = odds,
+            None => self.pays.push((outcome, odds)),
+        }
+        self
+    }
+
+    /// The odds for an outcome, or `None` if the table does not pay it.
+    pub fn odds(&self, outcome: SideBetOutcome) -> Option<u32> {
+        self.pays
+            .iter()
+            .find(|(o, _)| *o == outcome)
+            .map(|(_, odds)| *odds)
+    }
+
+    /// The amount returned, stake included, for `wager` on `outcome`.
+    pub fn payout(&self, wager: u32, outcome: Option<SideBetOutcome>) -> u32 {
+        outcome
+            .and_then(|o| self.odds(o))
+            .map_or(0, |odds| wager.saturating_mul(odds.saturating_add(1)))
+    }
+}
+
+/// The pay tables in use at a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideBetRules {
+    pub perfect_pairs: PayTable,
+    pub twenty_one_plus_three: PayTable,
+    pub lucky_ladies: PayTable,
+}
+
+impl SideBetRules {
+    pub fn pay_table(&self, kind: SideBetKind) -> &PayTable {
+        match kind {
+            SideBetKind::PerfectPairs => &self.perfect_pairs,
+            SideBetKind::TwentyOnePlusThree => &self.twenty_one_plus_three,
+            SideBetKind::LuckyLadies => &self.lucky_ladies,
+        }
+    }
+}
+
+impl Default for SideBetRules {
+    fn default() -> Self {
+        SideBetRules {
+            perfect_pairs: PayTable::perfect_pairs(),
+            twenty_one_plus_three: PayTable::twenty_one_plus_three(),
+            lucky_ladies: PayTable::lucky_ladies(),
+        }
+    }
+}
+
+/// A settled side bet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SideBetResult {
+    pub kind: SideBetKind,
+    pub wager: u32,
+    pub outcome: Option<SideBetOutcome>,
+    /// Amount returned, stake included. Zero for a losing bet.
+    pub payout: u32,
+}
+
+/// Scores Perfect Pairs.
+pub fn perfect_pairs([a, b]: [Card; 2]) -> Option<SideBetOutcome> {
+    if a.value() != b.value() || a.is_joker() {
+        return None;
+    }
+    Some(if a.suit() == b.suit() {
+        SideBetOutcome::PerfectPair
+    } else if a.is_red() == b.is_red() {
+        SideBetOutcome::ColoredPair
+    } else {
+        SideBetOutcome::MixedPair
+    })
+}
+
+/// Scores 21+3. Aces count high or low in straights.
+pub fn twenty_one_plus_three(cards: [Card; 3]) -> Option<SideBetOutcome> {
+    if cards.iter().any(Card::is_joker) {
+        return None;
+    }
+
+    let suited = cards.iter().all(|c| c.suit() == cards[0].suit());
+    let mut ranks: Vec<u8> = cards.iter().filter_map(Card::rank).collect();
+    ranks.sort_unstable();
+
+    let trips = ranks[0] == ranks[2];
+    let straight = (ranks[1] == ranks[0] + 1 && ranks[2] == ranks[1] + 1) || ranks == [1, 12, 13];
+
+    match (trips, straight, suited) {
+        (true, _, true) => Some(SideBetOutcome::SuitedTrips),
+        (_, true, true) => Some(SideBetOutcome::StraightFlush),
+        (true, _, false) => Some(SideBetOutcome::ThreeOfAKind),
+        (_, true, false) => Some(SideBetOutcome::Straight),
+        (_, _, true) => Some(SideBetOutcome::Flush),
+        _ => None,
+    }
+}
+
+/// Scores Lucky Ladies.
+pub fn lucky_ladies([a, b]: [Card; 2], dealer_blackjack: bool) -> Option<SideBetOutcome> {
+    if rules::hand_score(&[a, b]) != 20 {
+        return None;
+    }
+
+    let queen_of_hearts = Card::new(Suit::HEARTS, Value::QUEEN);
+    Some(if a == queen_of_hearts && b == queen_of_hearts {
+        if dealer_blackjack {
+            SideBetOutcome::QueenOfHeartsPairWithDealerBlackjack
+        } else {
+            SideBetOutcome::QueenOfHeartsPair
+        }
+    } else if a == b {
+        SideBetOutcome::MatchedTwenty
+    } else if a.suit() == b.suit() {
+        SideBetOutcome::SuitedTwenty
+    } else {
+        SideBetOutcome::Twenty
+    })
+}
+
+/// Expected return per unit staked on a side bet, dealt from `shoe`.
+///
+/// Every ordered deal of the cards the bet looks at is weighted by its
+/// probability, so the result is exact for the given composition. A
+/// negative value is the house edge.
+///
+/// # Example
+/// ```
+/// use card_games::cards::deck_builder::DeckBuilder;
+/// use card_games::game::blackjack::side_bets::{expected_value, PayTable, SideBetKind};
+/// let shoe: Vec<_> = DeckBuilder::new().standard52().repeat(6).build().into_iter().collect();
+/// let ev = expected_value(SideBetKind::PerfectPairs, &PayTable::perfect_pairs(), &shoe);
+/// assert!((ev + 0.0611).abs() < 0.001);
+/// ```
+pub fn expected_value(kind: SideBetKind, table: &PayTable, shoe: &[Card]) -> f64 {
+    let mut counts = [0u32; 52];
+    for card in shoe.iter().filter(|c| !c.is_joker()) {
+        counts[card.to_byte() as usize] += 1;
+    }
+    let total: u32 = counts.iter().sum();
+    if total < 4 {
+        return 0.0;
+    }
+
+    let cards: Vec<Card> = (0..52).filter_map(Card::from_byte).collect();
+    let odds = |outcome: Option<SideBetOutcome>| outcome.and_then(|o| table.odds(o));
+    let mut win = 0.0;
+    let mut p_win = 0.0;
+
+    for (i, first) in cards.iter().enumerate() {
+        if counts[i] == 0 {
+            continue;
+        }
+        let p_first = counts[i] as f64 / total as f64;
+        counts[i] -= 1;
+
+        for (j, second) in cards.iter().enumerate() {
+            if counts[j] == 0 {
+                continue;
+            }
+            let p_two = p_first * counts[j] as f64 / (total - 1) as f64;
+            counts[j] -= 1;
+            let player = [*first, *second];
+
+            match kind {
+                SideBetKind::PerfectPairs => {
+                    if let Some(o) = odds(perfect_pairs(player)) {
+                        win += p_two * o as f64;
+                        p_win += p_two;
+                    }
+                }
+                SideBetKind::LuckyLadies => {
+                    let p_blackjack = dealer_blackjack_chance(&counts, total - 2);
+                    for (blackjack, p) in [(true, p_blackjack), (false, 1.0 - p_blackjack)] {
+                        if let Some(o) = odds(lucky_ladies(player, blackjack)) {
+                            win += p_two * p * o as f64;
+                            p_win += p_two * p;
+                        }
+                    }
+                }
+                SideBetKind::TwentyOnePlusThree => {
+                    for (k, up) in cards.iter().enumerate() {
+                        if counts[k] == 0 {
+                            continue;
+                        }
+                        let p = p_two * counts[k] as f64 / (total - 2) as f64;
+                        if let Some(o) = odds(twenty_one_plus_three([*first, *second, *up])) {
+                            win += p * o as f64;
+                            p_win += p;
+                        }
+                    }
+                }
+            }
+
+            counts[j] += 1;
+        }
+        counts[i] += 1;
+    }
+
+    win - (1.0 - p_win)
+}
+
+/// Chance the next two cards are an ace and a ten-value card.
+fn dealer_blackjack_chance(counts: &[u32; 52], remaining: u32) -> f64 {
+    if remaining < 2 {
+        return 0.0;
+    }
+    let (mut aces, mut tens) = (0, 0);
+    for (code, count) in counts.iter().enumerate() {
+        match code % 13 {
+            0 => aces += count,
+            9..=12 => tens += count,
+            _ => {}
+        }
+    }
+    2.0 * aces as f64 * tens as f64 / (remaining as f64 * (remaining - 1) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{deck_builder::DeckBuilder, parse_cards};
+
+    fn cards<const N: usize>(s: &str) -> [Card; N] {
+        parse_cards(s).unwrap().try_into().unwrap()
+    }
+
+    fn six_deck_shoe() -> Vec<Card> {
+        DeckBuilder::new()
+            .standard52()
+            .repeat(6)
+            .build()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn perfect_pairs_grades_by_colour_and_suit() {
+        assert_eq!(
+            perfect_pairs(cards("8h 8s")),
+            Some(SideBetOutcome::MixedPair)
+        );
+        assert_eq!(
+            perfect_pairs(cards("8h 8d")),
+            Some(SideBetOutcome::ColoredPair)
+        );
+        assert_eq!(
+            perfect_pairs(cards("8h 8h")),
+            Some(SideBetOutcome::PerfectPair)
+        );
+        assert_eq!(perfect_pairs(cards("8h 9h")), None);
+    }
+
+    #[test]
+    fn twenty_one_plus_three_reads_poker_hands() {
+        let hand = |s| twenty_one_plus_three(cards(s));
+        assert_eq!(hand("2h 9h Kh"), Some(SideBetOutcome::Flush));
+        assert_eq!(hand("Qs Kh Ad"), Some(SideBetOutcome::Straight));
+        assert_eq!(hand("Ac 2h 3d"), Some(SideBetOutcome::Straight));
+        assert_eq!(hand("7c 7h 7d"), Some(SideBetOutcome::ThreeOfAKind));
+        assert_eq!(hand("5s 6s 7s"), Some(SideBetOutcome::StraightFlush));
+        assert_eq!(hand("7d 7d 7d"), Some(SideBetOutcome::SuitedTrips));
+        assert_eq!(hand("Kc Ah 2d"), None);
+    }
+
+    #[test]
+    fn lucky_ladies_needs_twenty() {
+        assert_eq!(lucky_ladies(cards("Kc 9h"), false), None);
+        assert_eq!(
+            lucky_ladies(cards("Kc Jh"), false),
+            Some(SideBetOutcome::Twenty)
+        );
+        assert_eq!(
+            lucky_ladies(cards("Ah 9h"), false),
+            Some(SideBetOutcome::SuitedTwenty)
+        );
+        assert_eq!(
+            lucky_ladies(cards("Js Js"), false),
+            Some(SideBetOutcome::MatchedTwenty)
+        );
+        assert_eq!(
+            lucky_ladies(cards("Qh Qh"), true),
+            Some(SideBetOutcome::QueenOfHeartsPairWithDealerBlackjack)
+        );
+    }
+
+    #[test]
+    fn payouts_include_the_stake() {
+        let table = PayTable::perfect_pairs();
+        assert_eq!(table.payout(10, Some(SideBetOutcome::MixedPair)), 70);
+        assert_eq!(table.payout(10, Some(SideBetOutcome::Flush)), 0);
+        assert_eq!(table.payout(10, None), 0);
+    }
+
+    #[test]
+    fn six_deck_house_edges() {
+        let shoe = six_deck_shoe();
+        let pairs = expected_value(SideBetKind::PerfectPairs, &PayTable::perfect_pairs(), &shoe);
+        // Of the 311 cards left after the first, 5 make a perfect pair, 6 a
+        // coloured pair and 12 a mixed pair.
+        let exact = (25.0 * 5.0 + 12.0 * 6.0 + 6.0 * 12.0 - 288.0) / 311.0;
+        assert!((pairs - exact).abs() < 1e-9, "perfect pairs {}", pairs);
+
+        let three = expected_value(
+            SideBetKind::TwentyOnePlusThree,
+            &PayTable::twenty_one_plus_three(),
+            &shoe,
+        );
+        assert!((three + 0.04621).abs() < 1e-4, "21+3 {}", three);
+
+        let ladies = expected_value(SideBetKind::LuckyLadies, &PayTable::lucky_ladies(), &shoe);
+        assert!(ladies < 0.0 && ladies > -0.30, "lucky ladies {}", ladies);
+    }
+
+    #[test]
+    fn shoe_rich_in_pairs_favours_perfect_pairs() {
+        let sevens: Vec<Card> = parse_cards("7h 7h 7h 7s 7s 7s").unwrap();
+        let ev = expected_value(
+            SideBetKind::PerfectPairs,
+            &PayTable::perfect_pairs(),
+            &sevens,
+        );
+        assert!(ev > 1.0);
+    }
+}
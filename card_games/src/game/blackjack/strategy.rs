@@ -0,0 +1,687 @@
+//! Basic strategy charts.
+//!
+//! A [`StrategyChart`] holds the best play for every starting hand against
+//! every dealer upcard, worked out with the [`Analyzer`] under the rules
+//! the engine deals by: the dealer stands on or hits soft 17 as the table
+//! says and peeks for blackjack, and a hand may be split once. The chart can be written out
+//! as plain text, CSV, Markdown or an HTML table with coloured cells.
+
+use std::fmt::{Display, Write};
+
+use crate::cards::Card;
+
+use super::{
+    analysis::{card_of, Analyzer, Composition, Decision, ACE, RANKS, TEN},
+    rules::SplitContext,
+};
+
+/// The rules a chart is computed for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrategyRules {
+    pub decks: u32,
+    pub dealer_hits_soft_17: bool,
+    pub double_after_split: bool,
+    /// Whether late surrender is offered.
+    pub surrender: bool,
+}
+
+impl StrategyRules {
+    /// The engine's rules with `decks` decks: the dealer stands on soft
+    /// 17, doubling after a split is allowed and surrender is not.
+    pub fn new(decks: u32) -> Self {
+        StrategyRules {
+            decks: decks.max(1),
+            dealer_hits_soft_17: false,
+            double_after_split: true,
+            surrender: false,
+        }
+    }
+
+    pub fn with_dealer_hits_soft_17(mut self, hits: bool) -> Self {
+        self.dealer_hits_soft_17 = hits;
+        self
+    }
+
+    pub fn with_double_after_split(mut self, allowed: bool) -> Self {
+        self.double_after_split = allowed;
+        self
+    }
+
+    pub fn with_surrender(mut self, allowed: bool) -> Self {
+        self.surrender = allowed;
+        self
+    }
+}
+
+impl Default for StrategyRules {
+    /// The engine's single-deck game.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Display for StrategyRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.decks {
+            1 => write!(f, "1 deck")?,
+            decks => write!(f, "{} decks", decks)?,
+        }
+        match self.dealer_hits_soft_17 {
+            true => write!(f, ", dealer hits soft 17")?,
+            false => write!(f, ", dealer stands on soft 17")?,
+        }
+        if self.double_after_split {
+            write!(f, ", double after split")?;
+        }
+        if self.surrender {
+            write!(f, ", late surrender")?;
+        }
+        Ok(())
+    }
+}
+
+/// One cell of a chart, with the fallback printed charts give when the
+/// preferred play is not allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartAction {
+    Hit,
+    Stand,
+    /// Double if allowed, otherwise hit.
+    DoubleOrHit,
+    /// Double if allowed, otherwise stand.
+    DoubleOrStand,
+    Split,
+    /// Surrender if allowed, otherwise hit.
+    SurrenderOrHit,
+    /// Surrender if allowed, otherwise stand.
+    SurrenderOrStand,
+}
+
+impl ChartAction {
+    pub const ALL: [ChartAction; 7] = [
+        ChartAction::Hit,
+        ChartAction::Stand,
+        ChartAction::DoubleOrHit,
+        ChartAction::DoubleOrStand,
+        ChartAction::Split,
+        ChartAction::SurrenderOrHit,
+        ChartAction::SurrenderOrStand,
+    ];
+
+    /// The abbreviation printed in the chart.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ChartAction::Hit => "H",
+            ChartAction::Stand => "S",
+            ChartAction::DoubleOrHit => "D",
+            ChartAction::DoubleOrStand => "Ds",
+            ChartAction::Split => "P",
+            ChartAction::SurrenderOrHit => "R",
+            ChartAction::SurrenderOrStand => "Rs",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ChartAction::Hit => "Hit",
+            ChartAction::Stand => "Stand",
+            ChartAction::DoubleOrHit => "Double if allowed, otherwise hit",
+            ChartAction::DoubleOrStand => "Double if allowed, otherwise stand",
+            ChartAction::Split => "Split",
+            ChartAction::SurrenderOrHit => "Surrender if allowed, otherwise hit",
+            ChartAction::SurrenderOrStand => "Surrender if allowed, otherwise stand",
+        }
+    }
+
+    /// Background colour of the cell in the HTML chart.
+    pub fn color(&self) -> &'static str {
+        match self {
+            ChartAction::Hit => "#ffffff",
+            ChartAction::Stand => "#ffe066",
+            ChartAction::DoubleOrHit | ChartAction::DoubleOrStand => "#7bd389",
+            ChartAction::Split => "#74a9e8",
+            ChartAction::SurrenderOrHit | ChartAction::SurrenderOrStand => "#c8c8c8",
+        }
+    }
+
+    /// The play the chart calls for, given every decision's value.
+    fn choose(values: &[(Decision, f64)]) -> Self {
+        let value = |decision| {
+            values
+                .iter()
+                .find(|(d, _)| *d == decision)
+                .map(|(_, ev)| *ev)
+        };
+        let (best, _) =
+            values
+                .iter()
+                .copied()
+                .fold((Decision::Stand, f64::NEG_INFINITY), |best, next| {
+                    if next.1 > best.1 {
+                        next
+                    } else {
+                        best
+                    }
+                });
+        let hit_over_stand = value(Decision::Hit) > value(Decision::Stand);
+
+        match (best, hit_over_stand) {
+            (Decision::Stand, _) => ChartAction::Stand,
+            (Decision::Hit, _) => ChartAction::Hit,
+            (Decision::Split, _) => ChartAction::Split,
+            (Decision::Double, true) => ChartAction::DoubleOrHit,
+            (Decision::Double, false) => ChartAction::DoubleOrStand,
+            (Decision::Surrender, true) => ChartAction::SurrenderOrHit,
+            (Decision::Surrender, false) => ChartAction::SurrenderOrStand,
+        }
+    }
+}
+
+impl Display for ChartAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// The three parts of a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartSection {
+    Hard,
+    Soft,
+    Pairs,
+}
+
+impl ChartSection {
+    pub const ALL: [ChartSection; 3] =
+        [ChartSection::Hard, ChartSection::Soft, ChartSection::Pairs];
+}
+
+impl Display for ChartSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChartSection::Hard => write!(f, "Hard totals"),
+            ChartSection::Soft => write!(f, "Soft totals"),
+            ChartSection::Pairs => write!(f, "Pairs"),
+        }
+    }
+}
+
+/// One starting hand and its play against each upcard, two through ace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartRow {
+    /// How the hand is printed: `12`, `A,7` or `8,8`.
+    pub label: String,
+    pub actions: [ChartAction; RANKS],
+}
+
+/// Upcards in chart order: two through ten, then ace.
+fn upcards() -> impl Iterator<Item = usize> {
+    (1..RANKS).chain([ACE])
+}
+
+fn rank_label(rank: usize) -> &'static str {
+    card_of(rank).value().short()
+}
+
+/// A complete basic-strategy chart.
+///
+/// # Example
+/// ```no_run
+/// use card_games::game::blackjack::strategy::{ChartAction, StrategyChart, StrategyRules};
+/// let chart = StrategyChart::generate(&StrategyRules::new(1));
+/// let eight: card_games::cards::Card = "8s".parse().unwrap();
+/// let ten = "Td".parse().unwrap();
+/// assert_eq!(chart.pair(&eight, &ten), Some(ChartAction::Split));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyChart {
+    rules: StrategyRules,
+    hard: Vec<ChartRow>,
+    soft: Vec<ChartRow>,
+    pairs: Vec<ChartRow>,
+}
+
+impl StrategyChart {
+    /// Hard totals from 5 to 17; every higher hard total stands.
+    pub const HARD_TOTALS: std::ops::RangeInclusive<u8> = 5..=17;
+
+    /// Works out every cell from a full shoe, with the player's two cards
+    /// and the upcard removed. A hard total can be dealt as several pairs
+    /// of cards, so its row weighs each pair by the chance of being dealt
+    /// it. Every hand is valued exactly, which takes a few seconds in an
+    /// optimised build.
+    pub fn generate(rules: &StrategyRules) -> Self {
+        let mut solver = Solver::new(rules);
+        let hard = Self::HARD_TOTALS
+            .map(|total| {
+                let mut actions = [ChartAction::Stand; RANKS];
+                for (column, up) in upcards().enumerate() {
+                    actions[column] = solver.play_hard(total as usize, up);
+                }
+                ChartRow {
+                    label: total.to_string(),
+                    actions,
+                }
+            })
+            .collect();
+
+        let mut row = |label: String, first: usize, second: usize, may_split: bool| {
+            let mut actions = [ChartAction::Stand; RANKS];
+            for (column, up) in upcards().enumerate() {
+                actions[column] = solver.play(first, second, up, may_split);
+            }
+            ChartRow { label, actions }
+        };
+        let soft = (1..TEN)
+            .map(|rank| row(format!("A,{}", rank_label(rank)), ACE, rank, false))
+            .collect();
+        let pairs = (1..RANKS)
+            .chain([ACE])
+            .map(|rank| {
+                let label = format!("{0},{0}", rank_label(rank));
+                row(label, rank, rank, true)
+            })
+            .collect();
+
+        StrategyChart {
+            rules: *rules,
+            hard,
+            soft,
+            pairs,
+        }
+    }
+
+    /// A chart from rows worked out elsewhere, such as a printed chart.
+    /// Returns `None` unless there is one row per hard total, eight soft
+    /// rows (A,2 to A,9) and ten pair rows (2,2 to A,A), in chart order.
+    pub fn from_rows(
+        rules: StrategyRules,
+        hard: Vec<ChartRow>,
+        soft: Vec<ChartRow>,
+        pairs: Vec<ChartRow>,
+    ) -> Option<Self> {
+        let hard_rows = Self::HARD_TOTALS.count();
+        (hard.len() == hard_rows && soft.len() == 8 && pairs.len() == RANKS).then_some(
+            StrategyChart {
+                rules,
+                hard,
+                soft,
+                pairs,
+            },
+        )
+    }
+
+    pub fn rules(&self) -> &StrategyRules {
+        &self.rules
+    }
+
+    pub fn section(&self, section: ChartSection) -> &[ChartRow] {
+        match section {
+            ChartSection::Hard => &self.hard,
+            ChartSection::Soft => &self.soft,
+            ChartSection::Pairs => &self.pairs,
+        }
+    }
+
+    /// The play for a hard total against `upcard`. Totals above the chart
+    /// stand; totals below it are not charted.
+    pub fn hard(&self, total: u8, upcard: &Card) -> Option<ChartAction> {
+        if total > *Self::HARD_TOTALS.end() && total <= 21 {
+            return Some(ChartAction::Stand);
+        }
+        let row = total.checked_sub(*Self::HARD_TOTALS.start())? as usize;
+        Some(self.hard.get(row)?.actions[Self::column(upcard)?])
+    }
+
+    /// The play for a soft total (13 to 20) against `upcard`.
+    pub fn soft(&self, total: u8, upcard: &Card) -> Option<ChartAction> {
+        let row = total.checked_sub(13)? as usize;
+        Some(self.soft.get(row)?.actions[Self::column(upcard)?])
+    }
+
+    /// The play for a pair of `card` against `upcard`.
+    pub fn pair(&self, card: &Card, upcard: &Card) -> Option<ChartAction> {
+        let row = match card.rank()? {
+            1 => RANKS - 1,
+            rank => (rank.min(10) - 2) as usize,
+        };
+        Some(self.pairs.get(row)?.actions[Self::column(upcard)?])
+    }
+
+    fn column(upcard: &Card) -> Option<usize> {
+        match upcard.rank()? {
+            1 => Some(RANKS - 1),
+            rank => Some((rank.min(10) - 2) as usize),
+        }
+    }
+
+    fn sections(&self) -> impl Iterator<Item = (ChartSection, &[ChartRow])> {
+        ChartSection::ALL
+            .into_iter()
+            .map(|section| (section, self.section(section)))
+    }
+
+    /// The chart as fixed-width text, one block per section.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Basic strategy: {}", self.rules).unwrap();
+        for (section, rows) in self.sections() {
+            writeln!(out).unwrap();
+            write!(out, "{:<6}", section_heading(section)).unwrap();
+            for up in upcards() {
+                write!(out, "{:>3}", rank_label(up)).unwrap();
+            }
+            writeln!(out).unwrap();
+            for row in rows {
+                write!(out, "{:<6}", row.label).unwrap();
+                for action in &row.actions {
+                    write!(out, "{:>3}", action.code()).unwrap();
+                }
+                writeln!(out).unwrap();
+            }
+        }
+        writeln!(out).unwrap();
+        for action in ChartAction::ALL {
+            writeln!(out, "{:<2} {}", action.code(), action.description()).unwrap();
+        }
+        out
+    }
+
+    /// The chart as CSV with a header row: section, hand, then one column
+    /// per upcard.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("section,hand");
+        for up in upcards() {
+            write!(out, ",{}", rank_label(up)).unwrap();
+        }
+        out.push('\n');
+        for (section, rows) in self.sections() {
+            for row in rows {
+                write!(out, "{},\"{}\"", section, row.label).unwrap();
+                for action in &row.actions {
+                    write!(out, ",{}", action.code()).unwrap();
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// The chart as Markdown, one table per section.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "## Basic strategy: {}", self.rules).unwrap();
+        for (section, rows) in self.sections() {
+            writeln!(out, "\n### {}\n", section).unwrap();
+            write!(out, "| Hand |").unwrap();
+            for up in upcards() {
+                write!(out, " {} |", rank_label(up)).unwrap();
+            }
+            writeln!(out).unwrap();
+            writeln!(out, "|---|{}", "---|".repeat(RANKS)).unwrap();
+            for row in rows {
+                write!(out, "| {} |", row.label).unwrap();
+                for action in &row.actions {
+                    write!(out, " {} |", action.code()).unwrap();
+                }
+                writeln!(out).unwrap();
+            }
+        }
+        writeln!(out).unwrap();
+        for action in ChartAction::ALL {
+            writeln!(out, "- **{}**: {}", action.code(), action.description()).unwrap();
+        }
+        out
+    }
+
+    /// The chart as HTML tables, one per section, with each cell coloured
+    /// by its play.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "<div class=\"strategy-chart\">").unwrap();
+        writeln!(out, "<h2>Basic strategy: {}</h2>", self.rules).unwrap();
+        for (section, rows) in self.sections() {
+            writeln!(out, "<table>").unwrap();
+            writeln!(out, "<caption>{}</caption>", section).unwrap();
+            write!(out, "<tr><th>Hand</th>").unwrap();
+            for up in upcards() {
+                write!(out, "<th>{}</th>", rank_label(up)).unwrap();
+            }
+            writeln!(out, "</tr>").unwrap();
+            for row in rows {
+                write!(out, "<tr><th>{}</th>", row.label).unwrap();
+                for action in &row.actions {
+                    write!(
+                        out,
+                        "<td style=\"background-color: {}\" title=\"{}\">{}</td>",
+                        action.color(),
+                        action.description(),
+                        action.code()
+                    )
+                    .unwrap();
+                }
+                writeln!(out, "</tr>").unwrap();
+            }
+            writeln!(out, "</table>").unwrap();
+        }
+        writeln!(out, "</div>").unwrap();
+        out
+    }
+}
+
+/// Works out single cells of a chart, sharing one analyzer between them.
+struct Solver {
+    analyzer: Analyzer,
+    shoe: Composition,
+}
+
+impl Solver {
+    fn new(rules: &StrategyRules) -> Self {
+        Solver {
+            analyzer: Analyzer::new()
+                .with_dealer_hits_soft_17(rules.dealer_hits_soft_17)
+                .with_double_after_split(rules.double_after_split)
+                .with_surrender(rules.surrender),
+            shoe: Composition::standard(rules.decks),
+        }
+    }
+
+    /// The play for two cards against an upcard, all given as ranks.
+    fn play(&mut self, first: usize, second: usize, up: usize, may_split: bool) -> ChartAction {
+        ChartAction::choose(&self.values(first, second, up, may_split))
+    }
+
+    /// The play for a hard total against an upcard. Each decision is
+    /// valued for every pair of cards without an ace that makes the total,
+    /// weighted by the chance of being dealt that pair. Pairs are played
+    /// as totals here, as they are once splitting is not allowed.
+    fn play_hard(&mut self, total: usize, up: usize) -> ChartAction {
+        let counts = self.shoe.without(&[card_of(up)]).counts;
+        let mut weighted: Vec<(Decision, f64)> = Vec::new();
+        let mut total_weight = 0.0;
+        // Ranks 1 to 8 are two to nine and `TEN` is ten: each is worth
+        // one more than its rank.
+        for first in 1..=TEN {
+            let Some(second) = total.checked_sub(first + 2) else {
+                continue;
+            };
+            if !(first..=TEN).contains(&second) {
+                continue;
+            }
+            let weight = match first == second {
+                true => counts[first] as f64 * counts[first].saturating_sub(1) as f64,
+                false => 2.0 * counts[first] as f64 * counts[second] as f64,
+            };
+            if weight == 0.0 {
+                continue;
+            }
+
+            let values = self.values(first, second, up, false);
+            if weighted.is_empty() {
+                weighted = values
+                    .iter()
+                    .map(|(decision, _)| (*decision, 0.0))
+                    .collect();
+            }
+            for ((_, sum), (_, ev)) in weighted.iter_mut().zip(values) {
+                *sum += weight * ev;
+            }
+            total_weight += weight;
+        }
+        for (_, sum) in &mut weighted {
+            *sum /= total_weight;
+        }
+        ChartAction::choose(&weighted)
+    }
+
+    /// The value of each decision the chart may call for, for two cards
+    /// against an upcard.
+    fn values(
+        &mut self,
+        first: usize,
+        second: usize,
+        up: usize,
+        may_split: bool,
+    ) -> Vec<(Decision, f64)> {
+        let player = [card_of(first), card_of(second)];
+        let upcard = card_of(up);
+        let unseen = self.shoe.without(&player).without(&[upcard]);
+        let values =
+            self.analyzer
+                .analyze(&player, &upcard, &unseen, SplitContext::NoPreviousSplit);
+
+        let mut allowed = vec![(Decision::Stand, values.stand), (Decision::Hit, values.hit)];
+        allowed.extend(values.double.map(|ev| (Decision::Double, ev)));
+        if may_split {
+            allowed.extend(values.split.map(|ev| (Decision::Split, ev)));
+        }
+        allowed.extend(values.surrender.map(|ev| (Decision::Surrender, ev)));
+        allowed
+    }
+}
+
+fn section_heading(section: ChartSection) -> &'static str {
+    match section {
+        ChartSection::Hard => "Hard",
+        ChartSection::Soft => "Soft",
+        ChartSection::Pairs => "Pair",
+    }
+}
+
+impl Display for StrategyChart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ChartAction::*;
+
+    const TWO: usize = 1;
+    const SIX: usize = 5;
+    const EIGHT: usize = 7;
+    const NINE: usize = 8;
+
+    #[test]
+    fn single_deck_cells_match_basic_strategy() {
+        let mut solver = Solver::new(&StrategyRules::new(1));
+        assert_eq!(solver.play(SIX, TEN, TEN, false), Hit);
+        assert_eq!(solver.play(SIX, TEN, SIX, false), Stand);
+        assert_eq!(solver.play(ACE, EIGHT, SIX, false), DoubleOrStand);
+        assert_eq!(solver.play(EIGHT, EIGHT, ACE, true), Split);
+        assert_eq!(solver.play(TEN, TEN, SIX, true), Stand);
+        assert_eq!(solver.play(NINE, NINE, 6, true), Stand);
+    }
+
+    #[test]
+    fn close_hands_depend_on_the_shoe() {
+        // Ten-two hits against a four, and eleven doubles against an ace
+        // only when the shoe is a single deck.
+        let mut single = Solver::new(&StrategyRules::new(1));
+        assert_eq!(single.play(TWO, TEN, 3, false), Hit);
+        assert_eq!(single.play(TWO, NINE, ACE, false), DoubleOrHit);
+        let mut six = Solver::new(&StrategyRules::new(6));
+        assert_eq!(six.play(TWO, NINE, ACE, false), Hit);
+    }
+
+    #[test]
+    fn surrender_is_charted_only_when_offered() {
+        let mut offered = Solver::new(&StrategyRules::new(6).with_surrender(true));
+        assert_eq!(offered.play(SIX, TEN, TEN, false), SurrenderOrHit);
+        assert_eq!(offered.play(SIX, TEN, 2, false), Stand);
+
+        let values = [(Decision::Stand, -0.6), (Decision::Hit, -0.55)];
+        assert_eq!(ChartAction::choose(&values), Hit);
+        let values = [(Decision::Stand, -0.6), (Decision::Surrender, -0.5)];
+        assert_eq!(ChartAction::choose(&values), SurrenderOrStand);
+    }
+
+    #[test]
+    fn generated_single_deck_chart_matches_basic_strategy() {
+        let chart = StrategyChart::generate(&StrategyRules::new(1));
+        assert_eq!(chart.section(ChartSection::Hard).len(), 13);
+        assert_eq!(chart.section(ChartSection::Soft).len(), 8);
+        assert_eq!(chart.section(ChartSection::Pairs).len(), 10);
+
+        let card = |text: &str| text.parse::<Card>().unwrap();
+        assert_eq!(chart.hard(12, &card("3d")), Some(Hit));
+        assert_eq!(chart.hard(12, &card("4d")), Some(Stand));
+        assert_eq!(chart.hard(16, &card("Td")), Some(Hit));
+        assert_eq!(chart.hard(11, &card("Ad")), Some(DoubleOrHit));
+        assert_eq!(chart.hard(9, &card("2d")), Some(DoubleOrHit));
+        assert_eq!(chart.soft(19, &card("6d")), Some(DoubleOrStand));
+        assert_eq!(chart.pair(&card("8s"), &card("Td")), Some(Split));
+        assert_eq!(chart.pair(&card("Ts"), &card("6d")), Some(Stand));
+    }
+
+    /// A chart that stands everywhere except for splitting eights.
+    fn chart() -> StrategyChart {
+        let row = |label: &str, action| ChartRow {
+            label: label.to_string(),
+            actions: [action; RANKS],
+        };
+        StrategyChart {
+            rules: StrategyRules::new(1),
+            hard: StrategyChart::HARD_TOTALS
+                .map(|total| row(&total.to_string(), Stand))
+                .collect(),
+            soft: (3..=9).map(|n| row(&format!("A,{}", n), Stand)).collect(),
+            pairs: vec![row("7,7", Stand), row("8,8", Split)],
+        }
+    }
+
+    #[test]
+    fn lookups_find_the_right_cell() {
+        let chart = chart();
+        let card = |s: &str| -> Card { s.parse().unwrap() };
+        assert_eq!(chart.hard(20, &card("Th")), Some(Stand));
+        assert_eq!(chart.hard(4, &card("Th")), None);
+        assert_eq!(chart.soft(14, &card("Ah")), Some(Stand));
+        assert_eq!(chart.soft(21, &card("Ah")), None);
+    }
+
+    #[test]
+    fn exports_share_the_same_cells() {
+        let chart = chart();
+
+        let csv = chart.to_csv();
+        assert_eq!(csv.lines().next(), Some("section,hand,2,3,4,5,6,7,8,9,T,A"));
+        assert_eq!(csv.lines().count(), 1 + 13 + 7 + 2);
+        assert!(csv.contains("Pairs,\"8,8\",P,P,P,P,P,P,P,P,P,P"));
+
+        let text = chart.to_text();
+        assert!(text.starts_with("Basic strategy: 1 deck, dealer stands on soft 17"));
+        assert!(text.contains("8,8     P  P  P  P  P  P  P  P  P  P"));
+
+        let markdown = chart.to_markdown();
+        assert!(markdown.contains("| 8,8 | P | P |"));
+        assert!(markdown.contains("### Soft totals"));
+
+        let html = chart.to_html();
+        assert_eq!(html.matches("<table>").count(), 3);
+        assert!(html.contains("<td style=\"background-color: #74a9e8\" title=\"Split\">P</td>"));
+    }
+}
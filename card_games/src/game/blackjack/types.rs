@@ -1,5 +1,7 @@
+use rand::Rng;
+
 use crate::{
-    bank::bet::Bet,
+    bank::{bet::Bet, limits::LimitError},
     cards::{deck_builder::DeckBuilder, hand::Hand, Card, Deck},
 };
 
@@ -14,27 +16,99 @@ pub enum BlackjackState {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Shoe {
     deck: Deck,
+    /// Cards dealt since the shuffle, oldest first.
+    dealt: Vec<Card>,
+    /// Cards burned face down since the shuffle.
+    burned: Vec<Card>,
 }
 
 impl Shoe {
     pub fn new_shuffled() -> Self {
         let mut deck = DeckBuilder::new().standard52().build();
         deck.shuffle();
-        Self { deck }
+        Self {
+            deck,
+            dealt: Vec::new(),
+            burned: Vec::new(),
+        }
+    }
+
+    /// A shoe of `decks` standard decks, shuffled with `rng`.
+    pub fn shuffled<R: Rng + ?Sized>(decks: usize, rng: &mut R) -> Self {
+        let mut deck = DeckBuilder::new().standard52().repeat(decks.max(1)).build();
+        deck.wash(rng);
+        Self {
+            deck,
+            dealt: Vec::new(),
+            burned: Vec::new(),
+        }
     }
 
     pub fn remaining(&self) -> usize {
         self.deck.len()
     }
 
+    /// The number of cards in the shoe when it was shuffled.
+    pub fn size(&self) -> usize {
+        self.deck.len() + self.dealt.len() + self.burned.len()
+    }
+
+    /// Deals the top card face up.
+    ///
+    /// # Panics
+    /// Panics if the shoe is empty. [`Shoe::reshuffle_discards`] refills it.
     pub fn draw(&mut self) -> Card {
-        self.deck.draw().expect("Deck exhausted")
+        let card = self.deck.draw().expect("Deck exhausted");
+        self.dealt.push(card);
+        card
+    }
+
+    /// Burns the top card face down, as the dealer does after a shuffle.
+    /// The last card is never burned.
+    pub fn burn(&mut self) {
+        if self.deck.len() > 1 {
+            self.burned.extend(self.deck.draw());
+        }
+    }
+
+    /// Shuffles the discards back into the shoe in the middle of a round,
+    /// when it runs out. The cards `in_play` stay on the table, and a fresh
+    /// card is burned.
+    pub fn reshuffle_discards<R: Rng + ?Sized>(&mut self, in_play: &[Card], rng: &mut R) {
+        let mut discards = std::mem::take(&mut self.dealt);
+        discards.append(&mut self.burned);
+        for card in in_play {
+            if let Some(position) = discards.iter().position(|c| c == card) {
+                discards.swap_remove(position);
+            }
+        }
+        self.deck.reshuffle_discards(&mut discards, rng);
+        self.dealt = in_play.to_vec();
+        self.burn();
+    }
+
+    /// The cards dealt since the shoe was shuffled, oldest first.
+    pub fn dealt(&self) -> &[Card] {
+        &self.dealt
+    }
+
+    /// The cards burned since the shoe was shuffled. The player never sees
+    /// them.
+    pub fn burned(&self) -> &[Card] {
+        &self.burned
+    }
+
+    /// The cards left to deal, top first.
+    pub fn cards(&self) -> impl Iterator<Item = &Card> {
+        self.deck.iter()
     }
 
     #[cfg(test)]
     pub fn rigged(draw_order: Vec<Card>) -> Self {
         Self {
             deck: Deck::from_cards(draw_order),
+            dealt: Vec::new(),
+            burned: Vec::new(),
         }
     }
 }
@@ -60,14 +134,16 @@ pub struct PlayerHand {
     pub hand: Hand,
     pub bet: Bet,
     pub is_complete: bool,
+    pub doubled: bool,
 }
 
 impl PlayerHand {
     pub fn new(bet_amount: u32) -> Self {
         PlayerHand {
             hand: Hand::new(),
-            bet: Bet { amount: bet_amount },
+            bet: Bet::new(bet_amount),
             is_complete: false,
+            doubled: false,
         }
     }
 }
@@ -111,6 +187,46 @@ impl std::fmt::Display for GameResult {
     }
 }
 
+/// How one of the player's hands was settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandResult {
+    pub result: GameResult,
+    /// The stake on the hand, including any double.
+    pub bet: u32,
+    /// Winnings less the stake.
+    pub net: i64,
+    pub blackjack: bool,
+    pub doubled: bool,
+    /// Whether the hand came from a split.
+    pub split: bool,
+}
+
+/// An action the engine refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlackjackError {
+    NotPlayerTurn,
+    RoundInProgress,
+    InsufficientFunds,
+    CannotDouble,
+    CannotSplit,
+    Limit(LimitError),
+}
+
+impl std::fmt::Display for BlackjackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlackjackError::NotPlayerTurn => write!(f, "It is not your turn"),
+            BlackjackError::RoundInProgress => write!(f, "The round is still in progress"),
+            BlackjackError::InsufficientFunds => write!(f, "Not enough funds for that bet"),
+            BlackjackError::CannotDouble => write!(f, "This hand cannot be doubled"),
+            BlackjackError::CannotSplit => write!(f, "This hand cannot be split"),
+            BlackjackError::Limit(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for BlackjackError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,7 @@
 use crate::{
+    bank::chips::PayoutRule,
     cards::Card,
-    game::blackjack::{types::PlayerAction, BlackjackState, GameResult},
+    game::blackjack::{side_bets::SideBetResult, types::PlayerAction, BlackjackState, GameResult},
 };
 
 #[derive(Debug, Clone)]
@@ -18,6 +19,10 @@ pub struct BlackjackView {
 
     pub bank_balance: u32,
     pub total_bet: u32,
+    /// How odd payouts, such as 3:2 on a $5 bet, are rounded.
+    pub payout_rule: PayoutRule,
+    /// Side bets settled on the initial deal of this round.
+    pub side_bets: Vec<SideBetResult>,
 
     pub result: GameResult,
 
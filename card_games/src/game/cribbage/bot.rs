@@ -0,0 +1,122 @@
+//! Computer opponents for Cribbage.
+//!
+//! [`BasicBot`] keeps the four cards with the best average score over every
+//! possible starter, and pegs whichever playable card scores the most now.
+
+use crate::cards::{deck_builder::DeckBuilder, Card};
+
+use super::{
+    scoring::{card_value, peg_points, score_hand},
+    types::DISCARD_COUNT,
+};
+
+/// A computer player for Cribbage.
+pub trait CribbageBot {
+    /// Chooses the cards to lay away to the crib.
+    fn choose_discards(&mut self, hand: &[Card], is_dealer: bool) -> Vec<Card>;
+
+    /// Chooses a card from `playable` to peg, given the cards played since
+    /// the count was last reset.
+    fn choose_play(&mut self, playable: &[Card], series: &[Card]) -> Card;
+}
+
+/// A greedy Cribbage player.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BasicBot;
+
+impl BasicBot {
+    /// Total score of `kept` across every starter not in `hand`.
+    fn expected_points(hand: &[Card], kept: &[Card]) -> u32 {
+        DeckBuilder::new()
+            .standard52()
+            .build()
+            .into_iter()
+            .filter(|starter| !hand.contains(starter))
+            .map(|starter| score_hand(kept, &starter, false).total())
+            .sum()
+    }
+}
+
+impl CribbageBot for BasicBot {
+    fn choose_discards(&mut self, hand: &[Card], _: bool) -> Vec<Card> {
+        let mut best: Option<(u32, Vec<Card>)> = None;
+        for i in 0..hand.len() {
+            for j in i + 1..hand.len() {
+                let kept: Vec<Card> = hand
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != i && *k != j)
+                    .map(|(_, c)| *c)
+                    .collect();
+                let points = Self::expected_points(hand, &kept);
+                if best.as_ref().is_none_or(|(b, _)| points > *b) {
+                    best = Some((points, vec![hand[i], hand[j]]));
+                }
+            }
+        }
+
+        let mut discards = best.map(|(_, d)| d).unwrap_or_default();
+        discards.truncate(DISCARD_COUNT);
+        discards
+    }
+
+    fn choose_play(&mut self, playable: &[Card], series: &[Card]) -> Card {
+        let count: u8 = series.iter().map(card_value).sum();
+        *playable
+            .iter()
+            .max_by_key(|card| {
+                let mut next = series.to_vec();
+                next.push(**card);
+                let after = count + card_value(card);
+                // Avoid leaving 5 or 21, where a ten-card scores for the opponent.
+                let risky = after == 5 || after == 21;
+                (peg_points(&next), !risky, card_value(card))
+            })
+            .expect("a playable card")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Suit, Value};
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    #[test]
+    fn keeps_the_fives_and_jack() {
+        let hand = vec![
+            card(Suit::CLUBS, Value::FIVE),
+            card(Suit::DIAMONDS, Value::FIVE),
+            card(Suit::HEARTS, Value::JACK),
+            card(Suit::SPADES, Value::FIVE),
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::DIAMONDS, Value::NINE),
+        ];
+        let mut discards = BasicBot.choose_discards(&hand, false);
+        discards.sort_by_key(|c| c.rank());
+        assert_eq!(
+            discards,
+            vec![
+                card(Suit::CLUBS, Value::TWO),
+                card(Suit::DIAMONDS, Value::NINE)
+            ]
+        );
+    }
+
+    #[test]
+    fn pegs_for_fifteen() {
+        let series = vec![card(Suit::CLUBS, Value::EIGHT)];
+        let playable = vec![
+            card(Suit::HEARTS, Value::TWO),
+            card(Suit::SPADES, Value::SEVEN),
+            card(Suit::DIAMONDS, Value::KING),
+        ];
+        assert_eq!(
+            BasicBot.choose_play(&playable, &series),
+            card(Suit::SPADES, Value::SEVEN)
+        );
+    }
+}
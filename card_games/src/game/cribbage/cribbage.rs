@@ -0,0 +1,592 @@
+use crate::{
+    cards::{deck_builder::DeckBuilder, hand::Hand, Card, Deck, Value},
+    game::cribbage::{
+        bot::{BasicBot, CribbageBot},
+        scoring::{card_value, peg_points, score_hand},
+        types::{
+            CribbageError, CribbageState, PegPlay, ShowScores, DEAL_SIZE, DISCARD_COUNT,
+            HUMAN_SEAT, MAX_COUNT, SEATS, WINNING_SCORE,
+        },
+        view::CribbageView,
+    },
+    player::Player,
+};
+
+/// Points the dealer scores when the starter is a jack ("his heels").
+const HIS_HEELS: u32 = 2;
+
+/// Points for a go, or for playing the last card.
+const GO: u32 = 1;
+
+pub struct Cribbage {
+    state: CribbageState,
+    players: Vec<Player>,
+    bot: Box<dyn CribbageBot>,
+
+    hand_number: usize,
+    dealer: usize,
+    scores: [u32; SEATS],
+
+    deck: Deck,
+    crib: Hand,
+    starter: Option<Card>,
+
+    /// Cards each seat still has to peg; `players[..].hand` keeps the full
+    /// hand for the show.
+    pegging: [Vec<Card>; SEATS],
+    /// Cards played since the count was last reset.
+    series: Vec<Card>,
+    peg_log: Vec<PegPlay>,
+    show: Option<ShowScores>,
+}
+
+impl Default for Cribbage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cribbage {
+    /// Starts a game against a [`BasicBot`] opponent.
+    pub fn new() -> Self {
+        Self::with_opponent(Box::new(BasicBot))
+    }
+
+    /// Starts a game against the given opponent. The opponent deals first,
+    /// so the human counts first in the opening hand.
+    pub fn with_opponent(bot: Box<dyn CribbageBot>) -> Self {
+        let mut game = Cribbage {
+            state: CribbageState::Discarding,
+            players: vec![
+                Player::new("You".to_string()),
+                Player::new("CPU".to_string()),
+            ],
+            bot,
+
+            hand_number: 0,
+            dealer: 1 - HUMAN_SEAT,
+            scores: [0; SEATS],
+
+            deck: Deck::from_cards(Vec::new()),
+            crib: Hand::new(),
+            starter: None,
+
+            pegging: [Vec::new(), Vec::new()],
+            series: Vec::new(),
+            peg_log: Vec::new(),
+            show: None,
+        };
+        game.start_hand();
+        game
+    }
+
+    /// The non-dealer, who plays first and counts first.
+    fn pone(&self) -> usize {
+        1 - self.dealer
+    }
+
+    fn start_hand(&mut self) {
+        self.deck = DeckBuilder::new().standard52().build();
+        self.deck.shuffle();
+
+        for (seat, player) in self.players.iter_mut().enumerate() {
+            player.reset_hand();
+            player.set_dealer(seat == self.dealer);
+        }
+        self.deck
+            .deal(DEAL_SIZE as u8, self.players.iter_mut())
+            .expect("a 52-card deck deals six cards to two players");
+        for player in &mut self.players {
+            player.hand.sort_by_key(|c| c.rank());
+        }
+
+        self.crib.clear_hand();
+        self.starter = None;
+        self.pegging = [Vec::new(), Vec::new()];
+        self.series.clear();
+        self.peg_log.clear();
+        self.show = None;
+        self.state = CribbageState::Discarding;
+    }
+
+    /// Lays two cards from the human's hand away to the crib. The opponent
+    /// discards at the same time, the starter is cut and pegging begins.
+    pub fn discard(&mut self, cards: &[Card]) -> Result<(), CribbageError> {
+        if self.state != CribbageState::Discarding {
+            return Err(CribbageError::NotDiscarding);
+        }
+        if cards.len() != DISCARD_COUNT {
+            return Err(CribbageError::WrongDiscardCount);
+        }
+
+        let mut remaining = self.players[HUMAN_SEAT].hand.clone();
+        if !cards.iter().all(|c| remaining.remove(c).is_some()) {
+            return Err(CribbageError::CardNotInHand);
+        }
+
+        let bot_seat = 1 - HUMAN_SEAT;
+        let bot_hand = self.players[bot_seat].hand.cards().to_vec();
+        let mut bot_discards = self.bot.choose_discards(&bot_hand, self.dealer == bot_seat);
+        // A misbehaving bot forfeits its choice to its first two cards.
+        let mut check = self.players[bot_seat].hand.clone();
+        if bot_discards.len() != DISCARD_COUNT
+            || !bot_discards.iter().all(|c| check.remove(c).is_some())
+        {
+            bot_discards = bot_hand[..DISCARD_COUNT].to_vec();
+        }
+
+        for (seat, discards) in [(HUMAN_SEAT, cards.to_vec()), (bot_seat, bot_discards)] {
+            for card in discards {
+                self.players[seat].hand.remove(&card);
+                self.crib.add(card);
+            }
+        }
+
+        self.cut_starter();
+        Ok(())
+    }
+
+    fn cut_starter(&mut self) {
+        let starter = self.deck.draw().expect("cards remain after the deal");
+        self.starter = Some(starter);
+
+        if *starter.value() == Value::JACK && self.award(self.dealer, HIS_HEELS) {
+            return;
+        }
+
+        for seat in 0..SEATS {
+            self.pegging[seat] = self.players[seat].hand.cards().to_vec();
+        }
+        self.state = CribbageState::Pegging {
+            to_play: self.pone(),
+        };
+        self.run_bot();
+    }
+
+    /// Adds points to a seat's score. Returns `true` if that ends the game.
+    fn award(&mut self, seat: usize, points: u32) -> bool {
+        self.scores[seat] += points;
+        if self.scores[seat] >= WINNING_SCORE {
+            self.state = CribbageState::GameOver { winner: seat };
+            return true;
+        }
+        false
+    }
+
+    /// Returns the running count of the current series.
+    pub fn count(&self) -> u8 {
+        self.series.iter().map(card_value).sum()
+    }
+
+    fn playable(&self, seat: usize) -> Vec<Card> {
+        let count = self.count();
+        self.pegging[seat]
+            .iter()
+            .filter(|c| count + card_value(c) <= MAX_COUNT)
+            .copied()
+            .collect()
+    }
+
+    /// Pegs a card from the human's hand. A player who cannot play says
+    /// "go" automatically.
+    pub fn play(&mut self, card: Card) -> Result<(), CribbageError> {
+        match self.state {
+            CribbageState::Pegging { to_play } if to_play == HUMAN_SEAT => {}
+            _ => return Err(CribbageError::NotYourTurn),
+        }
+        if !self.pegging[HUMAN_SEAT].contains(&card) {
+            return Err(CribbageError::CardNotInHand);
+        }
+        if self.count() + card_value(&card) > MAX_COUNT {
+            return Err(CribbageError::CountExceeded);
+        }
+
+        self.play_card(HUMAN_SEAT, card);
+        self.run_bot();
+        Ok(())
+    }
+
+    fn run_bot(&mut self) {
+        while let CribbageState::Pegging { to_play } = self.state {
+            if to_play == HUMAN_SEAT {
+                break;
+            }
+
+            let playable = self.playable(to_play);
+            let choice = self.bot.choose_play(&playable, &self.series);
+            let card = if playable.contains(&choice) {
+                choice
+            } else {
+                playable[0]
+            };
+            self.play_card(to_play, card);
+        }
+    }
+
+    fn play_card(&mut self, seat: usize, card: Card) {
+        self.pegging[seat].retain(|c| *c != card);
+        self.series.push(card);
+
+        let count = self.count();
+        let points = peg_points(&self.series);
+        self.peg_log.push(PegPlay {
+            seat,
+            card,
+            count,
+            points,
+            go: false,
+        });
+        if self.award(seat, points) {
+            return;
+        }
+
+        if count == MAX_COUNT {
+            self.series.clear();
+        }
+        self.advance(seat);
+    }
+
+    /// Passes the turn after `last` has played, handling goes and the end of
+    /// pegging.
+    fn advance(&mut self, last: usize) {
+        let other = 1 - last;
+        if !self.playable(other).is_empty() {
+            self.state = CribbageState::Pegging { to_play: other };
+            return;
+        }
+        if !self.playable(last).is_empty() {
+            self.state = CribbageState::Pegging { to_play: last };
+            return;
+        }
+
+        // Neither player can go: one for the go (or last card), unless the
+        // series already ended on 31.
+        if !self.series.is_empty() {
+            if let Some(play) = self.peg_log.last_mut() {
+                play.points += GO;
+                play.go = true;
+            }
+            if self.award(last, GO) {
+                return;
+            }
+            self.series.clear();
+        }
+
+        if self.pegging.iter().all(|p| p.is_empty()) {
+            self.count_hands();
+        } else if self.pegging[other].is_empty() {
+            self.state = CribbageState::Pegging { to_play: last };
+        } else {
+            self.state = CribbageState::Pegging { to_play: other };
+        }
+    }
+
+    /// Scores the show: the pone's hand, then the dealer's hand, then the
+    /// crib. The game can end partway through.
+    fn count_hands(&mut self) {
+        let starter = self.starter.expect("starter cut before pegging");
+        let (pone, dealer) = (self.pone(), self.dealer);
+
+        let show = ShowScores {
+            pone: score_hand(self.players[pone].hand.cards(), &starter, false),
+            dealer: score_hand(self.players[dealer].hand.cards(), &starter, false),
+            crib: score_hand(self.crib.cards(), &starter, true),
+        };
+        self.show = Some(show);
+
+        for (seat, score) in [
+            (pone, show.pone),
+            (dealer, show.dealer),
+            (dealer, show.crib),
+        ] {
+            if self.award(seat, score.total()) {
+                return;
+            }
+        }
+        self.state = CribbageState::HandOver;
+    }
+
+    /// Starts the next hand once the current one is scored. The deal
+    /// alternates between players.
+    pub fn next_hand(&mut self) -> Result<(), CribbageError> {
+        if self.state != CribbageState::HandOver {
+            return Err(CribbageError::HandNotOver);
+        }
+        self.hand_number += 1;
+        self.dealer = self.pone();
+        self.start_hand();
+        Ok(())
+    }
+
+    pub fn view(&self) -> CribbageView {
+        let revealed = matches!(
+            self.state,
+            CribbageState::HandOver | CribbageState::GameOver { .. }
+        ) && self.show.is_some();
+        let opponent = 1 - HUMAN_SEAT;
+
+        let playable = match self.state {
+            CribbageState::Pegging { to_play } if to_play == HUMAN_SEAT => {
+                self.playable(HUMAN_SEAT)
+            }
+            _ => Vec::new(),
+        };
+
+        CribbageView {
+            phase: self.state,
+            hand_number: self.hand_number,
+            dealer: self.dealer,
+
+            player_names: self.players.iter().map(|p| p.name().to_string()).collect(),
+            scores: self.scores,
+
+            hand: self.players[HUMAN_SEAT].hand.cards().to_vec(),
+            to_peg: self.pegging[HUMAN_SEAT].clone(),
+            playable,
+            opponent_cards_left: self.pegging[opponent].len(),
+            crib_size: self.crib.len(),
+            starter: self.starter,
+
+            count: self.count(),
+            series: self.series.clone(),
+            peg_log: self.peg_log.clone(),
+
+            show: self.show,
+            opponent_hand: revealed.then(|| self.players[opponent].hand.cards().to_vec()),
+            crib: revealed.then(|| self.crib.cards().to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Suit;
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    /// A game in the middle of pegging with empty show hands, so that every
+    /// point scored comes from the given pegging cards.
+    fn pegging_position(human: Vec<Card>, cpu: Vec<Card>, to_play: usize) -> Cribbage {
+        let mut game = Cribbage::new();
+        for player in &mut game.players {
+            player.reset_hand();
+        }
+        game.crib.clear_hand();
+        game.starter = Some(card(Suit::HEARTS, Value::TWO));
+        game.pegging = [human, cpu];
+        game.state = CribbageState::Pegging { to_play };
+        game
+    }
+
+    fn play_out_game(game: &mut Cribbage) {
+        let mut bot = BasicBot;
+        loop {
+            match game.state {
+                CribbageState::Discarding => {
+                    let hand = game.view().hand;
+                    let discards = bot.choose_discards(&hand, game.dealer == HUMAN_SEAT);
+                    game.discard(&discards).unwrap();
+                }
+                CribbageState::Pegging { .. } => {
+                    let view = game.view();
+                    let card = bot.choose_play(&view.playable, &view.series);
+                    game.play(card).unwrap();
+                }
+                CribbageState::HandOver => game.next_hand().unwrap(),
+                CribbageState::GameOver { .. } => break,
+            }
+        }
+    }
+
+    #[test]
+    fn new_game_deals_six_each_with_opponent_dealing() {
+        let game = Cribbage::new();
+        assert_eq!(game.state, CribbageState::Discarding);
+        for player in &game.players {
+            assert_eq!(player.hand.len(), DEAL_SIZE);
+        }
+        assert!(game.players[1].is_dealer());
+        assert_eq!(game.pone(), HUMAN_SEAT);
+    }
+
+    #[test]
+    fn discard_validates_cards() {
+        let mut game = Cribbage::new();
+        let hand = game.view().hand;
+
+        assert_eq!(
+            game.discard(&hand[..1]),
+            Err(CribbageError::WrongDiscardCount)
+        );
+        let opponent_card = game.players[1].hand.cards()[0];
+        assert_eq!(
+            game.discard(&[hand[0], opponent_card]),
+            Err(CribbageError::CardNotInHand)
+        );
+        assert_eq!(game.play(hand[0]), Err(CribbageError::NotYourTurn));
+    }
+
+    #[test]
+    fn discard_fills_crib_and_cuts_starter() {
+        let mut game = Cribbage::new();
+        let hand = game.view().hand;
+        game.discard(&hand[..2]).unwrap();
+
+        assert_eq!(game.crib.len(), 4);
+        assert!(game.starter.is_some());
+        for player in &game.players {
+            assert_eq!(player.hand.len(), 4);
+        }
+        assert_eq!(
+            game.state,
+            CribbageState::Pegging {
+                to_play: HUMAN_SEAT
+            }
+        );
+    }
+
+    #[test]
+    fn jack_starter_scores_his_heels_for_dealer() {
+        let mut game = Cribbage::new();
+        game.deck = Deck::from_cards(vec![card(Suit::CLUBS, Value::JACK)]);
+        let hand = game.view().hand;
+        game.discard(&hand[..2]).unwrap();
+
+        assert!(game.scores[game.dealer] >= HIS_HEELS);
+        assert_eq!(game.scores[HUMAN_SEAT], 0);
+    }
+
+    #[test]
+    fn go_and_last_card_score_one_each() {
+        // K, 8, Q makes 28; the CPU is out and the human cannot play the 9,
+        // so the human takes a go, then one more for the last card.
+        let mut game = pegging_position(
+            vec![
+                card(Suit::CLUBS, Value::KING),
+                card(Suit::CLUBS, Value::QUEEN),
+                card(Suit::CLUBS, Value::NINE),
+            ],
+            vec![card(Suit::DIAMONDS, Value::EIGHT)],
+            HUMAN_SEAT,
+        );
+
+        game.play(card(Suit::CLUBS, Value::KING)).unwrap();
+        assert_eq!(game.count(), 18);
+        game.play(card(Suit::CLUBS, Value::QUEEN)).unwrap();
+        assert_eq!(game.count(), 0);
+        game.play(card(Suit::CLUBS, Value::NINE)).unwrap();
+
+        assert_eq!(game.scores, [2, 0]);
+        assert_eq!(game.state, CribbageState::HandOver);
+        let goes: Vec<bool> = game.peg_log.iter().map(|p| p.go).collect();
+        assert_eq!(goes, vec![false, false, true, true]);
+    }
+
+    #[test]
+    fn cannot_peg_past_31() {
+        let mut game = pegging_position(
+            vec![
+                card(Suit::CLUBS, Value::KING),
+                card(Suit::CLUBS, Value::TWO),
+            ],
+            vec![card(Suit::DIAMONDS, Value::ACE)],
+            HUMAN_SEAT,
+        );
+        game.series = vec![
+            card(Suit::HEARTS, Value::KING),
+            card(Suit::HEARTS, Value::QUEEN),
+            card(Suit::HEARTS, Value::FIVE),
+        ];
+
+        assert_eq!(
+            game.play(card(Suit::CLUBS, Value::KING)),
+            Err(CribbageError::CountExceeded)
+        );
+        assert_eq!(game.view().playable, vec![card(Suit::CLUBS, Value::TWO)]);
+    }
+
+    #[test]
+    fn thirty_one_resets_without_a_go() {
+        let mut game = pegging_position(
+            vec![card(Suit::CLUBS, Value::ACE)],
+            vec![card(Suit::DIAMONDS, Value::FIVE)],
+            HUMAN_SEAT,
+        );
+        game.series = vec![
+            card(Suit::HEARTS, Value::KING),
+            card(Suit::HEARTS, Value::QUEEN),
+            card(Suit::HEARTS, Value::TEN),
+        ];
+
+        game.play(card(Suit::CLUBS, Value::ACE)).unwrap();
+
+        // Two for 31 and no go; the CPU then leads the five and takes one
+        // for the last card.
+        assert_eq!(game.scores, [2, 1]);
+        let goes: Vec<bool> = game.peg_log.iter().map(|p| p.go).collect();
+        assert_eq!(goes, vec![false, true]);
+        assert_eq!(game.peg_log[1].count, 5);
+    }
+
+    #[test]
+    fn pegging_to_121_ends_the_game() {
+        let mut game = pegging_position(
+            vec![card(Suit::CLUBS, Value::SEVEN)],
+            vec![card(Suit::DIAMONDS, Value::TWO)],
+            1,
+        );
+        game.series = vec![card(Suit::HEARTS, Value::EIGHT)];
+        game.scores = [WINNING_SCORE - 2, 0];
+        game.state = CribbageState::Pegging {
+            to_play: HUMAN_SEAT,
+        };
+
+        game.play(card(Suit::CLUBS, Value::SEVEN)).unwrap();
+        assert_eq!(game.state, CribbageState::GameOver { winner: HUMAN_SEAT });
+        assert_eq!(game.next_hand(), Err(CribbageError::HandNotOver));
+    }
+
+    #[test]
+    fn full_game_reaches_121() {
+        let mut game = Cribbage::new();
+        play_out_game(&mut game);
+
+        let CribbageState::GameOver { winner } = game.state else {
+            panic!("game should be over");
+        };
+        assert!(game.scores[winner] >= WINNING_SCORE);
+        assert!(game.scores[1 - winner] < WINNING_SCORE);
+    }
+
+    #[test]
+    fn deal_alternates_between_hands() {
+        let mut game = Cribbage::new();
+        let mut bot = BasicBot;
+        while game.state != CribbageState::HandOver {
+            match game.state {
+                CribbageState::Discarding => {
+                    let hand = game.view().hand;
+                    game.discard(&hand[..2]).unwrap();
+                }
+                CribbageState::Pegging { .. } => {
+                    let view = game.view();
+                    game.play(bot.choose_play(&view.playable, &view.series))
+                        .unwrap();
+                }
+                // Extremely unlikely from zero in one hand.
+                CribbageState::GameOver { .. } => return,
+                CribbageState::HandOver => unreachable!(),
+            }
+        }
+
+        let view = game.view();
+        assert!(view.show.is_some());
+        assert_eq!(view.crib.as_ref().map(Vec::len), Some(4));
+        game.next_hand().unwrap();
+        assert_eq!(game.dealer, HUMAN_SEAT);
+        assert!(game.players[HUMAN_SEAT].is_dealer());
+    }
+}
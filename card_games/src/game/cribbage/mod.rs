@@ -0,0 +1,10 @@
+pub mod bot;
+#[allow(clippy::module_inception)]
+pub mod cribbage;
+pub mod scoring;
+pub mod types;
+pub mod view;
+
+pub use cribbage::Cribbage;
+pub use scoring::{peg_points, score_hand};
+pub use types::{CribbageError, CribbageState, HandScore};
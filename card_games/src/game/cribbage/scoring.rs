@@ -0,0 +1,290 @@
+//! Cribbage scoring for the show and for pegging.
+//!
+//! [`score_hand`] counts a four-card hand with its starter and can be used on
+//! its own as a "count my hand" tool:
+//! - Fifteens: 2 for each combination of cards totalling 15
+//! - Pairs: 2 for each pair of equal rank
+//! - Runs: 1 per card in each run of three or more, counted once per
+//!   combination
+//! - Flush: 4 when the hand is one suit (5 with the starter); a crib only
+//!   scores a five-card flush
+//! - Nobs: 1 for the jack of the starter's suit
+
+use crate::cards::{Card, Value};
+
+use super::types::HandScore;
+
+/// Returns the counting value of a card: aces 1, face cards 10.
+pub fn card_value(card: &Card) -> u8 {
+    card.rank().map(|rank| rank.min(10)).unwrap_or(0)
+}
+
+/// Scores a hand (normally four cards) together with the starter.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{Card, Suit, Value};
+/// use card_games::game::cribbage::score_hand;
+/// let hand = [
+///     Card::new(Suit::CLUBS, Value::FIVE),
+///     Card::new(Suit::DIAMONDS, Value::FIVE),
+///     Card::new(Suit::HEARTS, Value::FIVE),
+///     Card::new(Suit::SPADES, Value::JACK),
+/// ];
+/// let starter = Card::new(Suit::SPADES, Value::FIVE);
+/// assert_eq!(score_hand(&hand, &starter, false).total(), 29);
+/// ```
+pub fn score_hand(hand: &[Card], starter: &Card, is_crib: bool) -> HandScore {
+    let mut all = hand.to_vec();
+    all.push(*starter);
+
+    HandScore {
+        fifteens: fifteens(&all),
+        pairs: pairs(&all),
+        runs: runs(&all),
+        flush: flush(hand, starter, is_crib),
+        nobs: nobs(hand, starter),
+    }
+}
+
+/// Two points for every combination of cards adding up to 15. Counts the
+/// combinations by total rather than trying each one, so any number of
+/// cards can be scored.
+fn fifteens(cards: &[Card]) -> u32 {
+    // ways[total]: how many combinations of the cards so far add up to it.
+    let mut ways = [0u32; 16];
+    ways[0] = 1;
+    for value in cards.iter().map(|card| card_value(card) as usize) {
+        for total in (value..ways.len()).rev() {
+            ways[total] = ways[total].saturating_add(ways[total - value]);
+        }
+    }
+    ways[15].saturating_mul(2)
+}
+
+fn pairs(cards: &[Card]) -> u32 {
+    let mut points = 0;
+    for (i, a) in cards.iter().enumerate() {
+        for b in &cards[i + 1..] {
+            if a.rank() == b.rank() {
+                points += 2;
+            }
+        }
+    }
+    points
+}
+
+fn runs(cards: &[Card]) -> u32 {
+    let mut counts = [0u32; 14];
+    for card in cards {
+        if let Some(rank) = card.rank() {
+            counts[rank as usize] += 1;
+        }
+    }
+
+    let mut points = 0;
+    let mut rank = 1;
+    while rank <= 13 {
+        if counts[rank] == 0 {
+            rank += 1;
+            continue;
+        }
+
+        let start = rank;
+        let mut combinations = 1;
+        while rank <= 13 && counts[rank] > 0 {
+            combinations *= counts[rank];
+            rank += 1;
+        }
+
+        let length = (rank - start) as u32;
+        if length >= 3 {
+            points += length * combinations;
+        }
+    }
+    points
+}
+
+fn flush(hand: &[Card], starter: &Card, is_crib: bool) -> u32 {
+    let Some(first) = hand.first() else {
+        return 0;
+    };
+    if !hand.iter().all(|c| c.suit() == first.suit()) {
+        return 0;
+    }
+
+    match (starter.suit() == first.suit(), is_crib) {
+        (true, _) => hand.len() as u32 + 1,
+        (false, false) => hand.len() as u32,
+        (false, true) => 0,
+    }
+}
+
+fn nobs(hand: &[Card], starter: &Card) -> u32 {
+    hand.iter()
+        .any(|c| *c.value() == Value::JACK && c.suit() == starter.suit()) as u32
+}
+
+/// Scores the card just played during pegging.
+///
+/// `series` holds the cards played since the count was last reset, with the
+/// newest card last. Points come from reaching 15 or 31, pairs and runs
+/// formed with the cards directly before it.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{Card, Suit, Value};
+/// use card_games::game::cribbage::peg_points;
+/// let series = [
+///     Card::new(Suit::CLUBS, Value::SEVEN),
+///     Card::new(Suit::HEARTS, Value::EIGHT),
+/// ];
+/// assert_eq!(peg_points(&series), 2); // fifteen two
+/// ```
+pub fn peg_points(series: &[Card]) -> u32 {
+    let Some(last) = series.last() else {
+        return 0;
+    };
+
+    let count: u32 = series.iter().map(|c| card_value(c) as u32).sum();
+    let mut points = 0;
+    if count == 15 || count == 31 {
+        points += 2;
+    }
+
+    let same_rank = series
+        .iter()
+        .rev()
+        .take_while(|c| c.rank() == last.rank())
+        .count();
+    points += match same_rank {
+        2 => 2,
+        3 => 6,
+        4 => 12,
+        _ => 0,
+    };
+
+    points += (3..=series.len())
+        .rev()
+        .find(|&n| is_run(&series[series.len() - n..]))
+        .unwrap_or(0) as u32;
+
+    points
+}
+
+fn is_run(cards: &[Card]) -> bool {
+    let mut ranks: Vec<u8> = cards.iter().filter_map(|c| c.rank()).collect();
+    if ranks.len() != cards.len() {
+        return false;
+    }
+    ranks.sort_unstable();
+    ranks.windows(2).all(|w| w[1] == w[0] + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Suit;
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    #[test]
+    fn perfect_hand_scores_29() {
+        let hand = [
+            card(Suit::CLUBS, Value::FIVE),
+            card(Suit::DIAMONDS, Value::FIVE),
+            card(Suit::HEARTS, Value::FIVE),
+            card(Suit::SPADES, Value::JACK),
+        ];
+        let score = score_hand(&hand, &card(Suit::SPADES, Value::FIVE), false);
+        assert_eq!(score.fifteens, 16);
+        assert_eq!(score.pairs, 12);
+        assert_eq!(score.nobs, 1);
+        assert_eq!(score.total(), 29);
+    }
+
+    #[test]
+    fn fifteens_count_hands_of_any_size() {
+        // Any three of forty fives make fifteen: 40 choose 3 combinations.
+        let fives = vec![card(Suit::CLUBS, Value::FIVE); 40];
+        assert_eq!(fifteens(&fives), 2 * 9_880);
+    }
+
+    #[test]
+    fn double_run_counts_each_combination() {
+        // 3-4-4-5 with a 9 starter: two runs of three and a pair.
+        let hand = [
+            card(Suit::CLUBS, Value::THREE),
+            card(Suit::DIAMONDS, Value::FOUR),
+            card(Suit::HEARTS, Value::FOUR),
+            card(Suit::SPADES, Value::FIVE),
+        ];
+        let score = score_hand(&hand, &card(Suit::CLUBS, Value::NINE), false);
+        assert_eq!(score.runs, 6);
+        assert_eq!(score.pairs, 2);
+    }
+
+    #[test]
+    fn crib_needs_five_card_flush() {
+        let hand = [
+            card(Suit::HEARTS, Value::TWO),
+            card(Suit::HEARTS, Value::FOUR),
+            card(Suit::HEARTS, Value::SIX),
+            card(Suit::HEARTS, Value::EIGHT),
+        ];
+        let starter = card(Suit::CLUBS, Value::KING);
+        assert_eq!(score_hand(&hand, &starter, false).flush, 4);
+        assert_eq!(score_hand(&hand, &starter, true).flush, 0);
+        assert_eq!(
+            score_hand(&hand, &card(Suit::HEARTS, Value::KING), true).flush,
+            5
+        );
+    }
+
+    #[test]
+    fn nineteen_hand_scores_zero() {
+        let hand = [
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::DIAMONDS, Value::FOUR),
+            card(Suit::HEARTS, Value::SIX),
+            card(Suit::SPADES, Value::EIGHT),
+        ];
+        assert_eq!(
+            score_hand(&hand, &card(Suit::CLUBS, Value::QUEEN), false).total(),
+            0
+        );
+    }
+
+    #[test]
+    fn pegging_pairs_and_royal_pairs() {
+        let five = |s| card(s, Value::FIVE);
+        assert_eq!(peg_points(&[five(Suit::CLUBS), five(Suit::HEARTS)]), 2);
+        assert_eq!(
+            peg_points(&[five(Suit::CLUBS), five(Suit::HEARTS), five(Suit::SPADES)]),
+            6 + 2 // pair royal, and 15
+        );
+    }
+
+    #[test]
+    fn pegging_runs_in_any_order() {
+        let series = [
+            card(Suit::CLUBS, Value::FOUR),
+            card(Suit::HEARTS, Value::SIX),
+            card(Suit::SPADES, Value::FIVE),
+        ];
+        assert_eq!(peg_points(&series), 3 + 2); // run of three, fifteen
+    }
+
+    #[test]
+    fn pegging_thirty_one_scores_two() {
+        let series = [
+            card(Suit::CLUBS, Value::KING),
+            card(Suit::HEARTS, Value::QUEEN),
+            card(Suit::SPADES, Value::JACK),
+            card(Suit::DIAMONDS, Value::ACE),
+        ];
+        assert_eq!(peg_points(&series), 2);
+    }
+}
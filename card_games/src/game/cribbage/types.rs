@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use crate::cards::Card;
+
+/// Number of players in a two-handed game.
+pub const SEATS: usize = 2;
+
+/// Cards dealt to each player.
+pub const DEAL_SIZE: usize = 6;
+
+/// Cards each player lays away to the crib.
+pub const DISCARD_COUNT: usize = 2;
+
+/// The count may not go past this during pegging.
+pub const MAX_COUNT: u8 = 31;
+
+/// The first player to reach this score wins.
+pub const WINNING_SCORE: u32 = 121;
+
+/// The human player's seat; the other seat is played by a bot.
+pub const HUMAN_SEAT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CribbageState {
+    Discarding,
+    Pegging { to_play: usize },
+    HandOver,
+    GameOver { winner: usize },
+}
+
+impl Display for CribbageState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CribbageState::Discarding => write!(f, "Choose two cards for the crib"),
+            CribbageState::Pegging { to_play } if *to_play == HUMAN_SEAT => {
+                write!(f, "Your turn to play")
+            }
+            CribbageState::Pegging { .. } => write!(f, "Waiting for opponent"),
+            CribbageState::HandOver => write!(f, "Hand over"),
+            CribbageState::GameOver { winner } if *winner == HUMAN_SEAT => write!(f, "You win!"),
+            CribbageState::GameOver { .. } => write!(f, "Opponent wins"),
+        }
+    }
+}
+
+/// The points in a hand, broken down by category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HandScore {
+    pub fifteens: u32,
+    pub pairs: u32,
+    pub runs: u32,
+    pub flush: u32,
+    pub nobs: u32,
+}
+
+impl HandScore {
+    pub fn total(&self) -> u32 {
+        self.fifteens + self.pairs + self.runs + self.flush + self.nobs
+    }
+}
+
+impl Display for HandScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [
+            ("fifteens", self.fifteens),
+            ("pairs", self.pairs),
+            ("runs", self.runs),
+            ("flush", self.flush),
+            ("nobs", self.nobs),
+        ]
+        .iter()
+        .filter(|(_, points)| *points > 0)
+        .map(|(name, points)| format!("{} {}", name, points))
+        .collect();
+
+        if parts.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{} = {}", parts.join(", "), self.total())
+        }
+    }
+}
+
+/// A card played during pegging and what it scored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PegPlay {
+    pub seat: usize,
+    pub card: Card,
+    /// The count after this card.
+    pub count: u8,
+    /// Points scored, including one for a go or the last card.
+    pub points: u32,
+    /// Whether the opponent could not follow, ending the series.
+    pub go: bool,
+}
+
+/// The hands counted at the end of a deal, in counting order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShowScores {
+    pub pone: HandScore,
+    pub dealer: HandScore,
+    pub crib: HandScore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CribbageError {
+    NotDiscarding,
+    WrongDiscardCount,
+    CardNotInHand,
+    NotYourTurn,
+    CountExceeded,
+    HandNotOver,
+}
+
+impl Display for CribbageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CribbageError::NotDiscarding => write!(f, "Cards are not being laid away now"),
+            CribbageError::WrongDiscardCount => {
+                write!(f, "Lay away exactly {} cards", DISCARD_COUNT)
+            }
+            CribbageError::CardNotInHand => write!(f, "That card is not in your hand"),
+            CribbageError::NotYourTurn => write!(f, "It is not your turn"),
+            CribbageError::CountExceeded => write!(f, "That card would take the count past 31"),
+            CribbageError::HandNotOver => write!(f, "The hand is still in progress"),
+        }
+    }
+}
+
+impl std::error::Error for CribbageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hand_score_lists_scoring_categories() {
+        let score = HandScore {
+            fifteens: 4,
+            runs: 3,
+            ..Default::default()
+        };
+        assert_eq!(score.total(), 7);
+        assert_eq!(score.to_string(), "fifteens 4, runs 3 = 7");
+        assert_eq!(HandScore::default().to_string(), "0");
+    }
+}
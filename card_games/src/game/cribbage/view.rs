@@ -0,0 +1,33 @@
+use crate::{
+    cards::Card,
+    game::cribbage::types::{CribbageState, PegPlay, ShowScores, SEATS},
+};
+
+#[derive(Debug, Clone)]
+pub struct CribbageView {
+    pub phase: CribbageState,
+    pub hand_number: usize,
+    pub dealer: usize,
+
+    pub player_names: Vec<String>,
+    pub scores: [u32; SEATS],
+
+    /// The human's hand: six cards while discarding, then the four kept.
+    pub hand: Vec<Card>,
+    /// Cards the human has not yet pegged.
+    pub to_peg: Vec<Card>,
+    /// Cards the human may peg now without passing 31.
+    pub playable: Vec<Card>,
+    pub opponent_cards_left: usize,
+    pub crib_size: usize,
+    pub starter: Option<Card>,
+
+    pub count: u8,
+    pub series: Vec<Card>,
+    pub peg_log: Vec<PegPlay>,
+
+    pub show: Option<ShowScores>,
+    /// The opponent's hand and the crib, revealed after the show.
+    pub opponent_hand: Option<Vec<Card>>,
+    pub crib: Option<Vec<Card>>,
+}
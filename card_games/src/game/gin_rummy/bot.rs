@@ -0,0 +1,123 @@
+//! Computer opponents for Gin Rummy.
+//!
+//! [`BasicBot`] takes the upcard only when it completes a meld, discards
+//! whatever leaves the least deadwood, and knocks as soon as it may.
+
+use crate::cards::Card;
+
+use super::{
+    melds::{best_discard, best_melds},
+    types::{DrawSource, KNOCK_LIMIT},
+};
+
+/// A computer player for Gin Rummy.
+pub trait GinBot {
+    /// Chooses where to draw from, given the ten-card hand and the top of
+    /// the discard pile.
+    fn choose_draw(&mut self, hand: &[Card], upcard: Option<&Card>) -> DrawSource;
+
+    /// Chooses a discard from the eleven-card hand, and whether to knock
+    /// with it. `picked_up` is the card taken from the discard pile this
+    /// turn, which may not be thrown back.
+    fn choose_discard(&mut self, hand: &[Card], picked_up: Option<&Card>) -> (Card, bool);
+}
+
+/// A greedy Gin Rummy player.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BasicBot;
+
+impl GinBot for BasicBot {
+    fn choose_draw(&mut self, hand: &[Card], upcard: Option<&Card>) -> DrawSource {
+        let Some(upcard) = upcard else {
+            return DrawSource::Stock;
+        };
+
+        let current = best_melds(hand).deadwood_points();
+        let mut with_upcard = hand.to_vec();
+        with_upcard.push(*upcard);
+        match best_discard(&with_upcard, Some(upcard)) {
+            Some((_, arrangement))
+                if arrangement.is_melded(upcard) && arrangement.deadwood_points() < current =>
+            {
+                DrawSource::Discard
+            }
+            _ => DrawSource::Stock,
+        }
+    }
+
+    fn choose_discard(&mut self, hand: &[Card], picked_up: Option<&Card>) -> (Card, bool) {
+        let (discard, arrangement) =
+            best_discard(hand, picked_up).expect("an eleven-card hand has a discard");
+        (discard, arrangement.deadwood_points() <= KNOCK_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cards::{Suit, Value},
+        game::gin_rummy::melds::deadwood_value,
+    };
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    fn hand() -> Vec<Card> {
+        vec![
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::CLUBS, Value::THREE),
+            card(Suit::CLUBS, Value::FOUR),
+            card(Suit::HEARTS, Value::NINE),
+            card(Suit::SPADES, Value::NINE),
+            card(Suit::DIAMONDS, Value::KING),
+            card(Suit::HEARTS, Value::QUEEN),
+            card(Suit::SPADES, Value::JACK),
+            card(Suit::DIAMONDS, Value::ACE),
+            card(Suit::HEARTS, Value::TWO),
+        ]
+    }
+
+    #[test]
+    fn takes_an_upcard_that_makes_a_meld() {
+        let nine = card(Suit::CLUBS, Value::NINE);
+        assert_eq!(
+            BasicBot.choose_draw(&hand(), Some(&nine)),
+            DrawSource::Discard
+        );
+
+        let stray = card(Suit::DIAMONDS, Value::SEVEN);
+        assert_eq!(
+            BasicBot.choose_draw(&hand(), Some(&stray)),
+            DrawSource::Stock
+        );
+        assert_eq!(BasicBot.choose_draw(&hand(), None), DrawSource::Stock);
+    }
+
+    #[test]
+    fn discards_high_deadwood_and_knocks_when_low() {
+        let mut cards = hand();
+        cards.push(card(Suit::CLUBS, Value::NINE));
+        let (discard, knock) = BasicBot.choose_discard(&cards, None);
+        assert_eq!(deadwood_value(&discard), 10);
+        assert!(!knock);
+
+        let low = vec![
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::CLUBS, Value::THREE),
+            card(Suit::CLUBS, Value::FOUR),
+            card(Suit::HEARTS, Value::NINE),
+            card(Suit::SPADES, Value::NINE),
+            card(Suit::CLUBS, Value::NINE),
+            card(Suit::HEARTS, Value::FIVE),
+            card(Suit::HEARTS, Value::SIX),
+            card(Suit::HEARTS, Value::SEVEN),
+            card(Suit::DIAMONDS, Value::ACE),
+            card(Suit::DIAMONDS, Value::KING),
+        ];
+        let (discard, knock) = BasicBot.choose_discard(&low, None);
+        assert_eq!(discard, card(Suit::DIAMONDS, Value::KING));
+        assert!(knock);
+    }
+}
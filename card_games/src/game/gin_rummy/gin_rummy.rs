@@ -0,0 +1,550 @@
+use crate::{
+    cards::{deck_builder::DeckBuilder, Card, Deck},
+    game::gin_rummy::{
+        bot::{BasicBot, GinBot},
+        melds::{best_defence, best_discard, best_melds, suit_order},
+        types::{
+            DrawSource, GinError, GinState, HandResult, KnockOutcome, KnockResult, BOX_BONUS,
+            DEAD_STOCK, GAME_BONUS, GIN_BONUS, HAND_SIZE, HUMAN_SEAT, KNOCK_LIMIT, SEATS,
+            TARGET_SCORE, UNDERCUT_BONUS,
+        },
+        view::GinView,
+    },
+    player::Player,
+};
+
+pub struct GinRummy {
+    state: GinState,
+    players: Vec<Player>,
+    bot: Box<dyn GinBot>,
+
+    hand_number: usize,
+    dealer: usize,
+    scores: [u32; SEATS],
+    hands_won: [u32; SEATS],
+
+    stock: Deck,
+    discard_pile: Vec<Card>,
+    /// The card taken from the discard pile this turn, if any.
+    picked_up: Option<Card>,
+    last_result: Option<HandResult>,
+}
+
+impl Default for GinRummy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GinRummy {
+    /// Starts a game against a [`BasicBot`] opponent.
+    pub fn new() -> Self {
+        Self::with_opponent(Box::new(BasicBot))
+    }
+
+    /// Starts a game against the given opponent. The opponent deals first,
+    /// so the human has the first turn.
+    pub fn with_opponent(bot: Box<dyn GinBot>) -> Self {
+        let mut game = GinRummy {
+            state: GinState::HandOver,
+            players: vec![
+                Player::new("You".to_string()),
+                Player::new("CPU".to_string()),
+            ],
+            bot,
+
+            hand_number: 0,
+            dealer: 1 - HUMAN_SEAT,
+            scores: [0; SEATS],
+            hands_won: [0; SEATS],
+
+            stock: Deck::from_cards(Vec::new()),
+            discard_pile: Vec::new(),
+            picked_up: None,
+            last_result: None,
+        };
+        game.start_hand();
+        game
+    }
+
+    fn start_hand(&mut self) {
+        self.stock = DeckBuilder::new().standard52().build();
+        self.stock.shuffle();
+
+        for (seat, player) in self.players.iter_mut().enumerate() {
+            player.reset_hand();
+            player.set_dealer(seat == self.dealer);
+        }
+        self.stock
+            .deal(HAND_SIZE as u8, self.players.iter_mut())
+            .expect("a 52-card deck deals ten cards to two players");
+        for player in &mut self.players {
+            player.hand.sort_by_key(suit_order);
+        }
+
+        self.discard_pile = self.stock.draw().into_iter().collect();
+        self.picked_up = None;
+        self.last_result = None;
+        self.state = GinState::Drawing {
+            to_play: 1 - self.dealer,
+        };
+        self.run_bot();
+    }
+
+    /// Draws the human's card for this turn.
+    pub fn draw(&mut self, source: DrawSource) -> Result<(), GinError> {
+        match self.state {
+            GinState::Drawing { to_play } if to_play == HUMAN_SEAT => {}
+            GinState::Discarding { to_play } if to_play == HUMAN_SEAT => {
+                return Err(GinError::MustDiscard)
+            }
+            _ => return Err(GinError::NotYourTurn),
+        }
+
+        self.take_card(HUMAN_SEAT, source)?;
+        Ok(())
+    }
+
+    /// Discards a card and ends the human's turn.
+    pub fn discard(&mut self, card: Card) -> Result<(), GinError> {
+        self.check_discard(card)?;
+        self.finish_turn(HUMAN_SEAT, card, false);
+        self.run_bot();
+        Ok(())
+    }
+
+    /// Discards a card face down and knocks, ending the hand. The rest of
+    /// the hand may hold at most [`KNOCK_LIMIT`] deadwood.
+    pub fn knock(&mut self, card: Card) -> Result<(), GinError> {
+        self.check_discard(card)?;
+
+        let rest: Vec<Card> = self.players[HUMAN_SEAT]
+            .hand
+            .iter()
+            .filter(|c| **c != card)
+            .copied()
+            .collect();
+        if best_melds(&rest).deadwood_points() > KNOCK_LIMIT {
+            return Err(GinError::TooMuchDeadwood);
+        }
+
+        self.finish_turn(HUMAN_SEAT, card, true);
+        Ok(())
+    }
+
+    fn check_discard(&self, card: Card) -> Result<(), GinError> {
+        match self.state {
+            GinState::Discarding { to_play } if to_play == HUMAN_SEAT => {}
+            GinState::Drawing { to_play } if to_play == HUMAN_SEAT => {
+                return Err(GinError::MustDraw)
+            }
+            _ => return Err(GinError::NotYourTurn),
+        }
+        if !self.players[HUMAN_SEAT].hand.contains(&card) {
+            return Err(GinError::CardNotInHand);
+        }
+        if self.picked_up == Some(card) {
+            return Err(GinError::CannotDiscardPickup);
+        }
+        Ok(())
+    }
+
+    fn take_card(&mut self, seat: usize, source: DrawSource) -> Result<(), GinError> {
+        let card = match source {
+            DrawSource::Stock => self
+                .stock
+                .draw()
+                .expect("the hand ends before the stock runs out"),
+            DrawSource::Discard => {
+                let card = self.discard_pile.pop().ok_or(GinError::EmptyDiscardPile)?;
+                self.picked_up = Some(card);
+                card
+            }
+        };
+
+        let hand = &mut self.players[seat].hand;
+        hand.add(card);
+        hand.sort_by_key(suit_order);
+        self.state = GinState::Discarding { to_play: seat };
+        Ok(())
+    }
+
+    fn finish_turn(&mut self, seat: usize, card: Card, knock: bool) {
+        self.players[seat].hand.remove(&card);
+        self.discard_pile.push(card);
+        self.picked_up = None;
+
+        if knock {
+            self.settle(seat);
+        } else if self.stock.len() <= DEAD_STOCK {
+            self.last_result = Some(HandResult::Dead);
+            self.state = GinState::HandOver;
+        } else {
+            self.state = GinState::Drawing { to_play: 1 - seat };
+        }
+    }
+
+    fn run_bot(&mut self) {
+        loop {
+            match self.state {
+                GinState::Drawing { to_play } if to_play != HUMAN_SEAT => {
+                    let hand = self.players[to_play].hand.cards();
+                    let source = match self.bot.choose_draw(hand, self.discard_pile.last()) {
+                        DrawSource::Discard if !self.discard_pile.is_empty() => DrawSource::Discard,
+                        _ => DrawSource::Stock,
+                    };
+                    self.take_card(to_play, source)
+                        .expect("the chosen pile has a card");
+                }
+                GinState::Discarding { to_play } if to_play != HUMAN_SEAT => {
+                    let hand = self.players[to_play].hand.cards().to_vec();
+                    let picked_up = self.picked_up;
+                    let (choice, knock) = self.bot.choose_discard(&hand, picked_up.as_ref());
+
+                    // A misbehaving bot forfeits its choice to the best discard.
+                    let (card, arrangement) = if hand.contains(&choice) && picked_up != Some(choice)
+                    {
+                        let rest: Vec<Card> =
+                            hand.iter().filter(|c| **c != choice).copied().collect();
+                        (choice, best_melds(&rest))
+                    } else {
+                        best_discard(&hand, picked_up.as_ref()).expect("a discard")
+                    };
+                    let knock = knock && arrangement.deadwood_points() <= KNOCK_LIMIT;
+                    self.finish_turn(to_play, card, knock);
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Scores a knock: the defender lays off (unless the knocker went gin),
+    /// then the lower deadwood wins the difference.
+    fn settle(&mut self, knocker: usize) {
+        let defender = 1 - knocker;
+        let knocker_melds = best_melds(self.players[knocker].hand.cards());
+        let knocker_deadwood = knocker_melds.deadwood_points();
+
+        let (defender_melds, laid_off) = if knocker_deadwood == 0 {
+            (best_melds(self.players[defender].hand.cards()), Vec::new())
+        } else {
+            best_defence(self.players[defender].hand.cards(), &knocker_melds.melds)
+        };
+        let defender_deadwood = defender_melds.deadwood_points();
+
+        let (outcome, winner, points) = if knocker_deadwood == 0 {
+            (KnockOutcome::Gin, knocker, GIN_BONUS + defender_deadwood)
+        } else if defender_deadwood <= knocker_deadwood {
+            (
+                KnockOutcome::Undercut,
+                defender,
+                UNDERCUT_BONUS + knocker_deadwood - defender_deadwood,
+            )
+        } else {
+            (
+                KnockOutcome::Knock,
+                knocker,
+                defender_deadwood - knocker_deadwood,
+            )
+        };
+
+        self.scores[winner] += points;
+        self.hands_won[winner] += 1;
+        self.last_result = Some(HandResult::Knocked(KnockResult {
+            knocker,
+            outcome,
+            winner,
+            points,
+            knocker_melds,
+            defender_melds,
+            laid_off,
+        }));
+
+        if self.scores[winner] >= TARGET_SCORE {
+            self.scores[winner] += GAME_BONUS;
+            for seat in 0..SEATS {
+                self.scores[seat] += BOX_BONUS * self.hands_won[seat];
+            }
+            self.state = GinState::GameOver { winner };
+        } else {
+            self.state = GinState::HandOver;
+        }
+    }
+
+    /// Starts the next hand once the current one is scored. The winner of a
+    /// hand deals the next; after a dead hand the same dealer deals again.
+    pub fn next_hand(&mut self) -> Result<(), GinError> {
+        if self.state != GinState::HandOver {
+            return Err(GinError::HandNotOver);
+        }
+        if let Some(HandResult::Knocked(result)) = &self.last_result {
+            self.dealer = result.winner;
+        }
+        self.hand_number += 1;
+        self.start_hand();
+        Ok(())
+    }
+
+    pub fn view(&self) -> GinView {
+        let hand = self.players[HUMAN_SEAT].hand.cards().to_vec();
+        let opponent = 1 - HUMAN_SEAT;
+
+        let can_knock = match self.state {
+            GinState::Discarding { to_play } if to_play == HUMAN_SEAT => {
+                best_discard(&hand, self.picked_up.as_ref())
+                    .is_some_and(|(_, a)| a.deadwood_points() <= KNOCK_LIMIT)
+            }
+            _ => false,
+        };
+        let hand_over = matches!(self.state, GinState::HandOver | GinState::GameOver { .. });
+
+        GinView {
+            phase: self.state,
+            hand_number: self.hand_number,
+            dealer: self.dealer,
+
+            player_names: self.players.iter().map(|p| p.name().to_string()).collect(),
+            scores: self.scores,
+            hands_won: self.hands_won,
+
+            melds: best_melds(&hand),
+            hand,
+            can_knock,
+
+            upcard: self.discard_pile.last().copied(),
+            stock_size: self.stock.len(),
+            opponent_card_count: self.players[opponent].hand.len(),
+
+            last_result: self.last_result.clone(),
+            opponent_hand: hand_over.then(|| self.players[opponent].hand.cards().to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Suit, Value};
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    fn set_hand(game: &mut GinRummy, seat: usize, cards: &[Card]) {
+        game.players[seat].reset_hand();
+        for card in cards {
+            game.players[seat].hand.add(*card);
+        }
+    }
+
+    /// 2-3-4♣, 9♥ 9♠ 9♣ and 5-6-7♥ melded, plus the given deadwood.
+    fn melded_with(deadwood: &[Card]) -> Vec<Card> {
+        let mut cards = vec![
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::CLUBS, Value::THREE),
+            card(Suit::CLUBS, Value::FOUR),
+            card(Suit::HEARTS, Value::NINE),
+            card(Suit::SPADES, Value::NINE),
+            card(Suit::CLUBS, Value::NINE),
+            card(Suit::HEARTS, Value::FIVE),
+            card(Suit::HEARTS, Value::SIX),
+            card(Suit::HEARTS, Value::SEVEN),
+        ];
+        cards.extend_from_slice(deadwood);
+        cards
+    }
+
+    fn result(game: &GinRummy) -> KnockResult {
+        match &game.last_result {
+            Some(HandResult::Knocked(result)) => result.clone(),
+            other => panic!("expected a knock, got {:?}", other),
+        }
+    }
+
+    fn play_out_game(game: &mut GinRummy) {
+        let mut bot = BasicBot;
+        loop {
+            match game.state {
+                GinState::Drawing { .. } => {
+                    let view = game.view();
+                    let source = bot.choose_draw(&view.hand, view.upcard.as_ref());
+                    game.draw(source).unwrap();
+                }
+                GinState::Discarding { .. } => {
+                    let hand = game.view().hand;
+                    let (card, knock) = bot.choose_discard(&hand, game.picked_up.as_ref());
+                    if knock {
+                        game.knock(card).unwrap();
+                    } else {
+                        game.discard(card).unwrap();
+                    }
+                }
+                GinState::HandOver => game.next_hand().unwrap(),
+                GinState::GameOver { .. } => break,
+            }
+        }
+    }
+
+    #[test]
+    fn new_game_deals_ten_and_turns_an_upcard() {
+        let game = GinRummy::new();
+        assert_eq!(
+            game.state,
+            GinState::Drawing {
+                to_play: HUMAN_SEAT
+            }
+        );
+        for player in &game.players {
+            assert_eq!(player.hand.len(), HAND_SIZE);
+        }
+        assert_eq!(game.discard_pile.len(), 1);
+        assert_eq!(game.stock.len(), 31);
+    }
+
+    #[test]
+    fn turn_order_is_enforced() {
+        let mut game = GinRummy::new();
+        let card = game.view().hand[0];
+        assert_eq!(game.discard(card), Err(GinError::MustDraw));
+
+        game.draw(DrawSource::Discard).unwrap();
+        assert_eq!(game.draw(DrawSource::Stock), Err(GinError::MustDiscard));
+
+        let picked_up = game.picked_up.unwrap();
+        assert_eq!(game.discard(picked_up), Err(GinError::CannotDiscardPickup));
+
+        let other = *game.view().hand.iter().find(|c| **c != picked_up).unwrap();
+        game.discard(other).unwrap();
+        // The bot has taken its turn and it is ours again.
+        assert!(matches!(
+            game.state,
+            GinState::Drawing {
+                to_play: HUMAN_SEAT
+            } | GinState::HandOver
+        ));
+    }
+
+    #[test]
+    fn knock_requires_low_deadwood() {
+        let mut game = GinRummy::new();
+        game.draw(DrawSource::Stock).unwrap();
+        set_hand(
+            &mut game,
+            HUMAN_SEAT,
+            &melded_with(&[
+                card(Suit::DIAMONDS, Value::KING),
+                card(Suit::SPADES, Value::QUEEN),
+            ]),
+        );
+
+        assert_eq!(
+            game.knock(card(Suit::CLUBS, Value::TWO)),
+            Err(GinError::TooMuchDeadwood)
+        );
+        assert!(game.view().can_knock);
+        game.knock(card(Suit::DIAMONDS, Value::KING)).unwrap();
+
+        let result = result(&game);
+        assert_eq!(result.knocker, HUMAN_SEAT);
+        assert_eq!(result.knocker_melds.deadwood_points(), 10);
+    }
+
+    #[test]
+    fn gin_scores_bonus_and_blocks_layoffs() {
+        let mut game = GinRummy::new();
+        set_hand(
+            &mut game,
+            HUMAN_SEAT,
+            &melded_with(&[card(Suit::HEARTS, Value::EIGHT)]),
+        );
+        // The defender's 4♥ would lay off on 5-6-7-8♥, but not against gin.
+        set_hand(
+            &mut game,
+            1,
+            &[
+                card(Suit::HEARTS, Value::FOUR),
+                card(Suit::DIAMONDS, Value::KING),
+            ],
+        );
+
+        game.settle(HUMAN_SEAT);
+        let result = result(&game);
+        assert_eq!(result.outcome, KnockOutcome::Gin);
+        assert!(result.laid_off.is_empty());
+        assert_eq!(result.points, GIN_BONUS + 14);
+        assert_eq!(game.scores, [GIN_BONUS + 14, 0]);
+    }
+
+    #[test]
+    fn knock_scores_difference_after_layoffs() {
+        let mut game = GinRummy::new();
+        set_hand(
+            &mut game,
+            HUMAN_SEAT,
+            &melded_with(&[card(Suit::DIAMONDS, Value::FOUR)]),
+        );
+        set_hand(
+            &mut game,
+            1,
+            &[
+                card(Suit::HEARTS, Value::EIGHT),
+                card(Suit::DIAMONDS, Value::KING),
+            ],
+        );
+
+        game.settle(HUMAN_SEAT);
+        let result = result(&game);
+        assert_eq!(result.outcome, KnockOutcome::Knock);
+        assert_eq!(result.laid_off, vec![card(Suit::HEARTS, Value::EIGHT)]);
+        assert_eq!(result.points, 10 - 4);
+        assert_eq!(game.hands_won, [1, 0]);
+    }
+
+    #[test]
+    fn defender_undercuts_with_equal_deadwood() {
+        let mut game = GinRummy::new();
+        set_hand(
+            &mut game,
+            HUMAN_SEAT,
+            &melded_with(&[card(Suit::DIAMONDS, Value::SEVEN)]),
+        );
+        set_hand(&mut game, 1, &[card(Suit::SPADES, Value::SEVEN)]);
+
+        game.settle(HUMAN_SEAT);
+        let result = result(&game);
+        assert_eq!(result.outcome, KnockOutcome::Undercut);
+        assert_eq!(result.winner, 1);
+        assert_eq!(result.points, UNDERCUT_BONUS);
+
+        game.next_hand().unwrap();
+        assert_eq!(game.dealer, 1);
+    }
+
+    #[test]
+    fn hand_is_dead_when_stock_runs_low() {
+        let mut game = GinRummy::new();
+        game.stock = Deck::from_cards(vec![
+            card(Suit::SPADES, Value::TWO),
+            card(Suit::SPADES, Value::THREE),
+            card(Suit::SPADES, Value::FOUR),
+        ]);
+        game.draw(DrawSource::Stock).unwrap();
+        let card = game.view().hand[0];
+        game.discard(card).unwrap();
+
+        assert_eq!(game.state, GinState::HandOver);
+        assert_eq!(game.last_result, Some(HandResult::Dead));
+        assert_eq!(game.scores, [0, 0]);
+    }
+
+    #[test]
+    fn full_game_reaches_target_with_bonuses() {
+        let mut game = GinRummy::new();
+        play_out_game(&mut game);
+
+        let GinState::GameOver { winner } = game.state else {
+            panic!("game should be over");
+        };
+        assert!(game.scores[winner] >= TARGET_SCORE + GAME_BONUS);
+    }
+}
@@ -0,0 +1,652 @@
+//! Meld finding and deadwood minimisation for Gin Rummy.
+//!
+//! A meld is either a set (three or four cards of one rank) or a run (three
+//! or more consecutive cards of one suit, aces low). [`best_melds`] searches
+//! every way of splitting a hand into disjoint melds and returns the one that
+//! leaves the least deadwood.
+
+use std::collections::HashMap;
+
+use crate::cards::{Card, Suit};
+
+/// Suit order used when sorting hands and runs.
+pub const SUIT_ORDER: [Suit; 4] = [Suit::CLUBS, Suit::DIAMONDS, Suit::HEARTS, Suit::SPADES];
+
+/// Minimum number of cards in a meld.
+pub const MIN_MELD: usize = 3;
+
+/// Returns the deadwood value of a card: aces 1, face cards 10.
+pub fn deadwood_value(card: &Card) -> u32 {
+    card.rank().map(|rank| rank.min(10) as u32).unwrap_or(0)
+}
+
+/// Returns the total deadwood value of a set of cards.
+pub fn deadwood_points(cards: &[Card]) -> u32 {
+    cards.iter().map(deadwood_value).sum()
+}
+
+/// Sort key placing cards by suit, then by rank.
+pub fn suit_order(card: &Card) -> (usize, u8) {
+    let suit = SUIT_ORDER
+        .iter()
+        .position(|s| s == card.suit())
+        .unwrap_or(SUIT_ORDER.len());
+    (suit, card.rank().unwrap_or(0))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeldKind {
+    Set,
+    Run,
+}
+
+/// A set or run. Run cards are kept in rank order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meld {
+    pub kind: MeldKind,
+    pub cards: Vec<Card>,
+}
+
+impl Meld {
+    /// Returns `true` if `card` could be laid off on this meld.
+    pub fn accepts(&self, card: &Card) -> bool {
+        let Some(rank) = card.rank() else {
+            return false;
+        };
+        match self.kind {
+            MeldKind::Set => self.cards.len() < 4 && self.cards[0].rank() == Some(rank),
+            MeldKind::Run => {
+                let first = self.cards[0].rank().unwrap_or(0);
+                let last = self.cards[self.cards.len() - 1].rank().unwrap_or(0);
+                card.suit() == self.cards[0].suit() && (rank + 1 == first || rank == last + 1)
+            }
+        }
+    }
+
+    /// Adds a card accepted by [`Meld::accepts`], keeping runs in order.
+    fn extend(&mut self, card: Card) {
+        self.cards.push(card);
+        if self.kind == MeldKind::Run {
+            self.cards.sort_by_key(|c| c.rank());
+        }
+    }
+}
+
+/// A hand split into melds and leftover deadwood.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Arrangement {
+    pub melds: Vec<Meld>,
+    pub deadwood: Vec<Card>,
+}
+
+impl Arrangement {
+    pub fn deadwood_points(&self) -> u32 {
+        deadwood_points(&self.deadwood)
+    }
+
+    /// Returns `true` if `card` is part of a meld.
+    pub fn is_melded(&self, card: &Card) -> bool {
+        self.melds.iter().any(|m| m.cards.contains(card))
+    }
+}
+
+/// A set of hand indices, one bit per card.
+type Mask = u64;
+
+/// The most cards [`best_melds`] can arrange.
+pub const MAX_HAND: usize = Mask::BITS as usize;
+
+/// The most cards [`best_defence`] can arrange. What can be laid off depends
+/// on all of the deadwood at once, so its search tries every arrangement
+/// instead of sharing work between them the way [`best_melds`] does.
+pub const MAX_DEFENCE: usize = 16;
+
+/// The longest run offered as a candidate. Any longer run splits into runs
+/// of three to five, so searching those alone finds the same deadwood and
+/// keeps the search from looking far ahead; [`build_arrangement`] joins the
+/// pieces back up.
+const LONGEST_RUN: usize = 5;
+
+/// Candidate melds as bitmasks over hand indices.
+fn candidate_melds(hand: &[Card]) -> Vec<(MeldKind, Mask)> {
+    let mut candidates = Vec::new();
+
+    for rank in 1..=13u8 {
+        let indices: Vec<usize> = (0..hand.len())
+            .filter(|&i| hand[i].rank() == Some(rank))
+            .collect();
+        if indices.len() < MIN_MELD {
+            continue;
+        }
+        let all = indices.iter().fold(0, |mask: Mask, i| mask | 1 << i);
+        candidates.push((MeldKind::Set, all));
+        if indices.len() == 4 {
+            for i in &indices {
+                candidates.push((MeldKind::Set, all & !(1 << i)));
+            }
+        }
+    }
+
+    for suit in &SUIT_ORDER {
+        let mut by_rank: Vec<(u8, usize)> = (0..hand.len())
+            .filter(|&i| hand[i].suit() == suit)
+            .filter_map(|i| hand[i].rank().map(|r| (r, i)))
+            .collect();
+        by_rank.sort_unstable();
+
+        for start in 0..by_rank.len() {
+            let mut mask: Mask = 1 << by_rank[start].1;
+            for end in start + 1..by_rank.len().min(start + LONGEST_RUN) {
+                if by_rank[end].0 != by_rank[end - 1].0 + 1 {
+                    break;
+                }
+                mask |= 1 << by_rank[end].1;
+                if end + 1 - start >= MIN_MELD {
+                    candidates.push((MeldKind::Run, mask));
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// The least deadwood left by the cards not yet `decided`, working from the
+/// lowest such card. Deadwood adds up card by card, so the answer depends
+/// only on `decided` and is worked out once for each. `memo` also keeps the
+/// meld chosen for the lowest card, if any.
+fn least_deadwood(
+    hand: &[Card],
+    candidates: &[(MeldKind, Mask)],
+    decided: Mask,
+    memo: &mut HashMap<Mask, (u32, Option<usize>)>,
+) -> u32 {
+    let next = (!decided).trailing_zeros() as usize;
+    if next >= hand.len() {
+        return 0;
+    }
+    if let Some(&(points, _)) = memo.get(&decided) {
+        return points;
+    }
+
+    // The card stays out of any meld...
+    let mut best = (
+        deadwood_value(&hand[next]) + least_deadwood(hand, candidates, decided | 1 << next, memo),
+        None,
+    );
+    // ...or joins a meld that is still free.
+    for (i, &(_, mask)) in candidates.iter().enumerate() {
+        if mask & (1 << next) != 0 && mask & decided == 0 {
+            let points = least_deadwood(hand, candidates, decided | mask, memo);
+            if points < best.0 {
+                best = (points, Some(i));
+            }
+        }
+    }
+
+    memo.insert(decided, best);
+    best.0
+}
+
+/// Enumerates every choice of disjoint melds, keeping the one whose
+/// leftover cards `cost` the least.
+fn search<F>(hand: &[Card], cost: &F) -> Vec<(MeldKind, Mask)>
+where
+    F: Fn(&[Card]) -> u32,
+{
+    fn recurse<F>(
+        hand: &[Card],
+        candidates: &[(MeldKind, Mask)],
+        decided: Mask,
+        used: Mask,
+        chosen: &mut Vec<(MeldKind, Mask)>,
+        best: &mut (u32, Vec<(MeldKind, Mask)>),
+        cost: &F,
+    ) where
+        F: Fn(&[Card]) -> u32,
+    {
+        let Some(next) = (0..hand.len()).find(|i| decided & (1 << i) == 0) else {
+            let deadwood: Vec<Card> = (0..hand.len())
+                .filter(|i| used & (1 << i) == 0)
+                .map(|i| hand[i])
+                .collect();
+            let points = cost(&deadwood);
+            if points < best.0 {
+                *best = (points, chosen.clone());
+            }
+            return;
+        };
+
+        // The card stays out of any meld...
+        recurse(
+            hand,
+            candidates,
+            decided | 1 << next,
+            used,
+            chosen,
+            best,
+            cost,
+        );
+
+        // ...or joins a meld that is still free.
+        for &(kind, mask) in candidates {
+            if mask & (1 << next) != 0 && mask & decided == 0 {
+                chosen.push((kind, mask));
+                recurse(
+                    hand,
+                    candidates,
+                    decided | mask,
+                    used | mask,
+                    chosen,
+                    best,
+                    cost,
+                );
+                chosen.pop();
+            }
+        }
+    }
+
+    assert!(
+        hand.len() <= MAX_DEFENCE,
+        "cannot arrange more than {} cards",
+        MAX_DEFENCE
+    );
+    let candidates = candidate_melds(hand);
+    let mut best = (u32::MAX, Vec::new());
+    recurse(hand, &candidates, 0, 0, &mut Vec::new(), &mut best, cost);
+    best.1
+}
+
+fn build_arrangement(hand: &[Card], chosen: &[(MeldKind, Mask)]) -> Arrangement {
+    let mut used: Mask = 0;
+    let mut melds: Vec<Meld> = chosen
+        .iter()
+        .map(|&(kind, mask)| {
+            used |= mask;
+            let mut cards: Vec<Card> = (0..hand.len())
+                .filter(|i| mask & (1 << i) != 0)
+                .map(|i| hand[i])
+                .collect();
+            cards.sort_by_key(suit_order);
+            Meld { kind, cards }
+        })
+        .collect();
+    melds.sort_by_key(|m| suit_order(&m.cards[0]));
+    // Runs were searched in short pieces; put each long run back together.
+    melds.dedup_by(|next, run| {
+        let joins = run.kind == MeldKind::Run
+            && next.kind == MeldKind::Run
+            && next.cards[0].suit() == run.cards[0].suit()
+            && next.cards[0].rank() == run.cards[run.cards.len() - 1].rank().map(|r| r + 1);
+        if joins {
+            run.cards.append(&mut next.cards);
+        }
+        joins
+    });
+
+    let mut deadwood: Vec<Card> = (0..hand.len())
+        .filter(|i| used & (1 << i) == 0)
+        .map(|i| hand[i])
+        .collect();
+    deadwood.sort_by_key(suit_order);
+
+    Arrangement { melds, deadwood }
+}
+
+/// Splits a hand into the melds that leave the least deadwood.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{Card, Suit, Value};
+/// use card_games::game::gin_rummy::best_melds;
+/// let hand = vec![
+///     Card::new(Suit::HEARTS, Value::FOUR),
+///     Card::new(Suit::HEARTS, Value::FIVE),
+///     Card::new(Suit::HEARTS, Value::SIX),
+///     Card::new(Suit::CLUBS, Value::SIX),
+///     Card::new(Suit::SPADES, Value::SIX),
+///     Card::new(Suit::DIAMONDS, Value::KING),
+/// ];
+/// // The 6♥ fits the run or the set of sixes, not both. Melding the sixes
+/// // leaves 4♥, 5♥ and the king.
+/// let arrangement = best_melds(&hand);
+/// assert_eq!(arrangement.deadwood_points(), 19);
+/// ```
+///
+/// # Panics
+/// Panics if the hand holds more than [`MAX_HAND`] cards.
+pub fn best_melds(hand: &[Card]) -> Arrangement {
+    assert!(
+        hand.len() <= MAX_HAND,
+        "cannot arrange more than {} cards",
+        MAX_HAND
+    );
+    // Working through the cards by rank keeps the melds that start at the
+    // lowest undecided card close to it, so few cases need remembering.
+    let mut hand = hand.to_vec();
+    hand.sort_by_key(|card| (card.rank(), suit_order(card)));
+    let hand = &hand[..];
+
+    let candidates = candidate_melds(hand);
+    let mut memo = HashMap::new();
+    least_deadwood(hand, &candidates, 0, &mut memo);
+
+    // Follow the choices made for each lowest undecided card.
+    let mut chosen = Vec::new();
+    let mut decided: Mask = 0;
+    while let Some(&(_, choice)) = memo.get(&decided) {
+        match choice {
+            Some(i) => {
+                chosen.push(candidates[i]);
+                decided |= candidates[i].1;
+            }
+            None => decided |= 1 << (!decided).trailing_zeros(),
+        }
+    }
+    build_arrangement(hand, &chosen)
+}
+
+/// Chooses the discard from an eleven-card hand that leaves the least
+/// deadwood. `keep` is a card that may not be discarded, such as one just
+/// taken from the discard pile.
+pub fn best_discard(hand: &[Card], keep: Option<&Card>) -> Option<(Card, Arrangement)> {
+    hand.iter()
+        .filter(|c| Some(*c) != keep)
+        .map(|discard| {
+            let rest: Vec<Card> = hand.iter().filter(|c| *c != discard).copied().collect();
+            (*discard, best_melds(&rest))
+        })
+        // Prefer shedding the higher card when deadwood ties.
+        .min_by_key(|(discard, arrangement)| {
+            (
+                arrangement.deadwood_points(),
+                std::cmp::Reverse(deadwood_value(discard)),
+            )
+        })
+}
+
+/// Lays off `cards` onto `melds` so that the least deadwood is left over,
+/// returning the cards laid off and those left over.
+///
+/// A card may fit more than one meld, and laying it on one can shut out
+/// another card, so every choice of meld for each card is tried.
+pub fn lay_off(cards: &[Card], melds: &[Meld]) -> (Vec<Card>, Vec<Card>) {
+    fn recurse(
+        cards: &[Card],
+        melds: &[Meld],
+        targets: &mut Vec<Option<usize>>,
+        best: &mut (u32, Vec<Option<usize>>),
+    ) {
+        let Some(card) = cards.get(targets.len()) else {
+            let left: u32 = cards
+                .iter()
+                .zip(targets.iter())
+                .filter(|(_, target)| target.is_none())
+                .map(|(card, _)| deadwood_value(card))
+                .sum();
+            if left < best.0 && fits(cards, melds, targets) {
+                *best = (left, targets.clone());
+            }
+            return;
+        };
+
+        for target in std::iter::once(None).chain((0..melds.len()).map(Some)) {
+            if target.is_some_and(|m| !could_extend(&melds[m], card)) {
+                continue;
+            }
+            targets.push(target);
+            recurse(cards, melds, targets, best);
+            targets.pop();
+        }
+    }
+
+    let mut best = (u32::MAX, Vec::new());
+    recurse(cards, melds, &mut Vec::new(), &mut best);
+
+    let (laid_off, remaining) = cards
+        .iter()
+        .zip(best.1)
+        .partition::<Vec<_>, _>(|(_, target)| target.is_some());
+    (
+        laid_off.into_iter().map(|(card, _)| *card).collect(),
+        remaining.into_iter().map(|(card, _)| *card).collect(),
+    )
+}
+
+/// Whether `card` could end up on `meld` once other cards are laid off: a
+/// set of its rank, or a run of its suit that does not already hold it.
+fn could_extend(meld: &Meld, card: &Card) -> bool {
+    let Some(rank) = card.rank() else {
+        return false;
+    };
+    match meld.kind {
+        MeldKind::Set => meld.cards[0].rank() == Some(rank),
+        MeldKind::Run => {
+            card.suit() == meld.cards[0].suit() && meld.cards.iter().all(|c| c.rank() != Some(rank))
+        }
+    }
+}
+
+/// Whether every card can be laid off on the meld it is given, in some
+/// order.
+fn fits(cards: &[Card], melds: &[Meld], targets: &[Option<usize>]) -> bool {
+    melds.iter().enumerate().all(|(m, meld)| {
+        let mut meld = meld.clone();
+        let mut pending: Vec<Card> = cards
+            .iter()
+            .zip(targets)
+            .filter(|(_, target)| **target == Some(m))
+            .map(|(card, _)| *card)
+            .collect();
+        // A run grows one end at a time, so keep laying off whatever fits.
+        while let Some(i) = pending.iter().position(|card| meld.accepts(card)) {
+            meld.extend(pending.remove(i));
+        }
+        pending.is_empty()
+    })
+}
+
+/// Arranges the defending hand against a knock, choosing melds so that the
+/// most deadwood can be laid off on the knocker's melds.
+///
+/// Returns the arrangement, with laid-off cards removed from its deadwood,
+/// and the cards laid off.
+///
+/// # Panics
+/// Panics if the hand holds more than [`MAX_DEFENCE`] cards.
+pub fn best_defence(hand: &[Card], knocker_melds: &[Meld]) -> (Arrangement, Vec<Card>) {
+    let chosen = search(hand, &|deadwood| {
+        deadwood_points(&lay_off(deadwood, knocker_melds).1)
+    });
+    let mut arrangement = build_arrangement(hand, &chosen);
+    let (laid_off, remaining) = lay_off(&arrangement.deadwood, knocker_melds);
+    arrangement.deadwood = remaining;
+    (arrangement, laid_off)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Value;
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    #[test]
+    fn hands_longer_than_sixteen_cards_are_arranged() {
+        let hand: Vec<Card> = Value::standard_values()
+            .map(|value| card(Suit::CLUBS, value))
+            .chain(
+                [Value::ACE, Value::TWO, Value::THREE, Value::FOUR]
+                    .map(|value| card(Suit::DIAMONDS, value)),
+            )
+            .collect();
+        assert_eq!(hand.len(), 17);
+        assert_eq!(best_melds(&hand).deadwood_points(), 0);
+    }
+
+    #[test]
+    fn finds_gin_with_set_and_runs() {
+        let hand = vec![
+            card(Suit::CLUBS, Value::ACE),
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::CLUBS, Value::THREE),
+            card(Suit::CLUBS, Value::FOUR),
+            card(Suit::HEARTS, Value::SEVEN),
+            card(Suit::SPADES, Value::SEVEN),
+            card(Suit::DIAMONDS, Value::SEVEN),
+            card(Suit::SPADES, Value::JACK),
+            card(Suit::SPADES, Value::QUEEN),
+            card(Suit::SPADES, Value::KING),
+        ];
+        let arrangement = best_melds(&hand);
+        assert_eq!(arrangement.deadwood_points(), 0);
+        assert_eq!(arrangement.melds.len(), 3);
+    }
+
+    #[test]
+    fn chooses_the_cheaper_use_of_a_shared_card() {
+        // The 8♥ fits the 8s set or the 8-9-10♥ run, but not both. Using it
+        // in the run and melding the other three 8s leaves nothing.
+        let hand = vec![
+            card(Suit::HEARTS, Value::EIGHT),
+            card(Suit::HEARTS, Value::NINE),
+            card(Suit::HEARTS, Value::TEN),
+            card(Suit::CLUBS, Value::EIGHT),
+            card(Suit::SPADES, Value::EIGHT),
+            card(Suit::DIAMONDS, Value::EIGHT),
+        ];
+        let arrangement = best_melds(&hand);
+        assert_eq!(arrangement.deadwood_points(), 0);
+        assert!(arrangement
+            .melds
+            .iter()
+            .any(|m| m.kind == MeldKind::Run && m.cards.len() == 3));
+    }
+
+    #[test]
+    fn aces_are_low_only() {
+        let hand = vec![
+            card(Suit::CLUBS, Value::QUEEN),
+            card(Suit::CLUBS, Value::KING),
+            card(Suit::CLUBS, Value::ACE),
+        ];
+        assert_eq!(best_melds(&hand).deadwood_points(), 21);
+    }
+
+    #[test]
+    fn best_discard_respects_the_kept_card() {
+        let hand = vec![
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::CLUBS, Value::THREE),
+            card(Suit::CLUBS, Value::FOUR),
+            card(Suit::HEARTS, Value::KING),
+        ];
+        let keep = card(Suit::HEARTS, Value::KING);
+        let (discard, _) = best_discard(&hand, None).unwrap();
+        assert_eq!(discard, keep);
+
+        let (discard, arrangement) = best_discard(&hand, Some(&keep)).unwrap();
+        assert_ne!(discard, keep);
+        assert!(arrangement.deadwood.contains(&keep));
+    }
+
+    #[test]
+    fn lay_off_extends_runs_in_chains() {
+        let melds = vec![Meld {
+            kind: MeldKind::Run,
+            cards: vec![
+                card(Suit::SPADES, Value::FIVE),
+                card(Suit::SPADES, Value::SIX),
+                card(Suit::SPADES, Value::SEVEN),
+            ],
+        }];
+        let cards = vec![
+            card(Suit::SPADES, Value::NINE),
+            card(Suit::SPADES, Value::EIGHT),
+            card(Suit::HEARTS, Value::EIGHT),
+        ];
+        let (laid_off, remaining) = lay_off(&cards, &melds);
+        assert_eq!(laid_off.len(), 2);
+        assert_eq!(remaining, vec![card(Suit::HEARTS, Value::EIGHT)]);
+    }
+
+    #[test]
+    fn lay_off_picks_the_meld_that_frees_other_cards() {
+        // The 4♥ fits the fours or the run. Only on the run does it let the
+        // 3♥ follow.
+        let melds = vec![
+            Meld {
+                kind: MeldKind::Set,
+                cards: vec![
+                    card(Suit::CLUBS, Value::FOUR),
+                    card(Suit::DIAMONDS, Value::FOUR),
+                    card(Suit::SPADES, Value::FOUR),
+                ],
+            },
+            Meld {
+                kind: MeldKind::Run,
+                cards: vec![
+                    card(Suit::HEARTS, Value::FIVE),
+                    card(Suit::HEARTS, Value::SIX),
+                    card(Suit::HEARTS, Value::SEVEN),
+                ],
+            },
+        ];
+        let cards = vec![
+            card(Suit::HEARTS, Value::FOUR),
+            card(Suit::HEARTS, Value::THREE),
+        ];
+        let (laid_off, remaining) = lay_off(&cards, &melds);
+        assert_eq!(laid_off, cards);
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn long_runs_come_back_whole() {
+        let hand: Vec<Card> = Value::standard_values()
+            .take(8)
+            .map(|value| card(Suit::HEARTS, value))
+            .collect();
+        let arrangement = best_melds(&hand);
+        assert_eq!(arrangement.melds.len(), 1);
+        assert_eq!(arrangement.melds[0].cards, hand);
+    }
+
+    #[test]
+    fn a_whole_deck_is_arranged() {
+        let deck: Vec<Card> = SUIT_ORDER
+            .iter()
+            .flat_map(|suit| Value::standard_values().map(|value| card(*suit, value)))
+            .collect();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(best_melds(&deck).deadwood_points(), 0);
+    }
+
+    #[test]
+    fn defence_picks_melds_that_lay_off_best() {
+        // Melding 8-9-10♦ leaves the 8♣ and 8♠ (16). Melding the eights
+        // instead leaves 9♦ and 10♦ (19), but both lay off on J-Q-K♦.
+        let knocker = vec![Meld {
+            kind: MeldKind::Run,
+            cards: vec![
+                card(Suit::DIAMONDS, Value::JACK),
+                card(Suit::DIAMONDS, Value::QUEEN),
+                card(Suit::DIAMONDS, Value::KING),
+            ],
+        }];
+        let hand = vec![
+            card(Suit::DIAMONDS, Value::EIGHT),
+            card(Suit::DIAMONDS, Value::NINE),
+            card(Suit::DIAMONDS, Value::TEN),
+            card(Suit::CLUBS, Value::EIGHT),
+            card(Suit::SPADES, Value::EIGHT),
+        ];
+        assert_eq!(best_melds(&hand).deadwood_points(), 16);
+
+        let (arrangement, laid_off) = best_defence(&hand, &knocker);
+        assert_eq!(arrangement.deadwood_points(), 0);
+        assert_eq!(laid_off.len(), 2);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod bot;
+#[allow(clippy::module_inception)]
+pub mod gin_rummy;
+pub mod melds;
+pub mod types;
+pub mod view;
+
+pub use gin_rummy::GinRummy;
+pub use melds::{best_melds, Arrangement, Meld, MeldKind};
+pub use types::{DrawSource, GinError, GinState};
@@ -0,0 +1,143 @@
+use std::fmt::Display;
+
+use crate::cards::Card;
+
+use super::melds::Arrangement;
+
+/// Number of players in a game of Gin.
+pub const SEATS: usize = 2;
+
+/// Cards dealt to each player.
+pub const HAND_SIZE: usize = 10;
+
+/// A player may knock with at most this much deadwood.
+pub const KNOCK_LIMIT: u32 = 10;
+
+/// Bonus for going gin (no deadwood).
+pub const GIN_BONUS: u32 = 25;
+
+/// Bonus to the defender for undercutting a knock.
+pub const UNDERCUT_BONUS: u32 = 25;
+
+/// The game ends once a player reaches this score.
+pub const TARGET_SCORE: u32 = 100;
+
+/// Bonus to the player who reaches [`TARGET_SCORE`].
+pub const GAME_BONUS: u32 = 100;
+
+/// Bonus per hand won, added to both players at the end of the game.
+pub const BOX_BONUS: u32 = 25;
+
+/// The hand is void once the stock is down to this many cards.
+pub const DEAD_STOCK: usize = 2;
+
+/// The human player's seat; the other seat is played by a bot.
+pub const HUMAN_SEAT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GinState {
+    Drawing { to_play: usize },
+    Discarding { to_play: usize },
+    HandOver,
+    GameOver { winner: usize },
+}
+
+impl Display for GinState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GinState::Drawing { to_play } if *to_play == HUMAN_SEAT => {
+                write!(f, "Draw from the stock or the discard pile")
+            }
+            GinState::Discarding { to_play } if *to_play == HUMAN_SEAT => {
+                write!(f, "Discard or knock")
+            }
+            GinState::Drawing { .. } | GinState::Discarding { .. } => {
+                write!(f, "Waiting for opponent")
+            }
+            GinState::HandOver => write!(f, "Hand over"),
+            GinState::GameOver { winner } if *winner == HUMAN_SEAT => write!(f, "You win!"),
+            GinState::GameOver { .. } => write!(f, "Opponent wins"),
+        }
+    }
+}
+
+/// Where a player draws from at the start of a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawSource {
+    Stock,
+    Discard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnockOutcome {
+    /// The knocker had less deadwood and scores the difference.
+    Knock,
+    /// The knocker had no deadwood; the defender may not lay off.
+    Gin,
+    /// The defender matched or beat the knocker's deadwood.
+    Undercut,
+}
+
+impl Display for KnockOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KnockOutcome::Knock => write!(f, "Knock"),
+            KnockOutcome::Gin => write!(f, "Gin"),
+            KnockOutcome::Undercut => write!(f, "Undercut"),
+        }
+    }
+}
+
+/// How a knock was settled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnockResult {
+    pub knocker: usize,
+    pub outcome: KnockOutcome,
+    pub winner: usize,
+    pub points: u32,
+    pub knocker_melds: Arrangement,
+    /// The defender's melds, with laid-off cards removed from the deadwood.
+    pub defender_melds: Arrangement,
+    pub laid_off: Vec<Card>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HandResult {
+    Knocked(KnockResult),
+    /// The stock ran out before anyone knocked; nobody scores.
+    Dead,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GinError {
+    NotYourTurn,
+    MustDraw,
+    MustDiscard,
+    EmptyDiscardPile,
+    CardNotInHand,
+    /// The card just taken from the discard pile cannot be thrown back.
+    CannotDiscardPickup,
+    TooMuchDeadwood,
+    HandNotOver,
+}
+
+impl Display for GinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GinError::NotYourTurn => write!(f, "It is not your turn"),
+            GinError::MustDraw => write!(f, "Draw a card first"),
+            GinError::MustDiscard => write!(f, "You have already drawn; discard a card"),
+            GinError::EmptyDiscardPile => write!(f, "The discard pile is empty"),
+            GinError::CardNotInHand => write!(f, "That card is not in your hand"),
+            GinError::CannotDiscardPickup => {
+                write!(f, "You cannot discard the card you just picked up")
+            }
+            GinError::TooMuchDeadwood => {
+                write!(f, "You need {} or less deadwood to knock", KNOCK_LIMIT)
+            }
+            GinError::HandNotOver => write!(f, "The hand is still in progress"),
+        }
+    }
+}
+
+impl std::error::Error for GinError {}
@@ -0,0 +1,33 @@
+use crate::{
+    cards::Card,
+    game::gin_rummy::{
+        melds::Arrangement,
+        types::{GinState, HandResult, SEATS},
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct GinView {
+    pub phase: GinState,
+    pub hand_number: usize,
+    pub dealer: usize,
+
+    pub player_names: Vec<String>,
+    pub scores: [u32; SEATS],
+    pub hands_won: [u32; SEATS],
+
+    /// The human's hand, sorted by suit and rank.
+    pub hand: Vec<Card>,
+    /// The best melds in the human's hand, for highlighting.
+    pub melds: Arrangement,
+    /// Whether the human may knock now with some discard.
+    pub can_knock: bool,
+
+    pub upcard: Option<Card>,
+    pub stock_size: usize,
+    pub opponent_card_count: usize,
+
+    pub last_result: Option<HandResult>,
+    /// The opponent's hand, revealed once the hand is over.
+    pub opponent_hand: Option<Vec<Card>>,
+}
@@ -0,0 +1,182 @@
+//! Computer opponents for Hearts.
+//!
+//! Any type implementing [`TrickTakingBot`] can fill a seat. [`BasicBot`] is a
+//! simple rule-based player: it passes its most dangerous cards, ducks under
+//! the winning card when following suit, and dumps points when void.
+
+use crate::cards::{Card, Suit};
+
+use super::{
+    rules::{self, is_queen_of_spades, rank_ace_high},
+    types::{PassDirection, PlayedCard, SEATS},
+};
+
+/// Everything a bot may see when it is asked to play a card.
+#[derive(Debug, Clone, Copy)]
+pub struct TrickContext<'a> {
+    pub seat: usize,
+    pub hand: &'a [Card],
+    /// The cards the rules allow; the bot must return one of these.
+    pub legal: &'a [Card],
+    /// Cards already played to the current trick, in order.
+    pub trick: &'a [PlayedCard],
+    pub hearts_broken: bool,
+    /// Points each seat has taken so far this hand.
+    pub hand_points: &'a [u32; SEATS],
+}
+
+/// A computer player for trick-taking games.
+pub trait TrickTakingBot {
+    /// Chooses the cards to pass at the start of a hand.
+    fn choose_pass(&mut self, hand: &[Card], count: usize, direction: PassDirection) -> Vec<Card>;
+
+    /// Chooses a card from `context.legal` to play to the current trick.
+    fn choose_play(&mut self, context: &TrickContext) -> Card;
+}
+
+fn highest<'a>(cards: impl Iterator<Item = &'a Card>) -> Option<Card> {
+    cards.max_by_key(|c| rank_ace_high(c)).copied()
+}
+
+fn lowest<'a>(cards: impl Iterator<Item = &'a Card>) -> Option<Card> {
+    cards.min_by_key(|c| rank_ace_high(c)).copied()
+}
+
+/// A rule-based Hearts player.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BasicBot;
+
+impl BasicBot {
+    /// How much the bot wants to get rid of a card when passing.
+    fn danger(card: &Card) -> u32 {
+        let rank = rank_ace_high(card) as u32;
+        match *card.suit() {
+            _ if is_queen_of_spades(card) => 100,
+            Suit::SPADES if rank > 12 => 90 + rank,
+            Suit::HEARTS => 20 + rank,
+            _ => rank,
+        }
+    }
+}
+
+impl TrickTakingBot for BasicBot {
+    fn choose_pass(&mut self, hand: &[Card], count: usize, _: PassDirection) -> Vec<Card> {
+        let mut cards = hand.to_vec();
+        cards.sort_by_key(|c| std::cmp::Reverse(Self::danger(c)));
+        cards.truncate(count);
+        cards
+    }
+
+    fn choose_play(&mut self, context: &TrickContext) -> Card {
+        let legal = context.legal;
+        let lowest_legal = || lowest(legal.iter()).expect("a legal card");
+
+        let Some(led) = context.trick.first().map(|p| *p.card.suit()) else {
+            return lowest_legal();
+        };
+
+        if *legal[0].suit() == led {
+            let trick: Vec<Card> = context.trick.iter().map(|p| p.card).collect();
+            let winning = rules::trick_winner(&trick)
+                .map(|i| rank_ace_high(&trick[i]))
+                .unwrap_or(0);
+
+            // Play the highest card that still loses the trick.
+            if let Some(duck) = highest(legal.iter().filter(|c| rank_ace_high(c) < winning)) {
+                return duck;
+            }
+            // Forced to win: shed the highest card, keeping the queen back.
+            return highest(legal.iter().filter(|c| !is_queen_of_spades(c)))
+                .unwrap_or_else(lowest_legal);
+        }
+
+        // Void in the suit led: unload penalty cards first.
+        if let Some(queen) = legal.iter().find(|c| is_queen_of_spades(c)) {
+            return *queen;
+        }
+        highest(legal.iter().filter(|c| *c.suit() == Suit::HEARTS))
+            .or_else(|| highest(legal.iter()))
+            .expect("a legal card")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Value;
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    #[test]
+    fn passes_queen_and_high_spades_first() {
+        let hand = vec![
+            card(Suit::CLUBS, Value::TWO),
+            card(Suit::SPADES, Value::QUEEN),
+            card(Suit::HEARTS, Value::ACE),
+            card(Suit::SPADES, Value::ACE),
+            card(Suit::DIAMONDS, Value::KING),
+        ];
+        let pass = BasicBot.choose_pass(&hand, 3, PassDirection::Left);
+        assert_eq!(
+            pass,
+            vec![
+                card(Suit::SPADES, Value::ACE),
+                card(Suit::SPADES, Value::QUEEN),
+                card(Suit::HEARTS, Value::ACE),
+            ]
+        );
+    }
+
+    #[test]
+    fn ducks_under_the_winning_card() {
+        let legal = vec![
+            card(Suit::DIAMONDS, Value::THREE),
+            card(Suit::DIAMONDS, Value::NINE),
+            card(Suit::DIAMONDS, Value::KING),
+        ];
+        let trick = vec![PlayedCard {
+            seat: 0,
+            card: card(Suit::DIAMONDS, Value::TEN),
+        }];
+        let context = TrickContext {
+            seat: 1,
+            hand: &legal,
+            legal: &legal,
+            trick: &trick,
+            hearts_broken: false,
+            hand_points: &[0; SEATS],
+        };
+
+        assert_eq!(
+            BasicBot.choose_play(&context),
+            card(Suit::DIAMONDS, Value::NINE)
+        );
+    }
+
+    #[test]
+    fn dumps_queen_when_void() {
+        let legal = vec![
+            card(Suit::SPADES, Value::QUEEN),
+            card(Suit::HEARTS, Value::ACE),
+        ];
+        let trick = vec![PlayedCard {
+            seat: 0,
+            card: card(Suit::DIAMONDS, Value::TEN),
+        }];
+        let context = TrickContext {
+            seat: 1,
+            hand: &legal,
+            legal: &legal,
+            trick: &trick,
+            hearts_broken: false,
+            hand_points: &[0; SEATS],
+        };
+
+        assert_eq!(
+            BasicBot.choose_play(&context),
+            card(Suit::SPADES, Value::QUEEN)
+        );
+    }
+}
@@ -0,0 +1,577 @@
+use crate::{
+    cards::{deck_builder::DeckBuilder, Card, Suit},
+    game::hearts::{
+        bot::{BasicBot, TrickContext, TrickTakingBot},
+        rules,
+        types::{
+            CompletedTrick, HeartsError, HeartsState, PassDirection, PlayedCard, HUMAN_SEAT,
+            PASS_COUNT, SEATS, TARGET_SCORE,
+        },
+        view::HeartsView,
+    },
+    player::Player,
+};
+
+/// Suit order used when displaying a hand: alternating colours.
+pub const DISPLAY_SUITS: [Suit; 4] = [Suit::CLUBS, Suit::DIAMONDS, Suit::SPADES, Suit::HEARTS];
+
+fn display_order(card: &Card) -> (usize, u8) {
+    let suit = DISPLAY_SUITS
+        .iter()
+        .position(|s| s == card.suit())
+        .unwrap_or(DISPLAY_SUITS.len());
+    (suit, rules::rank_ace_high(card))
+}
+
+pub struct Hearts {
+    state: HeartsState,
+    players: Vec<Player>,
+    /// Bots for every seat except [`HUMAN_SEAT`], indexed by seat.
+    bots: Vec<Option<Box<dyn TrickTakingBot>>>,
+
+    hand_number: usize,
+    scores: [u32; SEATS],
+    hand_points: [u32; SEATS],
+
+    trick: Vec<PlayedCard>,
+    tricks_played: usize,
+    hearts_broken: bool,
+    last_trick: Option<CompletedTrick>,
+    moon_shooter: Option<usize>,
+}
+
+impl Default for Hearts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hearts {
+    /// Starts a game against three [`BasicBot`] opponents.
+    pub fn new() -> Self {
+        Self::with_opponents([Box::new(BasicBot), Box::new(BasicBot), Box::new(BasicBot)])
+    }
+
+    /// Starts a game against the given opponents, seated to the human's left
+    /// in order.
+    pub fn with_opponents(opponents: [Box<dyn TrickTakingBot>; SEATS - 1]) -> Self {
+        let mut players = vec![Player::new("You".to_string())];
+        let mut bots: Vec<Option<Box<dyn TrickTakingBot>>> = vec![None];
+
+        for (i, bot) in opponents.into_iter().enumerate() {
+            players.push(Player::new(format!("CPU {}", i + 1)));
+            bots.push(Some(bot));
+        }
+
+        let mut game = Hearts {
+            state: HeartsState::Passing,
+            players,
+            bots,
+
+            hand_number: 0,
+            scores: [0; SEATS],
+            hand_points: [0; SEATS],
+
+            trick: Vec::new(),
+            tricks_played: 0,
+            hearts_broken: false,
+            last_trick: None,
+            moon_shooter: None,
+        };
+        game.start_hand();
+        game
+    }
+
+    pub fn pass_direction(&self) -> PassDirection {
+        PassDirection::for_hand(self.hand_number)
+    }
+
+    fn start_hand(&mut self) {
+        let mut deck = DeckBuilder::new().standard52().build();
+        deck.shuffle();
+
+        for player in &mut self.players {
+            player.reset_hand();
+        }
+        deck.deal(13, self.players.iter_mut())
+            .expect("a 52-card deck deals 13 cards to four players");
+
+        self.begin_hand();
+    }
+
+    fn begin_hand(&mut self) {
+        for player in &mut self.players {
+            player.hand.sort_by_key(display_order);
+        }
+
+        self.hand_points = [0; SEATS];
+        self.trick.clear();
+        self.tricks_played = 0;
+        self.hearts_broken = false;
+        self.last_trick = None;
+        self.moon_shooter = None;
+
+        if self.pass_direction() == PassDirection::Hold {
+            self.begin_play();
+        } else {
+            self.state = HeartsState::Passing;
+        }
+    }
+
+    /// Passes three cards from the human's hand; the bots pass at the same
+    /// time and play begins.
+    pub fn pass(&mut self, cards: &[Card]) -> Result<(), HeartsError> {
+        if self.state != HeartsState::Passing {
+            return Err(HeartsError::NotPassing);
+        }
+        if cards.len() != PASS_COUNT {
+            return Err(HeartsError::WrongPassCount);
+        }
+
+        let mut remaining = self.players[HUMAN_SEAT].hand.clone();
+        if !cards.iter().all(|c| remaining.remove(c).is_some()) {
+            return Err(HeartsError::CardNotInHand);
+        }
+
+        let direction = self.pass_direction();
+        let mut passes = vec![cards.to_vec()];
+        for seat in 1..SEATS {
+            let hand = self.players[seat].hand.cards().to_vec();
+            let bot = self.bots[seat].as_mut().expect("bot seat");
+            let pass = bot.choose_pass(&hand, PASS_COUNT, direction);
+
+            // A misbehaving bot forfeits its choice to the basic bot's pass.
+            let mut remaining = self.players[seat].hand.clone();
+            let legal =
+                pass.len() == PASS_COUNT && pass.iter().all(|c| remaining.remove(c).is_some());
+            passes.push(if legal {
+                pass
+            } else {
+                BasicBot.choose_pass(&hand, PASS_COUNT, direction)
+            });
+        }
+
+        for (seat, pass) in passes.iter().enumerate() {
+            for card in pass {
+                self.players[seat].hand.remove(card);
+            }
+        }
+        for (seat, pass) in passes.into_iter().enumerate() {
+            let target = direction.target(seat);
+            for card in pass {
+                self.players[target].hand.add(card);
+            }
+        }
+        for player in &mut self.players {
+            player.hand.sort_by_key(display_order);
+        }
+
+        self.begin_play();
+        Ok(())
+    }
+
+    fn begin_play(&mut self) {
+        let leader = self
+            .players
+            .iter()
+            .position(|p| p.hand.iter().any(rules::is_two_of_clubs))
+            .unwrap_or(0);
+
+        self.state = HeartsState::Playing { to_play: leader };
+        self.run_bots();
+    }
+
+    /// Plays a card from the human's hand.
+    pub fn play(&mut self, card: Card) -> Result<(), HeartsError> {
+        match self.state {
+            HeartsState::Playing { to_play } if to_play == HUMAN_SEAT => {}
+            _ => return Err(HeartsError::NotYourTurn),
+        }
+        if !self.players[HUMAN_SEAT].hand.contains(&card) {
+            return Err(HeartsError::CardNotInHand);
+        }
+        if !self.legal_plays(HUMAN_SEAT).contains(&card) {
+            return Err(HeartsError::IllegalPlay);
+        }
+
+        self.play_card(HUMAN_SEAT, card);
+        self.run_bots();
+        Ok(())
+    }
+
+    /// Starts the next hand once the current one is scored.
+    pub fn next_hand(&mut self) -> Result<(), HeartsError> {
+        if self.state != HeartsState::HandOver {
+            return Err(HeartsError::HandNotOver);
+        }
+        self.hand_number += 1;
+        self.start_hand();
+        Ok(())
+    }
+
+    fn legal_plays(&self, seat: usize) -> Vec<Card> {
+        let trick: Vec<Card> = self.trick.iter().map(|p| p.card).collect();
+        rules::legal_plays(
+            self.players[seat].hand.cards(),
+            &trick,
+            self.hearts_broken,
+            self.tricks_played == 0,
+        )
+    }
+
+    fn run_bots(&mut self) {
+        while let HeartsState::Playing { to_play } = self.state {
+            if self.bots[to_play].is_none() {
+                break;
+            }
+
+            let legal = self.legal_plays(to_play);
+            let context = TrickContext {
+                seat: to_play,
+                hand: self.players[to_play].hand.cards(),
+                legal: &legal,
+                trick: &self.trick,
+                hearts_broken: self.hearts_broken,
+                hand_points: &self.hand_points,
+            };
+            let bot = self.bots[to_play].as_mut().expect("bot seat");
+            let choice = bot.choose_play(&context);
+
+            // A misbehaving bot forfeits its choice to the first legal card.
+            let card = if legal.contains(&choice) {
+                choice
+            } else {
+                legal[0]
+            };
+            self.play_card(to_play, card);
+        }
+    }
+
+    fn play_card(&mut self, seat: usize, card: Card) {
+        self.players[seat].hand.remove(&card);
+        if *card.suit() == Suit::HEARTS {
+            self.hearts_broken = true;
+        }
+        self.trick.push(PlayedCard { seat, card });
+
+        if self.trick.len() < SEATS {
+            self.state = HeartsState::Playing {
+                to_play: (seat + 1) % SEATS,
+            };
+            return;
+        }
+
+        let cards: Vec<Card> = self.trick.iter().map(|p| p.card).collect();
+        let winner = self.trick[rules::trick_winner(&cards).expect("full trick")].seat;
+        let points = rules::points(&cards);
+        self.hand_points[winner] += points;
+
+        self.last_trick = Some(CompletedTrick {
+            cards: std::mem::take(&mut self.trick),
+            winner,
+            points,
+        });
+        self.tricks_played += 1;
+
+        if self.players.iter().all(|p| p.hand.is_empty()) {
+            self.end_hand();
+        } else {
+            self.state = HeartsState::Playing { to_play: winner };
+        }
+    }
+
+    fn end_hand(&mut self) {
+        let changes = rules::hand_scores(&self.hand_points);
+        self.moon_shooter = self
+            .hand_points
+            .iter()
+            .position(|&p| p == rules::POINTS_PER_HAND);
+
+        for (score, change) in self.scores.iter_mut().zip(changes) {
+            *score += change;
+        }
+
+        self.state = if self.scores.iter().any(|&s| s >= TARGET_SCORE) {
+            HeartsState::GameOver
+        } else {
+            HeartsState::HandOver
+        };
+    }
+
+    /// Returns the seats with the lowest score.
+    pub fn leaders(&self) -> Vec<usize> {
+        let best = *self.scores.iter().min().expect("four seats");
+        (0..SEATS).filter(|&s| self.scores[s] == best).collect()
+    }
+
+    pub fn view(&self) -> HeartsView {
+        let hand = self.players[HUMAN_SEAT].hand.cards().to_vec();
+
+        let hand_by_suit = DISPLAY_SUITS
+            .iter()
+            .map(|suit| {
+                let cards = hand.iter().filter(|c| c.suit() == suit).copied().collect();
+                (*suit, cards)
+            })
+            .collect();
+
+        let legal_plays = match self.state {
+            HeartsState::Playing { to_play } if to_play == HUMAN_SEAT => {
+                self.legal_plays(HUMAN_SEAT)
+            }
+            _ => Vec::new(),
+        };
+
+        HeartsView {
+            phase: self.state,
+            pass_direction: self.pass_direction(),
+            hand_number: self.hand_number,
+
+            hand,
+            hand_by_suit,
+            legal_plays,
+
+            trick: self.trick.clone(),
+            last_trick: self.last_trick.clone(),
+            hearts_broken: self.hearts_broken,
+
+            player_names: self.players.iter().map(|p| p.name().to_string()).collect(),
+            scores: self.scores,
+            hand_points: self.hand_points,
+            moon_shooter: self.moon_shooter,
+            leaders: if self.state == HeartsState::GameOver {
+                self.leaders()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Value;
+
+    /// Deals a fixed, sorted deck: seat 0 gets all clubs, seat 1 diamonds,
+    /// seat 2 spades and seat 3 hearts.
+    fn rigged(hand_number: usize) -> Hearts {
+        let mut game = Hearts::new();
+        game.hand_number = hand_number;
+        for player in &mut game.players {
+            player.reset_hand();
+        }
+        let cards: Vec<Card> = DISPLAY_SUITS
+            .iter()
+            .flat_map(|suit| Value::standard_values().map(move |value| Card::new(*suit, value)))
+            .collect();
+        for (i, card) in cards.into_iter().enumerate() {
+            game.players[i / 13].hand.add(card);
+        }
+        game.begin_hand();
+        game
+    }
+
+    fn play_out_hand(game: &mut Hearts) {
+        let mut bot = BasicBot;
+        while let HeartsState::Playing { .. } = game.state {
+            let view = game.view();
+            let context = TrickContext {
+                seat: HUMAN_SEAT,
+                hand: &view.hand,
+                legal: &view.legal_plays,
+                trick: &view.trick,
+                hearts_broken: view.hearts_broken,
+                hand_points: &view.hand_points,
+            };
+            let card = bot.choose_play(&context);
+            game.play(card).unwrap();
+        }
+    }
+
+    #[test]
+    fn new_game_deals_thirteen_each_and_waits_for_pass() {
+        let game = Hearts::new();
+        assert_eq!(game.state, HeartsState::Passing);
+        for player in &game.players {
+            assert_eq!(player.hand.len(), 13);
+        }
+        assert_eq!(game.view().pass_direction, PassDirection::Left);
+    }
+
+    #[test]
+    fn hand_view_is_sorted_by_suit() {
+        let game = Hearts::new();
+        let view = game.view();
+        let order: Vec<(usize, u8)> = view.hand.iter().map(display_order).collect();
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(order, sorted);
+        assert_eq!(
+            view.hand_by_suit
+                .iter()
+                .map(|(_, c)| c.len())
+                .sum::<usize>(),
+            13
+        );
+    }
+
+    /// Passes whatever it is told to, held or not.
+    struct CheatingBot(Vec<Card>);
+
+    impl TrickTakingBot for CheatingBot {
+        fn choose_pass(&mut self, _: &[Card], _: usize, _: PassDirection) -> Vec<Card> {
+            self.0.clone()
+        }
+
+        fn choose_play(&mut self, context: &TrickContext) -> Card {
+            context.legal[0]
+        }
+    }
+
+    #[test]
+    fn bad_bot_passes_fall_back_to_a_legal_pass() {
+        let spade = |value| Card::new(Suit::SPADES, value);
+        let pass = vec![
+            Card::new(Suit::CLUBS, Value::ACE),
+            Card::new(Suit::CLUBS, Value::KING),
+            Card::new(Suit::CLUBS, Value::QUEEN),
+        ];
+        for bad in [
+            // Cards held by the human.
+            pass.clone(),
+            // Too few.
+            vec![spade(Value::TWO)],
+            // The same card twice.
+            vec![spade(Value::TWO), spade(Value::TWO), spade(Value::THREE)],
+        ] {
+            let mut game = rigged(0);
+            game.bots[1] = Some(Box::new(CheatingBot(bad)));
+            game.pass(&pass).unwrap();
+
+            let mut all: Vec<Card> = game
+                .players
+                .iter()
+                .flat_map(|p| p.hand.cards().to_vec())
+                .collect();
+            all.extend(game.trick.iter().map(|played| played.card));
+            let total = all.len();
+            all.sort();
+            all.dedup();
+            assert_eq!(all.len(), total, "no card is duplicated");
+            assert_eq!(total, 52);
+        }
+    }
+
+    #[test]
+    fn pass_left_moves_cards_to_next_seat() {
+        let mut game = rigged(0);
+        let pass = vec![
+            Card::new(Suit::CLUBS, Value::ACE),
+            Card::new(Suit::CLUBS, Value::KING),
+            Card::new(Suit::CLUBS, Value::QUEEN),
+        ];
+
+        game.pass(&pass).unwrap();
+
+        // Seat 3 (all hearts) passed to us; we passed to seat 1.
+        let hearts = game.players[HUMAN_SEAT]
+            .hand
+            .iter()
+            .filter(|c| *c.suit() == Suit::HEARTS)
+            .count();
+        assert_eq!(hearts, 3);
+        assert!(pass
+            .iter()
+            .all(|c| !game.players[HUMAN_SEAT].hand.contains(c)));
+    }
+
+    #[test]
+    fn pass_rejects_cards_not_in_hand() {
+        let mut game = rigged(0);
+        let pass = vec![
+            Card::new(Suit::HEARTS, Value::ACE),
+            Card::new(Suit::CLUBS, Value::KING),
+            Card::new(Suit::CLUBS, Value::QUEEN),
+        ];
+        assert_eq!(game.pass(&pass), Err(HeartsError::CardNotInHand));
+        assert_eq!(game.pass(&pass[1..]), Err(HeartsError::WrongPassCount));
+    }
+
+    #[test]
+    fn hold_hand_skips_passing_and_two_of_clubs_leads() {
+        let game = rigged(3);
+        let view = game.view();
+
+        // We hold the two of clubs, so it's our lead and it's the only play.
+        assert_eq!(
+            view.phase,
+            HeartsState::Playing {
+                to_play: HUMAN_SEAT
+            }
+        );
+        assert_eq!(view.legal_plays, vec![Card::new(Suit::CLUBS, Value::TWO)]);
+    }
+
+    #[test]
+    fn illegal_play_is_rejected() {
+        let mut game = rigged(3);
+        assert_eq!(
+            game.play(Card::new(Suit::CLUBS, Value::ACE)),
+            Err(HeartsError::IllegalPlay)
+        );
+        assert_eq!(
+            game.play(Card::new(Suit::HEARTS, Value::ACE)),
+            Err(HeartsError::CardNotInHand)
+        );
+    }
+
+    #[test]
+    fn bots_play_after_human_leads() {
+        let mut game = rigged(3);
+        game.play(Card::new(Suit::CLUBS, Value::TWO)).unwrap();
+
+        // Everyone else is void in clubs; we win the first trick and lead again.
+        let last = game.view().last_trick.unwrap();
+        assert_eq!(last.cards.len(), SEATS);
+        assert_eq!(last.winner, HUMAN_SEAT);
+        assert_eq!(
+            game.state,
+            HeartsState::Playing {
+                to_play: HUMAN_SEAT
+            }
+        );
+    }
+
+    #[test]
+    fn each_hand_hands_out_26_points_or_a_moon() {
+        let mut game = Hearts::new();
+        let pass: Vec<Card> = game.view().hand[..PASS_COUNT].to_vec();
+        game.pass(&pass).unwrap();
+        play_out_hand(&mut game);
+
+        assert_eq!(game.state, HeartsState::HandOver);
+        let total: u32 = game.view().scores.iter().sum();
+        assert!(total == 26 || total == 78);
+    }
+
+    #[test]
+    fn game_ends_when_someone_reaches_target() {
+        let mut game = Hearts::new();
+        loop {
+            if game.state == HeartsState::Passing {
+                let pass: Vec<Card> = game.view().hand[..PASS_COUNT].to_vec();
+                game.pass(&pass).unwrap();
+            }
+            play_out_hand(&mut game);
+            if game.state == HeartsState::GameOver {
+                break;
+            }
+            game.next_hand().unwrap();
+        }
+
+        let view = game.view();
+        assert!(view.scores.iter().any(|&s| s >= TARGET_SCORE));
+        assert!(!view.leaders.is_empty());
+    }
+}
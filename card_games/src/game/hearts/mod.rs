@@ -0,0 +1,9 @@
+pub mod bot;
+#[allow(clippy::module_inception)]
+pub mod hearts;
+pub mod rules;
+pub mod types;
+pub mod view;
+
+pub use hearts::Hearts;
+pub use types::{HeartsError, HeartsState, PassDirection};
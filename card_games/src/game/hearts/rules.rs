@@ -0,0 +1,229 @@
+//! Hearts rules: card points, trick winners and which cards may be played.
+//!
+//! These rules follow the common North American game:
+//! - Each heart is worth 1 point and the queen of spades 13
+//! - The two of clubs leads the first trick
+//! - No points may be played on the first trick unless a hand holds nothing else
+//! - Hearts cannot be led until one has been discarded, unless the leader
+//!   holds only hearts
+
+use crate::cards::{Card, RankOrder, Suit, Value};
+
+use super::types::SEATS;
+
+/// Total points in a deal; taking all of them "shoots the moon".
+pub const POINTS_PER_HAND: u32 = 26;
+
+/// Returns the rank of a card with aces high (2 = 2 … A = 14).
+pub fn rank_ace_high(card: &Card) -> u8 {
+    match card.rank() {
+        Some(1) => 14,
+        Some(rank) => rank,
+        None => 0,
+    }
+}
+
+/// Returns `true` for the queen of spades.
+pub fn is_queen_of_spades(card: &Card) -> bool {
+    *card.suit() == Suit::SPADES && *card.value() == Value::QUEEN
+}
+
+/// Returns `true` for the two of clubs, which leads the first trick.
+pub fn is_two_of_clubs(card: &Card) -> bool {
+    *card.suit() == Suit::CLUBS && *card.value() == Value::TWO
+}
+
+/// Returns the penalty points a card carries.
+pub fn card_points(card: &Card) -> u32 {
+    if *card.suit() == Suit::HEARTS {
+        1
+    } else if is_queen_of_spades(card) {
+        13
+    } else {
+        0
+    }
+}
+
+/// Returns the penalty points in a set of cards.
+pub fn points(cards: &[Card]) -> u32 {
+    cards.iter().map(card_points).sum()
+}
+
+/// Converts the points taken in a hand into score changes.
+///
+/// A player who takes every point "shoots the moon": they score nothing and
+/// every other player scores 26.
+pub fn hand_scores(taken: &[u32; SEATS]) -> [u32; SEATS] {
+    match taken.iter().position(|&p| p == POINTS_PER_HAND) {
+        Some(shooter) => {
+            let mut scores = [POINTS_PER_HAND; SEATS];
+            scores[shooter] = 0;
+            scores
+        }
+        None => *taken,
+    }
+}
+
+/// Returns the index (into `trick`) of the winning card: the highest card of
+/// the suit led.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{Card, Suit, Value};
+/// use card_games::game::hearts::rules::trick_winner;
+/// let trick = vec![
+///     Card::new(Suit::CLUBS, Value::TEN),
+///     Card::new(Suit::HEARTS, Value::ACE),
+///     Card::new(Suit::CLUBS, Value::KING),
+///     Card::new(Suit::CLUBS, Value::TWO),
+/// ];
+/// assert_eq!(trick_winner(&trick), Some(2));
+/// ```
+pub fn trick_winner(trick: &[Card]) -> Option<usize> {
+    RankOrder::ace_high().trick_winner(trick)
+}
+
+/// Returns the cards in `hand` that may legally be played.
+///
+/// `trick` holds the cards already played to the current trick, in order.
+pub fn legal_plays(
+    hand: &[Card],
+    trick: &[Card],
+    hearts_broken: bool,
+    first_trick: bool,
+) -> Vec<Card> {
+    let Some(led) = trick.first().map(|c| *c.suit()) else {
+        return legal_leads(hand, hearts_broken, first_trick);
+    };
+
+    let following: Vec<Card> = hand.iter().filter(|c| *c.suit() == led).copied().collect();
+    if !following.is_empty() {
+        return following;
+    }
+
+    if first_trick {
+        let safe: Vec<Card> = hand
+            .iter()
+            .filter(|c| card_points(c) == 0)
+            .copied()
+            .collect();
+        if !safe.is_empty() {
+            return safe;
+        }
+    }
+
+    hand.to_vec()
+}
+
+fn legal_leads(hand: &[Card], hearts_broken: bool, first_trick: bool) -> Vec<Card> {
+    if first_trick {
+        if let Some(two) = hand.iter().find(|c| is_two_of_clubs(c)) {
+            return vec![*two];
+        }
+    }
+
+    if hearts_broken {
+        return hand.to_vec();
+    }
+
+    let non_hearts: Vec<Card> = hand
+        .iter()
+        .filter(|c| *c.suit() != Suit::HEARTS)
+        .copied()
+        .collect();
+
+    if non_hearts.is_empty() {
+        hand.to_vec()
+    } else {
+        non_hearts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    #[test]
+    fn points_count_hearts_and_queen() {
+        let cards = vec![
+            card(Suit::HEARTS, Value::TWO),
+            card(Suit::HEARTS, Value::ACE),
+            card(Suit::SPADES, Value::QUEEN),
+            card(Suit::SPADES, Value::KING),
+        ];
+        assert_eq!(points(&cards), 15);
+    }
+
+    #[test]
+    fn shooting_the_moon_gives_everyone_else_26() {
+        assert_eq!(hand_scores(&[0, 26, 0, 0]), [26, 0, 26, 26]);
+        assert_eq!(hand_scores(&[13, 5, 8, 0]), [13, 5, 8, 0]);
+    }
+
+    #[test]
+    fn two_of_clubs_must_lead_first_trick() {
+        let hand = vec![card(Suit::CLUBS, Value::TWO), card(Suit::CLUBS, Value::ACE)];
+        assert_eq!(
+            legal_plays(&hand, &[], false, true),
+            vec![card(Suit::CLUBS, Value::TWO)]
+        );
+    }
+
+    #[test]
+    fn must_follow_suit() {
+        let hand = vec![
+            card(Suit::CLUBS, Value::FIVE),
+            card(Suit::HEARTS, Value::ACE),
+        ];
+        let trick = vec![card(Suit::CLUBS, Value::TWO)];
+        assert_eq!(
+            legal_plays(&hand, &trick, false, false),
+            vec![card(Suit::CLUBS, Value::FIVE)]
+        );
+    }
+
+    #[test]
+    fn no_points_on_first_trick_when_void() {
+        let hand = vec![
+            card(Suit::SPADES, Value::QUEEN),
+            card(Suit::HEARTS, Value::ACE),
+            card(Suit::DIAMONDS, Value::TWO),
+        ];
+        let trick = vec![card(Suit::CLUBS, Value::TWO)];
+        assert_eq!(
+            legal_plays(&hand, &trick, false, true),
+            vec![card(Suit::DIAMONDS, Value::TWO)]
+        );
+    }
+
+    #[test]
+    fn hearts_cannot_be_led_until_broken() {
+        let hand = vec![
+            card(Suit::HEARTS, Value::TWO),
+            card(Suit::SPADES, Value::ACE),
+        ];
+        assert_eq!(
+            legal_plays(&hand, &[], false, false),
+            vec![card(Suit::SPADES, Value::ACE)]
+        );
+        assert_eq!(legal_plays(&hand, &[], true, false).len(), 2);
+
+        let only_hearts = vec![card(Suit::HEARTS, Value::TWO)];
+        assert_eq!(legal_plays(&only_hearts, &[], false, false).len(), 1);
+    }
+
+    #[test]
+    fn highest_of_led_suit_wins() {
+        let trick = vec![
+            card(Suit::DIAMONDS, Value::TWO),
+            card(Suit::SPADES, Value::ACE),
+            card(Suit::DIAMONDS, Value::ACE),
+            card(Suit::DIAMONDS, Value::KING),
+        ];
+        assert_eq!(trick_winner(&trick), Some(2));
+    }
+}
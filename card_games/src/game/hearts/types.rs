@@ -0,0 +1,134 @@
+use std::fmt::Display;
+
+use crate::cards::Card;
+
+/// Number of players at a Hearts table.
+pub const SEATS: usize = 4;
+
+/// Cards passed at the start of each hand.
+pub const PASS_COUNT: usize = 3;
+
+/// The game ends once a player reaches this score.
+pub const TARGET_SCORE: u32 = 100;
+
+/// The human player's seat; the other seats are played by bots.
+pub const HUMAN_SEAT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartsState {
+    Passing,
+    Playing { to_play: usize },
+    HandOver,
+    GameOver,
+}
+
+impl Display for HeartsState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeartsState::Passing => write!(f, "Choose three cards to pass"),
+            HeartsState::Playing { to_play } if *to_play == HUMAN_SEAT => write!(f, "Your turn"),
+            HeartsState::Playing { .. } => write!(f, "Waiting for other players"),
+            HeartsState::HandOver => write!(f, "Hand over"),
+            HeartsState::GameOver => write!(f, "Game over"),
+        }
+    }
+}
+
+/// Where cards are passed before a hand. The direction rotates every hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassDirection {
+    Left,
+    Right,
+    Across,
+    Hold,
+}
+
+impl PassDirection {
+    /// Returns the direction for a hand, counting from zero.
+    pub fn for_hand(hand_number: usize) -> Self {
+        match hand_number % 4 {
+            0 => PassDirection::Left,
+            1 => PassDirection::Right,
+            2 => PassDirection::Across,
+            _ => PassDirection::Hold,
+        }
+    }
+
+    /// Returns the seat that receives cards passed from `seat`.
+    pub fn target(&self, seat: usize) -> usize {
+        match self {
+            PassDirection::Left => (seat + 1) % SEATS,
+            PassDirection::Right => (seat + SEATS - 1) % SEATS,
+            PassDirection::Across => (seat + 2) % SEATS,
+            PassDirection::Hold => seat,
+        }
+    }
+}
+
+impl Display for PassDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PassDirection::Left => write!(f, "Pass left"),
+            PassDirection::Right => write!(f, "Pass right"),
+            PassDirection::Across => write!(f, "Pass across"),
+            PassDirection::Hold => write!(f, "No pass"),
+        }
+    }
+}
+
+/// A card played to a trick and the seat that played it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayedCard {
+    pub seat: usize,
+    pub card: Card,
+}
+
+/// A completed trick and who took it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletedTrick {
+    pub cards: Vec<PlayedCard>,
+    pub winner: usize,
+    pub points: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartsError {
+    NotPassing,
+    WrongPassCount,
+    CardNotInHand,
+    NotYourTurn,
+    IllegalPlay,
+    HandNotOver,
+}
+
+impl Display for HeartsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeartsError::NotPassing => write!(f, "Cards are not being passed now"),
+            HeartsError::WrongPassCount => write!(f, "Pass exactly {} cards", PASS_COUNT),
+            HeartsError::CardNotInHand => write!(f, "That card is not in your hand"),
+            HeartsError::NotYourTurn => write!(f, "It is not your turn"),
+            HeartsError::IllegalPlay => write!(f, "That card cannot be played now"),
+            HeartsError::HandNotOver => write!(f, "The hand is still in progress"),
+        }
+    }
+}
+
+impl std::error::Error for HeartsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pass_direction_rotates_and_targets_seats() {
+        assert_eq!(PassDirection::for_hand(0), PassDirection::Left);
+        assert_eq!(PassDirection::for_hand(3), PassDirection::Hold);
+        assert_eq!(PassDirection::for_hand(4), PassDirection::Left);
+
+        assert_eq!(PassDirection::Left.target(3), 0);
+        assert_eq!(PassDirection::Right.target(0), 3);
+        assert_eq!(PassDirection::Across.target(1), 3);
+        assert_eq!(PassDirection::Hold.target(2), 2);
+    }
+}
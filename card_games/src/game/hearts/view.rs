@@ -0,0 +1,27 @@
+use crate::{
+    cards::{Card, Suit},
+    game::hearts::types::{CompletedTrick, HeartsState, PassDirection, PlayedCard, SEATS},
+};
+
+#[derive(Debug, Clone)]
+pub struct HeartsView {
+    pub phase: HeartsState,
+    pub pass_direction: PassDirection,
+    pub hand_number: usize,
+
+    /// The human's hand, sorted by suit and rank.
+    pub hand: Vec<Card>,
+    pub hand_by_suit: Vec<(Suit, Vec<Card>)>,
+    pub legal_plays: Vec<Card>,
+
+    pub trick: Vec<PlayedCard>,
+    pub last_trick: Option<CompletedTrick>,
+    pub hearts_broken: bool,
+
+    pub player_names: Vec<String>,
+    pub scores: [u32; SEATS],
+    pub hand_points: [u32; SEATS],
+    pub moon_shooter: Option<usize>,
+    /// Seats with the lowest score, filled in once the game is over.
+    pub leaders: Vec<usize>,
+}
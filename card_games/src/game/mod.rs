@@ -1 +1,6 @@
+pub mod baccarat;
 pub mod blackjack;
+pub mod cribbage;
+pub mod gin_rummy;
+pub mod hearts;
+pub mod solitaire;
@@ -0,0 +1,111 @@
+//! Microsoft-compatible FreeCell deal generation.
+//!
+//! Deals are produced from a deal number with the linear congruential generator
+//! of the Microsoft C runtime, so "game #11982" lays out exactly as it did in
+//! Windows FreeCell.
+
+use crate::cards::{deck_builder::DeckBuilder, Card, Deck};
+
+use super::types::foundation_index;
+
+/// The `rand()` generator from the Microsoft C runtime.
+struct MsRand {
+    state: u32,
+}
+
+impl MsRand {
+    fn new(seed: u32) -> Self {
+        Self { state: seed }
+    }
+
+    fn next(&mut self) -> u32 {
+        self.state = self.state.wrapping_mul(214_013).wrapping_add(2_531_011);
+        (self.state >> 16) & 0x7fff
+    }
+}
+
+/// Position of a card in the unshuffled Microsoft deck: A♣ A♦ A♥ A♠ 2♣ … K♠.
+fn ms_index(card: &Card) -> usize {
+    let rank = card.rank().unwrap_or(0) as usize;
+    let suit = foundation_index(card.suit()).unwrap_or(0);
+    rank.saturating_sub(1) * 4 + suit
+}
+
+/// Returns the cards of deal `deal_number` in the order they are laid out.
+///
+/// Card `i` of the returned deck goes to column `i % 8`.
+///
+/// # Example
+/// ```
+/// use card_games::cards::{Card, Suit, Value};
+/// use card_games::game::solitaire::freecell::deal::microsoft_deal;
+/// let mut deck = microsoft_deal(1);
+/// assert_eq!(deck.draw(), Some(Card::new(Suit::DIAMONDS, Value::JACK)));
+/// ```
+pub fn microsoft_deal(deal_number: u32) -> Deck {
+    let mut cards: Vec<Card> = DeckBuilder::new()
+        .standard52()
+        .build()
+        .into_iter()
+        .collect();
+    cards.sort_by_key(ms_index);
+
+    let mut rng = MsRand::new(deal_number);
+    let mut dealt = Vec::with_capacity(cards.len());
+
+    while !cards.is_empty() {
+        let j = rng.next() as usize % cards.len();
+        dealt.push(cards.swap_remove(j));
+    }
+
+    Deck::from_cards(dealt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Suit, Value};
+
+    #[test]
+    fn deal_one_first_row_matches_windows() {
+        let first_row: Vec<Card> = microsoft_deal(1).into_iter().take(8).collect();
+        assert_eq!(
+            first_row,
+            vec![
+                Card::new(Suit::DIAMONDS, Value::JACK),
+                Card::new(Suit::DIAMONDS, Value::TWO),
+                Card::new(Suit::HEARTS, Value::NINE),
+                Card::new(Suit::CLUBS, Value::JACK),
+                Card::new(Suit::DIAMONDS, Value::FIVE),
+                Card::new(Suit::HEARTS, Value::SEVEN),
+                Card::new(Suit::CLUBS, Value::SEVEN),
+                Card::new(Suit::HEARTS, Value::FIVE),
+            ]
+        );
+    }
+
+    #[test]
+    fn deal_one_last_row_matches_windows() {
+        let last_row: Vec<Card> = microsoft_deal(1).into_iter().skip(48).collect();
+        assert_eq!(
+            last_row,
+            vec![
+                Card::new(Suit::SPADES, Value::SIX),
+                Card::new(Suit::CLUBS, Value::NINE),
+                Card::new(Suit::HEARTS, Value::TWO),
+                Card::new(Suit::HEARTS, Value::SIX),
+            ]
+        );
+    }
+
+    #[test]
+    fn deal_contains_every_card_once() {
+        let deck = microsoft_deal(11982);
+        assert_eq!(deck.len(), 52);
+        let mut seen = [false; 52];
+        for card in deck {
+            seen[ms_index(&card)] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+}
@@ -0,0 +1,577 @@
+//! The FreeCell game engine.
+//!
+//! A [`FreeCell`] table holds eight tableau columns, four free cells and four
+//! foundations. Moves are validated against the standard rules, recorded, and
+//! can be undone one at a time.
+
+use crate::cards::{card_set::CardSet, Card, Deck};
+
+use super::{
+    deal::microsoft_deal,
+    types::{foundation_index, FreeCellError, Move, COLUMNS, FOUNDATIONS, FREE_CELLS},
+};
+
+/// Returns `true` if `card` may be placed on `onto` in the tableau
+/// (one rank lower and of the opposite colour).
+pub fn stacks_on(card: &Card, onto: &Card) -> bool {
+    match (card.rank(), onto.rank()) {
+        (Some(card_rank), Some(onto_rank)) => {
+            card_rank + 1 == onto_rank && card.is_red() != onto.is_red()
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if the cards form an alternating descending sequence
+/// that can be moved as a unit.
+pub fn is_sequence(cards: &[Card]) -> bool {
+    cards.windows(2).all(|pair| stacks_on(&pair[1], &pair[0]))
+}
+
+/// A game of FreeCell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeCell {
+    columns: Vec<Vec<Card>>,
+    free_cells: [Option<Card>; FREE_CELLS],
+    foundations: [Vec<Card>; FOUNDATIONS],
+    /// Foundation piles in the order cards were placed, used by undo.
+    foundation_order: Vec<usize>,
+    history: Vec<Move>,
+    deal_number: Option<u32>,
+}
+
+impl FreeCell {
+    /// Starts the Microsoft-numbered deal `deal_number`.
+    ///
+    /// # Example
+    /// ```
+    /// use card_games::game::solitaire::freecell::FreeCell;
+    /// let game = FreeCell::from_deal_number(11982);
+    /// assert_eq!(game.columns()[0].len(), 7);
+    /// ```
+    pub fn from_deal_number(deal_number: u32) -> Self {
+        let mut game =
+            Self::from_deck(microsoft_deal(deal_number)).expect("Microsoft deals are complete");
+        game.deal_number = Some(deal_number);
+        game
+    }
+
+    /// Deals a standard 52-card deck into the eight columns, left to right.
+    ///
+    /// # Errors
+    /// Returns [`FreeCellError::InvalidDeck`] unless the deck holds each of
+    /// the 52 standard cards exactly once.
+    pub fn from_deck(deck: Deck) -> Result<Self, FreeCellError> {
+        if deck.len() != 52 || deck.card_set() != CardSet::standard52() {
+            return Err(FreeCellError::InvalidDeck);
+        }
+
+        let mut columns = vec![Vec::new(); COLUMNS];
+        for (i, card) in deck.into_iter().enumerate() {
+            columns[i % COLUMNS].push(card);
+        }
+
+        Self::from_columns(columns)
+    }
+
+    /// Creates a game from an explicit tableau layout, with empty free cells
+    /// and foundations. Column `0` is the leftmost and each column lists its
+    /// cards from the bottom up.
+    ///
+    /// # Errors
+    /// Returns [`FreeCellError::InvalidColumn`] if more than eight columns are
+    /// given and [`FreeCellError::InvalidDeck`] if a joker is present.
+    pub fn from_columns(mut columns: Vec<Vec<Card>>) -> Result<Self, FreeCellError> {
+        if columns.len() > COLUMNS {
+            return Err(FreeCellError::InvalidColumn(columns.len() - 1));
+        }
+        if columns.iter().flatten().any(|c| c.is_joker()) {
+            return Err(FreeCellError::InvalidDeck);
+        }
+        columns.resize(COLUMNS, Vec::new());
+
+        Ok(Self {
+            columns,
+            free_cells: [None; FREE_CELLS],
+            foundations: Default::default(),
+            foundation_order: Vec::new(),
+            history: Vec::new(),
+            deal_number: None,
+        })
+    }
+
+    /// Returns the deal number this game was started from, if any.
+    pub fn deal_number(&self) -> Option<u32> {
+        self.deal_number
+    }
+
+    /// Returns the tableau columns, each listed from the bottom card up.
+    pub fn columns(&self) -> &[Vec<Card>] {
+        &self.columns
+    }
+
+    /// Returns the contents of the free cells.
+    pub fn free_cells(&self) -> &[Option<Card>; FREE_CELLS] {
+        &self.free_cells
+    }
+
+    /// Returns the foundation piles, ordered clubs, diamonds, hearts, spades.
+    pub fn foundations(&self) -> &[Vec<Card>; FOUNDATIONS] {
+        &self.foundations
+    }
+
+    /// Returns the moves played so far, oldest first.
+    pub fn history(&self) -> &[Move] {
+        &self.history
+    }
+
+    /// Returns `true` once every card is on the foundations.
+    pub fn is_won(&self) -> bool {
+        self.foundations.iter().all(|pile| pile.len() == 13)
+    }
+
+    /// Returns the largest number of cards that can be moved between columns
+    /// at once: `(empty free cells + 1) * 2^(empty columns)`.
+    ///
+    /// When the target is itself an empty column it cannot be used as
+    /// temporary storage, so it is not counted.
+    pub fn max_supermove(&self, to_empty_column: bool) -> usize {
+        let empty_cells = self.free_cells.iter().filter(|c| c.is_none()).count();
+        let empty_columns = self.columns.iter().filter(|c| c.is_empty()).count();
+        let usable_columns = empty_columns.saturating_sub(to_empty_column as usize);
+
+        (empty_cells + 1) << usable_columns
+    }
+
+    /// Returns `true` if the card is the next one its foundation needs.
+    pub fn can_move_to_foundation(&self, card: &Card) -> bool {
+        let Some(pile) = foundation_index(card.suit()) else {
+            return false;
+        };
+        card.rank() == Some(self.foundations[pile].len() as u8 + 1)
+    }
+
+    /// Checks a move against the rules without applying it.
+    ///
+    /// # Errors
+    /// Returns the [`FreeCellError`] describing why the move is illegal.
+    pub fn validate(&self, mv: Move) -> Result<(), FreeCellError> {
+        match mv {
+            Move::ColumnToColumn { from, to, count } => {
+                let source = self.column(from)?;
+                let target = self.column(to)?;
+
+                if from == to {
+                    return Err(FreeCellError::IllegalPlacement);
+                }
+                if count == 0 || count > source.len() {
+                    return Err(FreeCellError::EmptySource);
+                }
+
+                let moving = &source[source.len() - count..];
+                if !is_sequence(moving) {
+                    return Err(FreeCellError::NotASequence);
+                }
+                if let Some(top) = target.last() {
+                    if !stacks_on(&moving[0], top) {
+                        return Err(FreeCellError::IllegalPlacement);
+                    }
+                }
+
+                let max = self.max_supermove(target.is_empty());
+                if count > max {
+                    return Err(FreeCellError::TooManyCards {
+                        requested: count,
+                        max,
+                    });
+                }
+                Ok(())
+            }
+            Move::ColumnToFreeCell { from, cell } => {
+                self.column(from)?
+                    .last()
+                    .ok_or(FreeCellError::EmptySource)?;
+                match self.cell(cell)? {
+                    Some(_) => Err(FreeCellError::FreeCellOccupied(cell)),
+                    None => Ok(()),
+                }
+            }
+            Move::ColumnToFoundation { from } => {
+                let card = self
+                    .column(from)?
+                    .last()
+                    .ok_or(FreeCellError::EmptySource)?;
+                self.check_foundation(card)
+            }
+            Move::FreeCellToColumn { cell, to } => {
+                let card = self.cell(cell)?.ok_or(FreeCellError::EmptySource)?;
+                match self.column(to)?.last() {
+                    Some(top) if !stacks_on(&card, top) => Err(FreeCellError::IllegalPlacement),
+                    _ => Ok(()),
+                }
+            }
+            Move::FreeCellToFoundation { cell } => {
+                let card = self.cell(cell)?.ok_or(FreeCellError::EmptySource)?;
+                self.check_foundation(&card)
+            }
+        }
+    }
+
+    /// Applies a move and records it in the history.
+    ///
+    /// # Errors
+    /// Returns the [`FreeCellError`] describing why the move is illegal;
+    /// the table is left unchanged.
+    pub fn apply(&mut self, mv: Move) -> Result<(), FreeCellError> {
+        self.validate(mv)?;
+        self.perform(mv);
+        self.history.push(mv);
+        Ok(())
+    }
+
+    /// Reverts the most recent move, returning it, or `None` if no moves
+    /// have been played.
+    pub fn undo(&mut self) -> Option<Move> {
+        let mv = self.history.pop()?;
+
+        match mv {
+            Move::ColumnToColumn { from, to, count } => {
+                let at = self.columns[to].len() - count;
+                let cards = self.columns[to].split_off(at);
+                self.columns[from].extend(cards);
+            }
+            Move::ColumnToFreeCell { from, cell } => {
+                let card = self.free_cells[cell]
+                    .take()
+                    .expect("undo from empty free cell");
+                self.columns[from].push(card);
+            }
+            Move::ColumnToFoundation { from } => {
+                let card = self.pop_foundation();
+                self.columns[from].push(card);
+            }
+            Move::FreeCellToColumn { cell, to } => {
+                self.free_cells[cell] = self.columns[to].pop();
+            }
+            Move::FreeCellToFoundation { cell } => {
+                self.free_cells[cell] = Some(self.pop_foundation());
+            }
+        }
+
+        Some(mv)
+    }
+
+    /// Moves every card that can safely go to the foundations, returning the
+    /// moves played. Each is recorded in the history and can be undone.
+    ///
+    /// A card is safe to move when no card still in play could need it as a
+    /// landing spot: aces and twos always, otherwise only once both
+    /// opposite-colour foundations hold the rank below it.
+    pub fn auto_play(&mut self) -> Vec<Move> {
+        let mut played = Vec::new();
+
+        while let Some(mv) = self.next_safe_foundation_move() {
+            self.perform(mv);
+            self.history.push(mv);
+            played.push(mv);
+        }
+
+        played
+    }
+
+    /// Returns every legal move in the current position.
+    ///
+    /// Column-to-column moves are listed for each sequence length the
+    /// supermove limit allows.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+
+        for from in 0..COLUMNS {
+            moves.push(Move::ColumnToFoundation { from });
+            for cell in 0..FREE_CELLS {
+                moves.push(Move::ColumnToFreeCell { from, cell });
+            }
+            for to in 0..COLUMNS {
+                for count in 1..=self.columns[from].len() {
+                    moves.push(Move::ColumnToColumn { from, to, count });
+                }
+            }
+        }
+        for cell in 0..FREE_CELLS {
+            moves.push(Move::FreeCellToFoundation { cell });
+            for to in 0..COLUMNS {
+                moves.push(Move::FreeCellToColumn { cell, to });
+            }
+        }
+
+        moves.retain(|mv| self.validate(*mv).is_ok());
+        moves
+    }
+
+    fn column(&self, index: usize) -> Result<&Vec<Card>, FreeCellError> {
+        self.columns
+            .get(index)
+            .ok_or(FreeCellError::InvalidColumn(index))
+    }
+
+    fn cell(&self, index: usize) -> Result<Option<Card>, FreeCellError> {
+        self.free_cells
+            .get(index)
+            .copied()
+            .ok_or(FreeCellError::InvalidFreeCell(index))
+    }
+
+    fn check_foundation(&self, card: &Card) -> Result<(), FreeCellError> {
+        if self.can_move_to_foundation(card) {
+            Ok(())
+        } else {
+            Err(FreeCellError::IllegalPlacement)
+        }
+    }
+
+    /// Applies an already-validated move.
+    fn perform(&mut self, mv: Move) {
+        match mv {
+            Move::ColumnToColumn { from, to, count } => {
+                let at = self.columns[from].len() - count;
+                let cards = self.columns[from].split_off(at);
+                self.columns[to].extend(cards);
+            }
+            Move::ColumnToFreeCell { from, cell } => {
+                self.free_cells[cell] = self.columns[from].pop();
+            }
+            Move::ColumnToFoundation { from } => {
+                let card = self.columns[from].pop().expect("validated move");
+                self.push_foundation(card);
+            }
+            Move::FreeCellToColumn { cell, to } => {
+                let card = self.free_cells[cell].take().expect("validated move");
+                self.columns[to].push(card);
+            }
+            Move::FreeCellToFoundation { cell } => {
+                let card = self.free_cells[cell].take().expect("validated move");
+                self.push_foundation(card);
+            }
+        }
+    }
+
+    fn push_foundation(&mut self, card: Card) {
+        let pile = foundation_index(card.suit()).expect("jokers are rejected at setup");
+        self.foundations[pile].push(card);
+        self.foundation_order.push(pile);
+    }
+
+    /// Removes the card most recently placed on a foundation.
+    fn pop_foundation(&mut self) -> Card {
+        let pile = self
+            .foundation_order
+            .pop()
+            .expect("undo of a foundation move");
+        self.foundations[pile]
+            .pop()
+            .expect("foundation pile is not empty")
+    }
+
+    fn next_safe_foundation_move(&self) -> Option<Move> {
+        let safe = |card: &Card| self.can_move_to_foundation(card) && self.is_safe_autoplay(card);
+
+        (0..COLUMNS)
+            .find(|&from| self.columns[from].last().is_some_and(safe))
+            .map(|from| Move::ColumnToFoundation { from })
+            .or_else(|| {
+                (0..FREE_CELLS)
+                    .find(|&cell| self.free_cells[cell].as_ref().is_some_and(safe))
+                    .map(|cell| Move::FreeCellToFoundation { cell })
+            })
+    }
+
+    fn is_safe_autoplay(&self, card: &Card) -> bool {
+        let rank = card.rank().unwrap_or(0) as usize;
+        if rank <= 2 {
+            return true;
+        }
+
+        // Only an opposite-colour card one rank lower could need this card
+        // as a landing spot; once both are home, nothing is lost.
+        self.foundations
+            .iter()
+            .enumerate()
+            .filter(|(pile, _)| (*pile == 1 || *pile == 2) != card.is_red())
+            .all(|(_, cards)| cards.len() + 1 >= rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Suit, Value};
+
+    fn card(suit: Suit, value: Value) -> Card {
+        Card::new(suit, value)
+    }
+
+    #[test]
+    fn deal_lays_out_seven_and_six_card_columns() {
+        let game = FreeCell::from_deal_number(1);
+        let lengths: Vec<usize> = game.columns().iter().map(|c| c.len()).collect();
+        assert_eq!(lengths, vec![7, 7, 7, 7, 6, 6, 6, 6]);
+        assert_eq!(game.deal_number(), Some(1));
+    }
+
+    #[test]
+    fn from_deck_rejects_short_deck() {
+        let deck = Deck::from_cards(vec![card(Suit::SPADES, Value::ACE)]);
+        assert_eq!(FreeCell::from_deck(deck), Err(FreeCellError::InvalidDeck));
+    }
+
+    #[test]
+    fn from_deck_rejects_jokers_and_duplicates() {
+        let standard: Vec<Card> = microsoft_deal(1).into_iter().collect();
+
+        let mut with_joker = standard.clone();
+        with_joker[51] = Card::joker();
+        let deck = Deck::from_cards(with_joker);
+        assert_eq!(FreeCell::from_deck(deck), Err(FreeCellError::InvalidDeck));
+
+        let mut with_duplicate = standard;
+        with_duplicate[51] = with_duplicate[0];
+        let deck = Deck::from_cards(with_duplicate);
+        assert_eq!(FreeCell::from_deck(deck), Err(FreeCellError::InvalidDeck));
+    }
+
+    #[test]
+    fn supermove_counts_free_cells_and_empty_columns() {
+        let mut game = FreeCell::from_columns(vec![vec![card(Suit::SPADES, Value::KING)]]).unwrap();
+        // 4 free cells, 7 empty columns
+        assert_eq!(game.max_supermove(false), 5 << 7);
+        assert_eq!(game.max_supermove(true), 5 << 6);
+
+        game = FreeCell::from_deal_number(1);
+        assert_eq!(game.max_supermove(false), 5);
+    }
+
+    #[test]
+    fn illegal_move_is_rejected_and_table_unchanged() {
+        let mut game = FreeCell::from_columns(vec![
+            vec![card(Suit::HEARTS, Value::NINE)],
+            vec![card(Suit::DIAMONDS, Value::TEN)],
+        ])
+        .unwrap();
+        let before = game.clone();
+
+        let result = game.apply(Move::ColumnToColumn {
+            from: 0,
+            to: 1,
+            count: 1,
+        });
+
+        assert_eq!(result, Err(FreeCellError::IllegalPlacement));
+        assert_eq!(game, before);
+    }
+
+    #[test]
+    fn sequence_move_respects_supermove_limit() {
+        let mut game = FreeCell::from_columns(vec![
+            vec![
+                card(Suit::SPADES, Value::NINE),
+                card(Suit::HEARTS, Value::EIGHT),
+                card(Suit::CLUBS, Value::SEVEN),
+            ],
+            vec![card(Suit::HEARTS, Value::TEN)],
+            vec![card(Suit::CLUBS, Value::ACE)],
+            vec![card(Suit::CLUBS, Value::TWO)],
+            vec![card(Suit::CLUBS, Value::THREE)],
+            vec![card(Suit::CLUBS, Value::FOUR)],
+            vec![card(Suit::CLUBS, Value::FIVE)],
+            vec![card(Suit::CLUBS, Value::SIX)],
+        ])
+        .unwrap();
+        for (cell, from) in [(0, 3), (1, 4), (2, 5), (3, 6)] {
+            game.free_cells[cell] = game.columns[from].pop();
+        }
+        game.columns[3].push(card(Suit::DIAMONDS, Value::KING));
+        game.columns[4].push(card(Suit::DIAMONDS, Value::QUEEN));
+        game.columns[5].push(card(Suit::DIAMONDS, Value::JACK));
+        game.columns[6].push(card(Suit::DIAMONDS, Value::TWO));
+
+        let mv = Move::ColumnToColumn {
+            from: 0,
+            to: 1,
+            count: 3,
+        };
+        assert_eq!(
+            game.apply(mv),
+            Err(FreeCellError::TooManyCards {
+                requested: 3,
+                max: 1
+            })
+        );
+
+        game.free_cells[0] = None;
+        game.free_cells[1] = None;
+        assert_eq!(game.apply(mv), Ok(()));
+        assert_eq!(game.columns()[1].len(), 4);
+    }
+
+    #[test]
+    fn undo_restores_previous_position() {
+        let mut game = FreeCell::from_deal_number(1);
+        let start = game.clone();
+
+        game.apply(Move::ColumnToFreeCell { from: 0, cell: 0 })
+            .unwrap();
+        game.apply(Move::ColumnToFreeCell { from: 1, cell: 1 })
+            .unwrap();
+
+        assert_eq!(
+            game.undo(),
+            Some(Move::ColumnToFreeCell { from: 1, cell: 1 })
+        );
+        assert_eq!(
+            game.undo(),
+            Some(Move::ColumnToFreeCell { from: 0, cell: 0 })
+        );
+        assert_eq!(game.undo(), None);
+        assert_eq!(game, start);
+    }
+
+    #[test]
+    fn auto_play_sends_aces_home_and_can_be_undone() {
+        let mut game = FreeCell::from_columns(vec![
+            vec![
+                card(Suit::HEARTS, Value::TWO),
+                card(Suit::HEARTS, Value::ACE),
+            ],
+            vec![card(Suit::SPADES, Value::ACE)],
+            vec![
+                card(Suit::HEARTS, Value::FOUR),
+                card(Suit::HEARTS, Value::THREE),
+            ],
+        ])
+        .unwrap();
+
+        let played = game.auto_play();
+
+        // 3♥ is not safe while the black aces and twos are still out.
+        assert_eq!(played.len(), 3);
+        assert_eq!(game.foundations()[2].len(), 2);
+        assert_eq!(game.foundations()[3].len(), 1);
+
+        for _ in 0..played.len() {
+            game.undo();
+        }
+        assert!(game.foundations().iter().all(|pile| pile.is_empty()));
+        assert_eq!(game.columns()[0].len(), 2);
+    }
+
+    #[test]
+    fn legal_moves_are_all_valid() {
+        let game = FreeCell::from_deal_number(617);
+        let moves = game.legal_moves();
+        assert!(!moves.is_empty());
+        for mv in moves {
+            let mut copy = game.clone();
+            assert!(copy.apply(mv).is_ok());
+        }
+    }
+}
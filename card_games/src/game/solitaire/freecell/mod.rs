@@ -0,0 +1,8 @@
+pub mod deal;
+#[allow(clippy::module_inception)]
+pub mod freecell;
+pub mod solver;
+pub mod types;
+
+pub use freecell::FreeCell;
+pub use types::{FreeCellError, Move};
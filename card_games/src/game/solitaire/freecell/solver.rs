@@ -0,0 +1,441 @@
+//! A FreeCell solver.
+//!
+//! The solver runs a best-first search over positions, playing safe
+//! foundation moves automatically and skipping positions that only differ by
+//! the order of columns or free cells. When the whole reachable space is
+//! explored without a win the deal is proven unsolvable.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use super::{
+    freecell::FreeCell,
+    types::{Move, COLUMNS, FOUNDATIONS, FREE_CELLS},
+};
+use crate::cards::{Card, CardSet};
+
+/// Default number of distinct positions examined before giving up.
+pub const DEFAULT_MAX_POSITIONS: usize = 1_000_000;
+
+/// The result of a solver run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// The moves that win the game from the starting position, including
+    /// automatic foundation moves.
+    Solved(Vec<Move>),
+    /// Every reachable position was examined and none is a win.
+    Unsolvable,
+    /// The search limit was reached before an answer was found.
+    GaveUp { positions_explored: usize },
+}
+
+/// A configurable FreeCell solver.
+///
+/// # Example
+/// ```
+/// use card_games::game::solitaire::freecell::{solver::{Solver, SolveOutcome}, FreeCell};
+/// let mut game = FreeCell::from_deal_number(1);
+/// if let SolveOutcome::Solved(moves) = Solver::new().solve(&game) {
+///     for mv in moves {
+///         game.apply(mv).unwrap();
+///     }
+///     assert!(game.is_won());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Solver {
+    max_positions: usize,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver {
+    /// Creates a solver with [`DEFAULT_MAX_POSITIONS`].
+    pub fn new() -> Self {
+        Self {
+            max_positions: DEFAULT_MAX_POSITIONS,
+        }
+    }
+
+    /// Limits how many distinct positions are examined before giving up.
+    pub fn max_positions(mut self, max_positions: usize) -> Self {
+        self.max_positions = max_positions;
+        self
+    }
+
+    /// Searches for a winning move sequence from the game's current position.
+    pub fn solve(&self, game: &FreeCell) -> SolveOutcome {
+        let mut start = Position::from_game(game);
+        let mut opening = start.auto_play();
+        if start.is_won() {
+            return SolveOutcome::Solved(opening);
+        }
+
+        let mut seen = HashSet::new();
+        seen.insert(start.key());
+
+        let mut nodes = vec![Node {
+            parent: None,
+            moves: Vec::new(),
+        }];
+        let mut positions = vec![Some(start.clone())];
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((start.heuristic(), 0usize)));
+
+        while let Some(Reverse((_, index))) = queue.pop() {
+            let position = positions[index].take().expect("each node is expanded once");
+
+            for mv in position.moves() {
+                let mut next = position.clone();
+                next.perform(mv);
+                let mut moves = vec![mv];
+                moves.extend(next.auto_play());
+
+                if !seen.insert(next.key()) {
+                    continue;
+                }
+
+                nodes.push(Node {
+                    parent: Some(index),
+                    moves,
+                });
+
+                if next.is_won() {
+                    opening.extend(path_to(&nodes, nodes.len() - 1));
+                    return SolveOutcome::Solved(opening);
+                }
+                if seen.len() >= self.max_positions {
+                    return SolveOutcome::GaveUp {
+                        positions_explored: seen.len(),
+                    };
+                }
+
+                queue.push(Reverse((next.heuristic(), nodes.len() - 1)));
+                positions.push(Some(next));
+            }
+        }
+
+        SolveOutcome::Unsolvable
+    }
+}
+
+/// Solves the game with the default search limit.
+pub fn solve(game: &FreeCell) -> SolveOutcome {
+    Solver::new().solve(game)
+}
+
+struct Node {
+    parent: Option<usize>,
+    moves: Vec<Move>,
+}
+
+fn path_to(nodes: &[Node], mut index: usize) -> Vec<Move> {
+    let mut segments = Vec::new();
+    while let Some(parent) = nodes[index].parent {
+        segments.push(&nodes[index].moves);
+        index = parent;
+    }
+    segments.into_iter().rev().flatten().copied().collect()
+}
+
+/// A compact copy of the table used during search.
+///
+/// Cards are kept as their one-byte codes ([`Card::to_byte`]): `suit * 13 +
+/// rank`, with ranks starting at zero for the ace and suits in foundation
+/// order.
+#[derive(Debug, Clone)]
+struct Position {
+    columns: [Vec<u8>; COLUMNS],
+    cells: [Option<u8>; FREE_CELLS],
+    foundations: [u8; FOUNDATIONS],
+}
+
+fn rank(card: u8) -> u8 {
+    card % 13
+}
+
+fn suit(card: u8) -> usize {
+    (card / 13) as usize
+}
+
+fn is_red(card: u8) -> bool {
+    matches!(suit(card), 1 | 2)
+}
+
+fn stacks_on(card: u8, onto: u8) -> bool {
+    rank(card) + 1 == rank(onto) && is_red(card) != is_red(onto)
+}
+
+impl Position {
+    fn from_game(game: &FreeCell) -> Self {
+        let encode = Card::to_byte;
+
+        let mut columns: [Vec<u8>; COLUMNS] = Default::default();
+        for (column, cards) in columns.iter_mut().zip(game.columns()) {
+            *column = cards.iter().map(encode).collect();
+        }
+
+        let mut cells = [None; FREE_CELLS];
+        for (cell, card) in cells.iter_mut().zip(game.free_cells()) {
+            *cell = card.as_ref().map(encode);
+        }
+
+        let mut foundations = [0; FOUNDATIONS];
+        for (count, pile) in foundations.iter_mut().zip(game.foundations()) {
+            *count = pile.len() as u8;
+        }
+
+        Self {
+            columns,
+            cells,
+            foundations,
+        }
+    }
+
+    fn is_won(&self) -> bool {
+        self.foundations.iter().all(|&count| count == 13)
+    }
+
+    /// A key that ignores the order of columns and free cells.
+    fn key(&self) -> Vec<u8> {
+        let mut columns: Vec<&Vec<u8>> = self.columns.iter().collect();
+        columns.sort();
+
+        let cells: CardSet = self
+            .cells
+            .iter()
+            .flatten()
+            .filter_map(|&card| Card::from_byte(card))
+            .collect();
+
+        let mut key = Vec::with_capacity(64);
+        for column in columns {
+            key.extend_from_slice(column);
+            key.push(u8::MAX);
+        }
+        key.extend(cells.bits().to_le_bytes());
+        key
+    }
+
+    /// Lower is more promising: cards left to play, plus cards buried above
+    /// the next card each foundation needs, plus occupied free cells.
+    fn heuristic(&self) -> u32 {
+        let remaining = 52 - self.foundations.iter().map(|&c| c as u32).sum::<u32>();
+        let occupied = self.cells.iter().flatten().count() as u32;
+
+        let buried: u32 = (0..FOUNDATIONS)
+            .filter(|&s| self.foundations[s] < 13)
+            .map(|s| {
+                let needed = s as u8 * 13 + self.foundations[s];
+                self.columns
+                    .iter()
+                    .find_map(|column| {
+                        column
+                            .iter()
+                            .position(|&c| c == needed)
+                            .map(|at| (column.len() - at - 1) as u32)
+                    })
+                    .unwrap_or(0)
+            })
+            .sum();
+
+        remaining * 2 + buried + occupied
+    }
+
+    fn can_found(&self, card: u8) -> bool {
+        self.foundations[suit(card)] == rank(card)
+    }
+
+    fn is_safe(&self, card: u8) -> bool {
+        rank(card) <= 1
+            || (0..FOUNDATIONS)
+                .filter(|&s| matches!(s, 1 | 2) != is_red(card))
+                .all(|s| self.foundations[s] >= rank(card))
+    }
+
+    fn max_supermove(&self, to_empty_column: bool) -> usize {
+        let empty_cells = self.cells.iter().filter(|c| c.is_none()).count();
+        let empty_columns = self.columns.iter().filter(|c| c.is_empty()).count();
+        (empty_cells + 1) << empty_columns.saturating_sub(to_empty_column as usize)
+    }
+
+    fn auto_play(&mut self) -> Vec<Move> {
+        let mut played = Vec::new();
+
+        loop {
+            let safe = |card: u8| self.can_found(card) && self.is_safe(card);
+            let mv = (0..COLUMNS)
+                .find(|&from| self.columns[from].last().is_some_and(|&c| safe(c)))
+                .map(|from| Move::ColumnToFoundation { from })
+                .or_else(|| {
+                    (0..FREE_CELLS)
+                        .find(|&cell| self.cells[cell].is_some_and(safe))
+                        .map(|cell| Move::FreeCellToFoundation { cell })
+                });
+
+            match mv {
+                Some(mv) => {
+                    self.perform(mv);
+                    played.push(mv);
+                }
+                None => return played,
+            }
+        }
+    }
+
+    /// Generates moves, dropping those that are equivalent to another move
+    /// (any empty column or free cell is as good as the first one).
+    fn moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let first_empty_column = self.columns.iter().position(|c| c.is_empty());
+        let first_empty_cell = self.cells.iter().position(|c| c.is_none());
+
+        for from in 0..COLUMNS {
+            let Some(&top) = self.columns[from].last() else {
+                continue;
+            };
+
+            if self.can_found(top) {
+                moves.push(Move::ColumnToFoundation { from });
+            }
+
+            let column = &self.columns[from];
+            let mut run = 1;
+            while run < column.len()
+                && stacks_on(column[column.len() - run], column[column.len() - run - 1])
+            {
+                run += 1;
+            }
+
+            for to in 0..COLUMNS {
+                if to == from {
+                    continue;
+                }
+                match self.columns[to].last() {
+                    Some(&target) => {
+                        let max = run.min(self.max_supermove(false));
+                        for count in 1..=max {
+                            if stacks_on(column[column.len() - count], target) {
+                                moves.push(Move::ColumnToColumn { from, to, count });
+                            }
+                        }
+                    }
+                    None if Some(to) == first_empty_column && column.len() > 1 => {
+                        let max = run.min(column.len() - 1).min(self.max_supermove(true));
+                        for count in 1..=max {
+                            moves.push(Move::ColumnToColumn { from, to, count });
+                        }
+                    }
+                    None => {}
+                }
+            }
+
+            if let Some(cell) = first_empty_cell {
+                moves.push(Move::ColumnToFreeCell { from, cell });
+            }
+        }
+
+        for cell in 0..FREE_CELLS {
+            let Some(card) = self.cells[cell] else {
+                continue;
+            };
+            if self.can_found(card) {
+                moves.push(Move::FreeCellToFoundation { cell });
+            }
+            for to in 0..COLUMNS {
+                match self.columns[to].last() {
+                    Some(&target) if stacks_on(card, target) => {
+                        moves.push(Move::FreeCellToColumn { cell, to });
+                    }
+                    None if Some(to) == first_empty_column => {
+                        moves.push(Move::FreeCellToColumn { cell, to });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn perform(&mut self, mv: Move) {
+        match mv {
+            Move::ColumnToColumn { from, to, count } => {
+                let at = self.columns[from].len() - count;
+                let cards = self.columns[from].split_off(at);
+                self.columns[to].extend(cards);
+            }
+            Move::ColumnToFreeCell { from, cell } => {
+                self.cells[cell] = self.columns[from].pop();
+            }
+            Move::ColumnToFoundation { from } => {
+                let card = self.columns[from].pop().expect("generated move");
+                self.foundations[suit(card)] += 1;
+            }
+            Move::FreeCellToColumn { cell, to } => {
+                let card = self.cells[cell].take().expect("generated move");
+                self.columns[to].push(card);
+            }
+            Move::FreeCellToFoundation { cell } => {
+                let card = self.cells[cell].take().expect("generated move");
+                self.foundations[suit(card)] += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Card, Suit, Value};
+
+    #[test]
+    fn solution_replays_to_a_win() {
+        let mut game = FreeCell::from_deal_number(1);
+
+        let SolveOutcome::Solved(moves) = solve(&game) else {
+            panic!("deal #1 is solvable");
+        };
+        for mv in moves {
+            game.apply(mv).unwrap();
+        }
+
+        assert!(game.is_won());
+    }
+
+    #[test]
+    fn deal_11982_is_proven_unsolvable() {
+        let game = FreeCell::from_deal_number(11982);
+        assert_eq!(solve(&game), SolveOutcome::Unsolvable);
+    }
+
+    #[test]
+    fn search_limit_gives_up() {
+        let game = FreeCell::from_deal_number(617);
+        let outcome = Solver::new().max_positions(10).solve(&game);
+        assert!(matches!(outcome, SolveOutcome::GaveUp { .. }));
+    }
+
+    #[test]
+    fn trivial_position_is_solved_by_auto_play() {
+        let columns = Suit::standard_suits()
+            .map(|suit| {
+                let mut column: Vec<Card> = Value::standard_values()
+                    .map(|value| Card::new(suit, value))
+                    .collect();
+                column.reverse();
+                column
+            })
+            .collect();
+        let game = FreeCell::from_columns(columns).unwrap();
+
+        let SolveOutcome::Solved(moves) = solve(&game) else {
+            panic!("sorted columns are solvable");
+        };
+        assert_eq!(moves.len(), 52);
+    }
+}
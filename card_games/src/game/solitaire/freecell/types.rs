@@ -0,0 +1,139 @@
+//! Shared types for the FreeCell engine: moves, errors and table dimensions.
+
+use std::fmt::Display;
+
+use crate::cards::Suit;
+
+/// Number of tableau columns.
+pub const COLUMNS: usize = 8;
+
+/// Number of free cells.
+pub const FREE_CELLS: usize = 4;
+
+/// Number of foundation piles (one per standard suit).
+pub const FOUNDATIONS: usize = 4;
+
+/// Returns the foundation pile used for a suit, or `None` for jokers.
+///
+/// Piles are ordered clubs, diamonds, hearts, spades, matching the suit order
+/// of the Microsoft deal algorithm.
+pub fn foundation_index(suit: &Suit) -> Option<usize> {
+    match suit {
+        Suit::CLUBS => Some(0),
+        Suit::DIAMONDS => Some(1),
+        Suit::HEARTS => Some(2),
+        Suit::SPADES => Some(3),
+        Suit::JOKER => None,
+    }
+}
+
+/// A single move on the FreeCell table.
+///
+/// Column-to-column moves may carry more than one card; such "supermoves" are
+/// limited by the number of empty free cells and columns available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    ColumnToColumn {
+        from: usize,
+        to: usize,
+        count: usize,
+    },
+    ColumnToFreeCell {
+        from: usize,
+        cell: usize,
+    },
+    ColumnToFoundation {
+        from: usize,
+    },
+    FreeCellToColumn {
+        cell: usize,
+        to: usize,
+    },
+    FreeCellToFoundation {
+        cell: usize,
+    },
+}
+
+/// Formats the move in standard FreeCell notation: columns are `1`–`8`,
+/// free cells `a`–`d` and the foundations `h`.
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let column = |i: usize| (b'1' + i as u8) as char;
+        let cell = |i: usize| (b'a' + i as u8) as char;
+
+        match *self {
+            Move::ColumnToColumn { from, to, count } if count > 1 => {
+                write!(f, "{}{} ({} cards)", column(from), column(to), count)
+            }
+            Move::ColumnToColumn { from, to, .. } => write!(f, "{}{}", column(from), column(to)),
+            Move::ColumnToFreeCell { from, cell: c } => write!(f, "{}{}", column(from), cell(c)),
+            Move::ColumnToFoundation { from } => write!(f, "{}h", column(from)),
+            Move::FreeCellToColumn { cell: c, to } => write!(f, "{}{}", cell(c), column(to)),
+            Move::FreeCellToFoundation { cell: c } => write!(f, "{}h", cell(c)),
+        }
+    }
+}
+
+/// Errors returned when setting up a FreeCell game or applying a move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FreeCellError {
+    /// The deck is not a standard 52-card deck.
+    InvalidDeck,
+    /// The column index is out of range.
+    InvalidColumn(usize),
+    /// The free cell index is out of range.
+    InvalidFreeCell(usize),
+    /// There is no card at the source location.
+    EmptySource,
+    /// The target free cell already holds a card.
+    FreeCellOccupied(usize),
+    /// The card cannot be placed on the target.
+    IllegalPlacement,
+    /// The cards being moved do not form an alternating descending sequence.
+    NotASequence,
+    /// The supermove is larger than the free cells and empty columns allow.
+    TooManyCards { requested: usize, max: usize },
+}
+
+impl Display for FreeCellError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FreeCellError::InvalidDeck => write!(f, "FreeCell needs a standard 52-card deck"),
+            FreeCellError::InvalidColumn(i) => write!(f, "Column {} does not exist", i + 1),
+            FreeCellError::InvalidFreeCell(i) => write!(f, "Free cell {} does not exist", i + 1),
+            FreeCellError::EmptySource => write!(f, "There is no card to move"),
+            FreeCellError::FreeCellOccupied(i) => write!(f, "Free cell {} is occupied", i + 1),
+            FreeCellError::IllegalPlacement => write!(f, "That card cannot go there"),
+            FreeCellError::NotASequence => {
+                write!(f, "Only alternating descending sequences can be moved")
+            }
+            FreeCellError::TooManyCards { requested, max } => write!(
+                f,
+                "Cannot move {} cards at once (at most {})",
+                requested, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FreeCellError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_display_uses_standard_notation() {
+        let single = Move::ColumnToColumn {
+            from: 0,
+            to: 7,
+            count: 1,
+        };
+        assert_eq!(single.to_string(), "18");
+        assert_eq!(
+            Move::ColumnToFreeCell { from: 2, cell: 1 }.to_string(),
+            "3b"
+        );
+        assert_eq!(Move::FreeCellToFoundation { cell: 3 }.to_string(), "dh");
+    }
+}
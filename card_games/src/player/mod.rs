@@ -1,3 +1,6 @@
+#[allow(clippy::module_inception)]
 pub mod player;
+pub mod profile;
+pub mod stats;
 
 pub use player::Player;
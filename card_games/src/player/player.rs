@@ -7,15 +7,17 @@ pub struct Player {
     dealer: bool,
 }
 
-impl Player {
-    pub fn default() -> Self {
+impl Default for Player {
+    fn default() -> Self {
         Player {
             name: "CPU".to_string(),
             hand: Hand::new(),
             dealer: true,
         }
     }
+}
 
+impl Player {
     pub fn new(name: String) -> Self {
         Player {
             hand: Hand::new(),
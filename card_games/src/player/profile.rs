@@ -0,0 +1,421 @@
+//! Player profiles kept between sittings.
+//!
+//! A [`Profile`] holds a player's lifetime [`Stats`] and the stats of the
+//! current session. A [`ProfileStore`] keeps one plain-text file per
+//! profile in a directory, one `key = value` pair per line:
+//!
+//! ```text
+//! name = Alice
+//! hands = 120
+//! wins = 52
+//! net = -35
+//! time_played = 3600
+//! ```
+//!
+//! Time is in seconds and lines starting with `#` are comments. Keys
+//! missing from a file count as zero, and keys a newer version might add
+//! are skipped.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::game::blackjack::types::HandResult;
+
+use super::stats::Stats;
+
+/// The extension of profile files.
+const EXTENSION: &str = "profile";
+
+/// A named player and their results.
+///
+/// # Example
+/// ```
+/// use card_games::player::profile::Profile;
+/// let profile = Profile::new("Alice");
+/// let saved = profile.to_string();
+/// let loaded: Profile = saved.parse().unwrap();
+/// assert_eq!(loaded.name(), "Alice");
+/// assert_eq!(loaded.lifetime().hands, 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Profile {
+    name: String,
+    /// Lifetime totals before this session.
+    previous: Stats,
+    session: Stats,
+    /// When the first round of the session was dealt.
+    session_started: Option<Instant>,
+}
+
+impl Profile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self::with_history(name.into(), Stats::default())
+    }
+
+    fn with_history(name: String, previous: Stats) -> Self {
+        Profile {
+            name,
+            previous,
+            session: Stats::default(),
+            session_started: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The totals since the profile was loaded. Time counts from the
+    /// first round dealt, not from when the profile was picked.
+    pub fn session(&self) -> Stats {
+        let mut session = self.session;
+        session.time_played = self
+            .session_started
+            .map_or(Duration::ZERO, |started| started.elapsed());
+        session
+    }
+
+    /// The totals over every session, this one included.
+    pub fn lifetime(&self) -> Stats {
+        let mut lifetime = self.previous;
+        lifetime.merge(&self.session());
+        lifetime
+    }
+
+    /// Starts the session clock as a round is dealt, unless it is already
+    /// running.
+    pub fn start_round(&mut self) {
+        self.session_started.get_or_insert_with(Instant::now);
+    }
+
+    /// Adds the settled hands of one round.
+    pub fn record_round(&mut self, hands: &[HandResult]) {
+        self.session.record_round(hands);
+    }
+
+    /// Adds a decision made with a hint on screen.
+    pub fn record_decision(&mut self, followed_hint: bool) {
+        self.session.record_decision(followed_hint);
+    }
+}
+
+impl Display for Profile {
+    /// The profile file, with lifetime totals.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stats = self.lifetime();
+        writeln!(f, "name = {}", self.name)?;
+        for (key, value) in [
+            ("hands", stats.hands as i64),
+            ("wins", stats.wins as i64),
+            ("losses", stats.losses as i64),
+            ("pushes", stats.pushes as i64),
+            ("blackjacks", stats.blackjacks as i64),
+            ("doubles_won", stats.doubles_won as i64),
+            ("splits_won", stats.splits_won as i64),
+            ("biggest_win", stats.biggest_win),
+            ("net", stats.net),
+            ("time_played", stats.time_played.as_secs() as i64),
+            ("decisions", stats.decisions as i64),
+            ("correct_decisions", stats.correct_decisions as i64),
+        ] {
+            writeln!(f, "{} = {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = ProfileError;
+
+    /// Reads a profile file. The stats become the lifetime totals before
+    /// a new session.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut name = None;
+        let mut stats = Stats::default();
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || ProfileError::InvalidLine {
+                line: number + 1,
+                text: line.to_string(),
+            };
+
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim();
+            if key.trim() == "name" {
+                name = Some(value.to_string()).filter(|n| !n.is_empty());
+                continue;
+            }
+            let count = || value.parse::<u32>().map_err(|_| invalid());
+            let amount = || value.parse::<i64>().map_err(|_| invalid());
+            match key.trim() {
+                "hands" => stats.hands = count()?,
+                "wins" => stats.wins = count()?,
+                "losses" => stats.losses = count()?,
+                "pushes" => stats.pushes = count()?,
+                "blackjacks" => stats.blackjacks = count()?,
+                "doubles_won" => stats.doubles_won = count()?,
+                "splits_won" => stats.splits_won = count()?,
+                "biggest_win" => stats.biggest_win = amount()?,
+                "net" => stats.net = amount()?,
+                "time_played" => {
+                    let seconds = value.parse::<u64>().map_err(|_| invalid())?;
+                    stats.time_played = Duration::from_secs(seconds);
+                }
+                "decisions" => stats.decisions = count()?,
+                "correct_decisions" => stats.correct_decisions = count()?,
+                _ => {}
+            }
+        }
+        let name = name.ok_or(ProfileError::MissingName)?;
+        Ok(Profile::with_history(name, stats))
+    }
+}
+
+/// A directory of profile files.
+///
+/// # Example
+/// ```no_run
+/// use card_games::player::profile::ProfileStore;
+/// let store = ProfileStore::new("profiles");
+/// let profile = store.load_or_create("Alice").unwrap();
+/// // ... play ...
+/// store.save(&profile).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileStore {
+    dir: PathBuf,
+}
+
+impl ProfileStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ProfileStore { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The file holding the profile called `name`. ASCII letters, digits
+    /// and `-` are kept; every other byte of the name is written as `_`
+    /// and two hex digits, so no two names share a file.
+    pub fn path(&self, name: &str) -> PathBuf {
+        let mut stem = String::new();
+        for byte in name.bytes() {
+            match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' => stem.push(byte as char),
+                _ => stem.push_str(&format!("_{:02X}", byte)),
+            }
+        }
+        self.dir.join(stem).with_extension(EXTENSION)
+    }
+
+    /// Every saved profile, sorted by name. Files that cannot be read are
+    /// skipped and reported alongside. A directory that does not exist yet
+    /// holds no profiles.
+    pub fn list(&self) -> Result<ProfileList, ProfileError> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ProfileList::default()),
+            Err(e) => return Err(ProfileError::Io(e)),
+        };
+        let mut list = ProfileList::default();
+        for entry in entries {
+            let path = entry.map_err(ProfileError::Io)?.path();
+            if path.extension().is_none_or(|e| e != EXTENSION) {
+                continue;
+            }
+            match Self::read(&path) {
+                Ok(profile) => list.names.push(profile.name),
+                Err(e) => list.unreadable.push((path, e)),
+            }
+        }
+        list.names.sort();
+        list.unreadable.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(list)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Profile, ProfileError> {
+        Self::read(&self.path(name))
+    }
+
+    /// Loads the profile called `name`, or starts a new one if none is
+    /// saved.
+    pub fn load_or_create(&self, name: &str) -> Result<Profile, ProfileError> {
+        match self.load(name) {
+            Err(ProfileError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Profile::new(name))
+            }
+            result => result,
+        }
+    }
+
+    /// Writes the profile's lifetime totals, creating the directory if
+    /// needed.
+    pub fn save(&self, profile: &Profile) -> Result<(), ProfileError> {
+        std::fs::create_dir_all(&self.dir).map_err(ProfileError::Io)?;
+        std::fs::write(self.path(profile.name()), profile.to_string()).map_err(ProfileError::Io)
+    }
+
+    fn read(path: &Path) -> Result<Profile, ProfileError> {
+        std::fs::read_to_string(path)
+            .map_err(ProfileError::Io)?
+            .parse()
+    }
+}
+
+/// The profiles found by [`ProfileStore::list`].
+#[derive(Debug, Default)]
+pub struct ProfileList {
+    /// The names of the profiles that could be read, sorted.
+    pub names: Vec<String>,
+    /// Profile files that could not be read, and why.
+    pub unreadable: Vec<(PathBuf, ProfileError)>,
+}
+
+/// A failure reading or writing a profile.
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(std::io::Error),
+    /// A line that is not `key = value`, or whose value is not a number.
+    InvalidLine {
+        line: usize,
+        text: String,
+    },
+    /// A profile file without a `name`.
+    MissingName,
+}
+
+impl Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileError::Io(e) => write!(f, "Cannot access the profile: {}", e),
+            ProfileError::InvalidLine { line, text } => {
+                write!(f, "Line {} is not `key = value`: '{}'", line, text)
+            }
+            ProfileError::MissingName => write!(f, "The profile has no name"),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProfileError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::blackjack::types::GameResult;
+
+    fn win(net: i64) -> HandResult {
+        HandResult {
+            result: GameResult::PlayerWin,
+            bet: 10,
+            net,
+            blackjack: false,
+            doubled: false,
+            split: false,
+        }
+    }
+
+    #[test]
+    fn sessions_add_to_the_lifetime() {
+        let mut profile: Profile =
+            "name = Bob\nhands = 10\nwins = 4\nnet = -20\nbiggest_win = 30\n"
+                .parse()
+                .unwrap();
+        profile.record_round(&[win(10)]);
+        assert_eq!(profile.session().hands, 1);
+        let lifetime = profile.lifetime();
+        assert_eq!((lifetime.hands, lifetime.wins, lifetime.net), (11, 5, -10));
+        assert_eq!(lifetime.biggest_win, 30);
+
+        let reloaded: Profile = profile.to_string().parse().unwrap();
+        assert_eq!(reloaded.lifetime().hands, 11);
+        assert_eq!(reloaded.session().hands, 0);
+    }
+
+    #[test]
+    fn the_clock_starts_with_the_first_round() {
+        let mut profile = Profile::new("Alice");
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(profile.session().time_played, Duration::ZERO);
+
+        profile.start_round();
+        std::thread::sleep(Duration::from_millis(5));
+        let played = profile.session().time_played;
+        assert!(played >= Duration::from_millis(5));
+
+        profile.start_round();
+        assert!(profile.session().time_played >= played);
+    }
+
+    #[test]
+    fn bad_files_are_reported() {
+        assert!(matches!(
+            "hands = 3".parse::<Profile>(),
+            Err(ProfileError::MissingName)
+        ));
+        assert!(matches!(
+            "name = Bob\nhands = lots".parse::<Profile>(),
+            Err(ProfileError::InvalidLine { line: 2, .. })
+        ));
+        assert!("name = Bob\nfuture_key = 1".parse::<Profile>().is_ok());
+    }
+
+    #[test]
+    fn store_saves_and_lists_profiles() {
+        let dir = std::env::temp_dir().join(format!("card_games_profiles_{}", std::process::id()));
+        let store = ProfileStore::new(&dir);
+        assert!(store.list().unwrap().names.is_empty());
+
+        let mut alice = store.load_or_create("Alice").unwrap();
+        alice.record_round(&[win(25)]);
+        store.save(&alice).unwrap();
+        store.save(&Profile::new("Bob Smith")).unwrap();
+
+        assert_eq!(store.list().unwrap().names, vec!["Alice", "Bob Smith"]);
+        assert!(store.path("Bob Smith").ends_with("Bob_20Smith.profile"));
+        assert_eq!(store.load("Alice").unwrap().lifetime().biggest_win, 25);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn names_that_differ_get_their_own_files() {
+        let store = ProfileStore::new("profiles");
+        let names = ["Bob Smith", "Bob_Smith", "Bob.Smith", "Zoë", "Zo_"];
+        let paths: Vec<PathBuf> = names.iter().map(|name| store.path(name)).collect();
+        for (i, path) in paths.iter().enumerate() {
+            assert!(!paths[i + 1..].contains(path), "{:?} is shared", path);
+        }
+        assert!(store.path("Zoë").ends_with("Zo_C3_AB.profile"));
+    }
+
+    #[test]
+    fn unreadable_files_are_listed_apart() {
+        let dir =
+            std::env::temp_dir().join(format!("card_games_unreadable_{}", std::process::id()));
+        let store = ProfileStore::new(&dir);
+        store.save(&Profile::new("Alice")).unwrap();
+        std::fs::write(dir.join("broken.profile"), "hands = lots").unwrap();
+
+        let list = store.list().unwrap();
+        assert_eq!(list.names, vec!["Alice"]);
+        assert_eq!(list.unreadable.len(), 1);
+        assert!(list.unreadable[0].0.ends_with("broken.profile"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
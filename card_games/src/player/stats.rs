@@ -0,0 +1,151 @@
+//! Running totals of a player's blackjack results.
+
+use std::time::Duration;
+
+use crate::game::blackjack::types::{GameResult, HandResult};
+
+/// Totals over some stretch of play, either one session or a lifetime.
+///
+/// # Example
+/// ```
+/// use card_games::game::blackjack::types::{GameResult, HandResult};
+/// use card_games::player::stats::Stats;
+/// let mut stats = Stats::default();
+/// stats.record_round(&[HandResult {
+///     result: GameResult::PlayerWin,
+///     bet: 10,
+///     net: 15,
+///     blackjack: true,
+///     doubled: false,
+///     split: false,
+/// }]);
+/// assert_eq!(stats.blackjacks, 1);
+/// assert_eq!(stats.biggest_win, 15);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Hands played, counting each hand of a split.
+    pub hands: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub pushes: u32,
+    pub blackjacks: u32,
+    pub doubles_won: u32,
+    pub splits_won: u32,
+    /// The best net result of a single round.
+    pub biggest_win: i64,
+    pub net: i64,
+    pub time_played: Duration,
+    /// Decisions made while hints were shown.
+    pub decisions: u32,
+    /// Of those, the decisions that matched the hint.
+    pub correct_decisions: u32,
+}
+
+impl Stats {
+    /// Adds the settled hands of one round.
+    pub fn record_round(&mut self, hands: &[HandResult]) {
+        let mut round_net = 0;
+        for hand in hands {
+            self.hands += 1;
+            match hand.result {
+                GameResult::PlayerWin => {
+                    self.wins += 1;
+                    self.doubles_won += hand.doubled as u32;
+                    self.splits_won += hand.split as u32;
+                }
+                GameResult::DealerWin => self.losses += 1,
+                GameResult::Push => self.pushes += 1,
+                GameResult::Pending => {}
+            }
+            self.blackjacks += hand.blackjack as u32;
+            round_net += hand.net;
+        }
+        self.net += round_net;
+        self.biggest_win = self.biggest_win.max(round_net);
+    }
+
+    /// Adds a decision made with a hint on screen.
+    pub fn record_decision(&mut self, followed_hint: bool) {
+        self.decisions += 1;
+        self.correct_decisions += followed_hint as u32;
+    }
+
+    /// Share of settled hands won, or `None` before any hand.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.hands > 0).then(|| self.wins as f64 / self.hands as f64)
+    }
+
+    /// Share of hinted decisions that followed the hint, or `None` if no
+    /// hints were shown.
+    pub fn accuracy(&self) -> Option<f64> {
+        (self.decisions > 0).then(|| self.correct_decisions as f64 / self.decisions as f64)
+    }
+
+    /// Adds another stretch of play to these totals.
+    pub fn merge(&mut self, other: &Stats) {
+        self.hands += other.hands;
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.pushes += other.pushes;
+        self.blackjacks += other.blackjacks;
+        self.doubles_won += other.doubles_won;
+        self.splits_won += other.splits_won;
+        self.biggest_win = self.biggest_win.max(other.biggest_win);
+        self.net += other.net;
+        self.time_played += other.time_played;
+        self.decisions += other.decisions;
+        self.correct_decisions += other.correct_decisions;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(result: GameResult, net: i64, doubled: bool, split: bool) -> HandResult {
+        HandResult {
+            result,
+            bet: net.unsigned_abs() as u32,
+            net,
+            blackjack: false,
+            doubled,
+            split,
+        }
+    }
+
+    #[test]
+    fn split_hands_count_separately() {
+        let mut stats = Stats::default();
+        stats.record_round(&[
+            hand(GameResult::PlayerWin, 20, true, true),
+            hand(GameResult::DealerWin, -10, false, true),
+        ]);
+        stats.record_round(&[hand(GameResult::Push, 0, false, false)]);
+        assert_eq!(stats.hands, 3);
+        assert_eq!((stats.wins, stats.losses, stats.pushes), (1, 1, 1));
+        assert_eq!((stats.doubles_won, stats.splits_won), (1, 1));
+        assert_eq!(stats.net, 10);
+        assert_eq!(stats.biggest_win, 10);
+        assert_eq!(stats.win_rate(), Some(1.0 / 3.0));
+    }
+
+    #[test]
+    fn accuracy_counts_hinted_decisions() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.accuracy(), None);
+        stats.record_decision(true);
+        stats.record_decision(true);
+        stats.record_decision(false);
+        stats.record_decision(true);
+        assert_eq!(stats.accuracy(), Some(0.75));
+
+        let mut lifetime = Stats {
+            biggest_win: 50,
+            ..Stats::default()
+        };
+        lifetime.merge(&stats);
+        assert_eq!(lifetime.decisions, 4);
+        assert_eq!(lifetime.biggest_win, 50);
+    }
+}
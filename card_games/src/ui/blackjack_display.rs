@@ -1,5 +1,5 @@
 use crate::cards::{hand::Hand, Card};
-use crate::game::blackjack::{GameResult, BlackjackState};
+use crate::game::blackjack::{BlackjackState, GameResult};
 
 pub trait BlackjackDisplay {
     fn show_turn(&mut self, phase: &BlackjackState);
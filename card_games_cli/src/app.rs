@@ -1,67 +1,1065 @@
+use std::time::{Duration, Instant};
+
 use crossterm::event::{Event, KeyCode, KeyEvent};
 
-use card_games::game::blackjack::{blackjack::Blackjack, types::PlayerAction, view::BlackjackView};
+use card_games::{
+    bank::{
+        bank::Bank,
+        betting::{BettingSystem, SystemKind},
+        chips::Chip,
+        ledger::{RoundId, TransactionKind},
+        limits::TableLimits,
+    },
+    cards::card::Card,
+    game::{
+        baccarat::{types::BaccaratState, view::BaccaratView, Baccarat, BaccaratError, BetKind},
+        blackjack::{
+            blackjack::Blackjack,
+            rules::hand_score,
+            side_bets::SideBetKind,
+            types::{BlackjackError, BlackjackState, GameResult, PlayerAction},
+            view::{BlackjackView, VisibleCard},
+        },
+    },
+    player::profile::{Profile, ProfileStore},
+};
 
-enum AppCommand {
+use crate::{
+    config::{AnimationSpeed, Config, Key, KeyBindings, Theme},
+    history::History,
+    settings::{SettingField, Settings},
+};
+
+/// Moving through a list of choices.
+#[derive(Clone, Copy)]
+enum MenuCommand {
+    Up,
+    Down,
+    Select,
+    Back,
+}
+
+#[derive(Clone, Copy)]
+enum ProfileCommand {
+    Up,
+    Down,
+    Select,
+    NewProfile,
+    Type(char),
+    Erase,
+    Cancel,
+}
+
+#[derive(Clone, Copy)]
+enum SettingsCommand {
+    Up,
+    Down,
+    Decrease,
+    Increase,
+    Back,
+}
+
+#[derive(Clone, Copy)]
+enum TableCommand {
     Action(PlayerAction),
     NewRound,
+    CycleAutobet,
+    ToggleHints,
+    ToggleValues,
+    TogglePanel,
+    ShowStats,
+    ShowStatement,
+    /// Picks the next larger or smaller chip to bet with.
+    NextChip,
+    PreviousChip,
+    /// Adds the chip to, or takes it off, the next round's bet.
+    AddChip,
+    RemoveChip,
+    /// Places a side bet of one chip, or takes it down.
+    SideBet(SideBetKind),
+    Menu,
+}
+
+#[derive(Clone, Copy)]
+enum BaccaratCommand {
+    Bet(BetKind),
+    ClearBets,
+    Deal,
+    Menu,
+}
+
+#[derive(Clone, Copy)]
+enum StatsCommand {
+    SwitchProfile,
+    Back,
+}
+
+/// How often the screen is redrawn while nothing happens.
+const IDLE_REDRAW: Duration = Duration::from_millis(250);
+
+/// A command for the screen on show.
+enum AppCommand {
+    Menu(MenuCommand),
+    Profile(ProfileCommand),
+    Settings(SettingsCommand),
+    Table(TableCommand),
+    Baccarat(BaccaratCommand),
+    Stats(StatsCommand),
+    Quit,
+}
+
+/// The games the app can host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameKind {
+    Blackjack,
+    Baccarat,
+}
+
+impl GameKind {
+    pub const ALL: [GameKind; 2] = [GameKind::Blackjack, GameKind::Baccarat];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameKind::Blackjack => "Blackjack",
+            GameKind::Baccarat => "Baccarat",
+        }
+    }
+
+    /// The screen the game is played on.
+    fn screen(&self) -> Screen {
+        match self {
+            GameKind::Blackjack => Screen::Table,
+            GameKind::Baccarat => Screen::Baccarat,
+        }
+    }
+}
+
+/// The entries of the main menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuItem {
+    Resume,
+    Play,
+    Settings,
+    Profile,
+    Help,
     Quit,
 }
 
+impl MenuItem {
+    /// The entry's text; "play" reads "new game" while one is going.
+    pub fn label(&self, in_progress: bool) -> &'static str {
+        match self {
+            MenuItem::Resume => "Resume game",
+            MenuItem::Play if in_progress => "New game",
+            MenuItem::Play => "Play",
+            MenuItem::Settings => "Settings",
+            MenuItem::Profile => "Profile & stats",
+            MenuItem::Help => "Help",
+            MenuItem::Quit => "Quit",
+        }
+    }
+}
+
+/// What the app is showing.
+pub enum Screen {
+    /// Choosing the profile to play as. `selected` may point one past the
+    /// saved names, at "new profile".
+    ProfileSelect {
+        names: Vec<String>,
+        selected: usize,
+        /// The name being typed for a new profile.
+        new_name: Option<String>,
+    },
+    MainMenu {
+        selected: usize,
+    },
+    GameSelect {
+        selected: usize,
+    },
+    /// The rules and table for the next game.
+    Settings {
+        selected: usize,
+    },
+    Help,
+    Table,
+    /// The baccarat table and its scoreboards.
+    Baccarat,
+    /// The profile's stats, opened from the table or from the menu.
+    Stats {
+        from_table: bool,
+    },
+    /// The blackjack bank's ledger, opened from the table.
+    Statement,
+}
+
+/// A betting system choosing each round's opening bet.
+struct Autobet {
+    kind: SystemKind,
+    unit: u32,
+    system: Box<dyn BettingSystem>,
+}
+
+/// Where a round stands, as far as the best play is concerned. Within a
+/// round the shoe only changes when the player's hands take cards.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct HintKey {
+    round: RoundId,
+    phase: BlackjackState,
+    hand: usize,
+    cards: usize,
+}
+
+/// The dealer's cards being turned over one at a time.
+struct Reveal {
+    shown: usize,
+    next: Instant,
+    /// The balance before the round was settled.
+    balance: u32,
+}
+
 pub struct App {
     game: Blackjack,
+    baccarat: Baccarat,
+    /// The game last started from the menu.
+    playing: GameKind,
+    /// Whether `playing` has been started from the menu.
+    in_progress: bool,
+    settings: Settings,
+    autobet: Option<Autobet>,
+    screen: Screen,
+    store: ProfileStore,
+    profile: Option<Profile>,
+    /// The last round added to the profile's stats.
+    recorded_round: Option<RoundId>,
+    hints: bool,
+    /// Whether the expected value of each action is on show.
+    show_values: bool,
+    /// The best action for the hand in play, while hints are on.
+    hint: Option<PlayerAction>,
+    /// The expected value of each available action, while hints or the
+    /// values are on.
+    values: Vec<(PlayerAction, f64)>,
+    /// The table `values` were worked out for. Valuing a hand can take a
+    /// good part of a second, so it is only redone when the cards change.
+    hinted: Option<HintKey>,
+    /// A problem to show the player, such as a profile that would not save.
+    message: Option<String>,
+    /// The chip the blackjack bet is built with.
+    chip: Chip,
+    /// Why the last change to the blackjack bet was refused.
+    bet_error: Option<BlackjackError>,
+    /// Why the last baccarat bet or deal was refused.
+    baccarat_error: Option<BaccaratError>,
+    history: History,
+    show_panel: bool,
+    keys: KeyBindings,
+    theme: Theme,
+    animation: AnimationSpeed,
+    reveal: Option<Reveal>,
     should_quit: bool,
 }
 
 impl App {
-    pub fn new() -> Self {
-        let mut game = Blackjack::new();
-        game.start_round();
-        Self {
+    pub fn new(store: ProfileStore, config: Config) -> Self {
+        let settings = config.game.settings();
+        let game = settings.blackjack();
+        let history = History::new(game.bank().balance());
+        let chip = opening_chip(game.limits());
+        let mut app = Self {
             game,
+            baccarat: settings.baccarat(),
+            playing: GameKind::Blackjack,
+            in_progress: false,
+            settings,
+            autobet: None,
+            screen: Screen::MainMenu { selected: 0 },
+            store,
+            profile: None,
+            recorded_round: None,
+            hints: false,
+            show_values: false,
+            hint: None,
+            values: Vec::new(),
+            hinted: None,
+            message: None,
+            chip,
+            bet_error: None,
+            baccarat_error: None,
+            history,
+            show_panel: true,
+            keys: config.keys,
+            theme: config.theme,
+            animation: config.animation.speed,
+            reveal: None,
             should_quit: false,
-        }
+        };
+        app.open_profile_select();
+        app
     }
+
+    /// The table, with the dealer's hand cut short while it is being
+    /// turned over.
     pub fn view(&self) -> BlackjackView {
-        self.game.view()
+        let mut view = self.game.view();
+        if let Some(reveal) = &self.reveal {
+            view.dealer_cards.truncate(reveal.shown);
+            let cards: Vec<Card> = view
+                .dealer_cards
+                .iter()
+                .filter_map(|card| match card {
+                    VisibleCard::FaceUp(card) => Some(*card),
+                    VisibleCard::FaceDown => None,
+                })
+                .collect();
+            view.dealer_visible_score = Some(hand_score(&cards));
+            view.bank_balance = reveal.balance;
+            view.phase = BlackjackState::DealerTurn;
+            view.result = GameResult::Pending;
+            view.available_actions.clear();
+            view.can_start_new_round = false;
+        }
+        view
+    }
+
+    /// The blackjack game's bank, for its statement.
+    pub fn bank(&self) -> &Bank {
+        self.game.bank()
+    }
+
+    /// The chip the blackjack bet is built with.
+    pub fn chip(&self) -> Chip {
+        self.chip
+    }
+
+    /// The opening bet for the next blackjack round.
+    pub fn next_bet(&self) -> u32 {
+        self.game.bet()
+    }
+
+    /// The side bets placed for the next blackjack round.
+    pub fn side_bets(&self) -> &[(SideBetKind, u32)] {
+        self.game.side_bets()
+    }
+
+    pub fn bet_error(&self) -> Option<BlackjackError> {
+        self.bet_error
+    }
+
+    pub fn baccarat_view(&self) -> BaccaratView {
+        self.baccarat.view()
+    }
+
+    pub fn baccarat_error(&self) -> Option<BaccaratError> {
+        self.baccarat_error
+    }
+
+    /// What each baccarat bet key adds to its bet: the opening bet from the
+    /// settings, kept within the table limits.
+    pub fn baccarat_chip(&self) -> u32 {
+        let limits = self.baccarat.limits();
+        limits.clamp(self.settings.bet.unwrap_or(limits.min_bet))
+    }
+
+    pub fn keys(&self) -> &KeyBindings {
+        &self.keys
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn in_progress(&self) -> bool {
+        self.in_progress
+    }
+
+    /// The main menu entries, led by "resume" while a game is going.
+    pub fn menu_items(&self) -> Vec<MenuItem> {
+        let mut items = vec![
+            MenuItem::Play,
+            MenuItem::Settings,
+            MenuItem::Profile,
+            MenuItem::Help,
+            MenuItem::Quit,
+        ];
+        if self.in_progress {
+            items.insert(0, MenuItem::Resume);
+        }
+        items
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    pub fn hint(&self) -> Option<PlayerAction> {
+        self.hint
+    }
+
+    pub fn hints_enabled(&self) -> bool {
+        self.hints
+    }
+
+    /// The expected value of each available action, per unit bet, while
+    /// they are on show.
+    pub fn action_values(&self) -> Option<&[(PlayerAction, f64)]> {
+        self.show_values.then_some(self.values.as_slice())
+    }
+
+    pub fn values_enabled(&self) -> bool {
+        self.show_values
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    pub fn panel_shown(&self) -> bool {
+        self.show_panel
+    }
+
+    /// The share of the shoe dealt since the last shuffle.
+    pub fn penetration(&self) -> f64 {
+        self.game.penetration()
     }
 
     pub fn handle_event(&mut self, event: Event) {
         let command = match event {
-            Event::Key(KeyEvent { code, .. }) => Self::map_key_to_command(code),
+            Event::Key(KeyEvent { code, .. }) => self.map_key_to_command(code),
             _ => None,
         };
 
+        // A key at the table skips the dealer's cards being turned over.
+        if self.reveal.is_some() && matches!(command, Some(AppCommand::Table(_))) {
+            self.record_round();
+            return;
+        }
+
+        match command {
+            Some(AppCommand::Quit) => self.quit(),
+            Some(AppCommand::Menu(command)) => self.handle_menu(command),
+            Some(AppCommand::Profile(command)) => self.select_profile(command),
+            Some(AppCommand::Settings(command)) => self.handle_settings(command),
+            Some(AppCommand::Table(command)) => self.handle_table(command),
+            Some(AppCommand::Baccarat(command)) => self.handle_baccarat(command),
+            Some(AppCommand::Stats(command)) => self.handle_stats(command),
+            None => {}
+        }
+        self.after_change();
+    }
+
+    fn map_key_to_command(&self, code: KeyCode) -> Option<AppCommand> {
+        // Typing a name takes every key, the quit key included.
+        if let Screen::ProfileSelect {
+            new_name: Some(_), ..
+        } = self.screen
+        {
+            return match code {
+                KeyCode::Enter => Some(ProfileCommand::Select),
+                KeyCode::Esc => Some(ProfileCommand::Cancel),
+                KeyCode::Backspace => Some(ProfileCommand::Erase),
+                KeyCode::Char(c) => Some(ProfileCommand::Type(c)),
+                _ => None,
+            }
+            .map(AppCommand::Profile);
+        }
+        let keys = &self.keys;
+        if keys.quit.matches(code) {
+            return Some(AppCommand::Quit);
+        }
+
+        match &self.screen {
+            Screen::ProfileSelect { .. } => match code {
+                KeyCode::Up => Some(ProfileCommand::Up),
+                KeyCode::Down => Some(ProfileCommand::Down),
+                KeyCode::Enter => Some(ProfileCommand::Select),
+                KeyCode::Esc => Some(ProfileCommand::Cancel),
+                code => lookup(
+                    code,
+                    &[
+                        (keys.up, ProfileCommand::Up),
+                        (keys.down, ProfileCommand::Down),
+                        (keys.new_profile, ProfileCommand::NewProfile),
+                    ],
+                ),
+            }
+            .map(AppCommand::Profile),
+            Screen::MainMenu { .. } | Screen::GameSelect { .. } | Screen::Help => match code {
+                KeyCode::Up => Some(MenuCommand::Up),
+                KeyCode::Down => Some(MenuCommand::Down),
+                KeyCode::Enter => Some(MenuCommand::Select),
+                KeyCode::Esc => Some(MenuCommand::Back),
+                code => lookup(
+                    code,
+                    &[
+                        (keys.up, MenuCommand::Up),
+                        (keys.down, MenuCommand::Down),
+                        (keys.menu, MenuCommand::Back),
+                    ],
+                ),
+            }
+            .map(AppCommand::Menu),
+            Screen::Settings { .. } => match code {
+                KeyCode::Up => Some(SettingsCommand::Up),
+                KeyCode::Down => Some(SettingsCommand::Down),
+                KeyCode::Left | KeyCode::Char('-') => Some(SettingsCommand::Decrease),
+                KeyCode::Right | KeyCode::Char('+') | KeyCode::Enter => {
+                    Some(SettingsCommand::Increase)
+                }
+                KeyCode::Esc => Some(SettingsCommand::Back),
+                code => lookup(
+                    code,
+                    &[
+                        (keys.up, SettingsCommand::Up),
+                        (keys.down, SettingsCommand::Down),
+                        (keys.left, SettingsCommand::Decrease),
+                        (keys.right, SettingsCommand::Increase),
+                        (keys.menu, SettingsCommand::Back),
+                    ],
+                ),
+            }
+            .map(AppCommand::Settings),
+            Screen::Table => match code {
+                KeyCode::Esc => Some(TableCommand::Menu),
+                KeyCode::Right => Some(TableCommand::NextChip),
+                KeyCode::Left => Some(TableCommand::PreviousChip),
+                KeyCode::Char('+') => Some(TableCommand::AddChip),
+                KeyCode::Char('-') => Some(TableCommand::RemoveChip),
+                code => lookup(
+                    code,
+                    &[
+                        (keys.hit, TableCommand::Action(PlayerAction::Hit)),
+                        (keys.stay, TableCommand::Action(PlayerAction::Stay)),
+                        (keys.double, TableCommand::Action(PlayerAction::Double)),
+                        (keys.split, TableCommand::Action(PlayerAction::Split)),
+                        (keys.new_round, TableCommand::NewRound),
+                        (keys.autobet, TableCommand::CycleAutobet),
+                        (keys.hints, TableCommand::ToggleHints),
+                        (keys.values, TableCommand::ToggleValues),
+                        (keys.panel, TableCommand::TogglePanel),
+                        (keys.stats, TableCommand::ShowStats),
+                        (keys.statement, TableCommand::ShowStatement),
+                        (
+                            keys.perfect_pairs,
+                            TableCommand::SideBet(SideBetKind::PerfectPairs),
+                        ),
+                        (
+                            keys.twenty_one_plus_three,
+                            TableCommand::SideBet(SideBetKind::TwentyOnePlusThree),
+                        ),
+                        (
+                            keys.lucky_ladies,
+                            TableCommand::SideBet(SideBetKind::LuckyLadies),
+                        ),
+                        (keys.menu, TableCommand::Menu),
+                    ],
+                ),
+            }
+            .map(AppCommand::Table),
+            Screen::Baccarat => match code {
+                KeyCode::Esc => Some(BaccaratCommand::Menu),
+                code => lookup(
+                    code,
+                    &[
+                        (keys.bet_player, BaccaratCommand::Bet(BetKind::Player)),
+                        (keys.bet_banker, BaccaratCommand::Bet(BetKind::Banker)),
+                        (keys.bet_tie, BaccaratCommand::Bet(BetKind::Tie)),
+                        (keys.clear_bets, BaccaratCommand::ClearBets),
+                        (keys.new_round, BaccaratCommand::Deal),
+                        (keys.menu, BaccaratCommand::Menu),
+                    ],
+                ),
+            }
+            .map(AppCommand::Baccarat),
+            Screen::Stats { .. } => match code {
+                KeyCode::Esc => Some(StatsCommand::Back),
+                code => lookup(
+                    code,
+                    &[
+                        (keys.switch_profile, StatsCommand::SwitchProfile),
+                        (keys.stats, StatsCommand::Back),
+                        (keys.menu, StatsCommand::Back),
+                    ],
+                ),
+            }
+            .map(AppCommand::Stats),
+            Screen::Statement => match code {
+                KeyCode::Esc => Some(StatsCommand::Back),
+                code => lookup(
+                    code,
+                    &[
+                        (keys.statement, StatsCommand::Back),
+                        (keys.menu, StatsCommand::Back),
+                    ],
+                ),
+            }
+            .map(AppCommand::Stats),
+        }
+    }
+
+    /// Handles the main menu, the game select and the help screen.
+    fn handle_menu(&mut self, command: MenuCommand) {
+        let items = self.menu_items();
+        match (&mut self.screen, command) {
+            (Screen::MainMenu { selected }, MenuCommand::Up)
+            | (Screen::GameSelect { selected }, MenuCommand::Up) => {
+                *selected = selected.saturating_sub(1);
+            }
+            (Screen::MainMenu { selected }, MenuCommand::Down) => {
+                *selected = (*selected + 1).min(items.len() - 1);
+            }
+            (Screen::GameSelect { selected }, MenuCommand::Down) => {
+                *selected = (*selected + 1).min(GameKind::ALL.len() - 1);
+            }
+            (Screen::MainMenu { selected }, MenuCommand::Select) => match items[*selected] {
+                MenuItem::Resume => self.screen = self.playing.screen(),
+                MenuItem::Play => self.screen = Screen::GameSelect { selected: 0 },
+                MenuItem::Settings => self.screen = Screen::Settings { selected: 0 },
+                MenuItem::Profile => self.screen = Screen::Stats { from_table: false },
+                MenuItem::Help => self.screen = Screen::Help,
+                MenuItem::Quit => self.quit(),
+            },
+            (Screen::GameSelect { selected }, MenuCommand::Select) => {
+                let kind = GameKind::ALL[*selected];
+                self.start_game(kind);
+            }
+            (Screen::MainMenu { .. }, MenuCommand::Back) => {}
+            (_, MenuCommand::Back) => self.open_menu(),
+            _ => {}
+        }
+    }
+
+    fn handle_settings(&mut self, command: SettingsCommand) {
+        let Screen::Settings { selected } = &mut self.screen else {
+            return;
+        };
         match command {
-            Some(AppCommand::Quit) => {
-                self.should_quit = true;
+            SettingsCommand::Up => *selected = selected.saturating_sub(1),
+            SettingsCommand::Down => {
+                *selected = (*selected + 1).min(SettingField::ALL.len() - 1);
+            }
+            SettingsCommand::Decrease => {
+                SettingField::ALL[*selected].adjust(&mut self.settings, false)
+            }
+            SettingsCommand::Increase => {
+                SettingField::ALL[*selected].adjust(&mut self.settings, true)
             }
+            SettingsCommand::Back => self.open_menu(),
+        }
+    }
 
-            Some(AppCommand::NewRound) => {
+    fn handle_table(&mut self, command: TableCommand) {
+        self.bet_error = None;
+        match command {
+            TableCommand::NewRound => {
+                let round_over = self.game.state() == BlackjackState::RoundOver;
+                if let Some(autobet) = self.autobet.as_mut().filter(|_| round_over) {
+                    autobet.system.record_round(self.game.bank());
+                    let bet = self
+                        .game
+                        .limits()
+                        .clamp(autobet.system.next_bet(self.game.bank()));
+                    // A bet the bank cannot cover leaves the last one in place.
+                    let _ = self.game.set_bet(bet);
+                }
                 self.game.start_round();
+                self.start_clock();
+            }
+
+            TableCommand::CycleAutobet => {
+                self.cycle_autobet();
+            }
+
+            TableCommand::ToggleHints => {
+                self.hints = !self.hints;
             }
 
-            Some(AppCommand::Action(action)) => {
+            TableCommand::ToggleValues => {
+                self.show_values = !self.show_values;
+            }
+
+            TableCommand::TogglePanel => {
+                self.show_panel = !self.show_panel;
+            }
+
+            TableCommand::Action(action) => {
+                if let (Some(hint), Some(profile)) = (self.hint, &mut self.profile) {
+                    if self.game.view().available_actions.contains(&action) {
+                        profile.record_decision(action == hint);
+                    }
+                }
                 self.game.apply(action);
             }
 
-            None => {}
+            TableCommand::ShowStats => self.screen = Screen::Stats { from_table: true },
+
+            TableCommand::ShowStatement => self.screen = Screen::Statement,
+
+            TableCommand::NextChip => {
+                let next = Chip::ALL.iter().find(|chip| **chip > self.chip);
+                self.chip = next.copied().unwrap_or(self.chip);
+            }
+
+            TableCommand::PreviousChip => {
+                let previous = Chip::ALL.iter().rev().find(|chip| **chip < self.chip);
+                self.chip = previous.copied().unwrap_or(self.chip);
+            }
+
+            TableCommand::AddChip => {
+                let bet = self.game.bet().saturating_add(self.chip.value());
+                self.bet_error = self.game.set_bet(bet).err();
+            }
+
+            TableCommand::RemoveChip => {
+                let bet = self.game.bet().saturating_sub(self.chip.value());
+                self.bet_error = self.game.set_bet(bet).err();
+            }
+
+            TableCommand::SideBet(kind) => {
+                let placed = self.game.side_bets().iter().any(|(k, _)| *k == kind);
+                let amount = if placed { 0 } else { self.chip.value() };
+                self.bet_error = self.game.set_side_bet(kind, amount).err();
+            }
+
+            TableCommand::Menu => {
+                self.save_profile();
+                self.open_menu();
+            }
         }
     }
 
-    fn map_key_to_command(code: KeyCode) -> Option<AppCommand> {
-        match code {
-            KeyCode::Char('h') => Some(AppCommand::Action(PlayerAction::Hit)),
-            KeyCode::Char('s') => Some(AppCommand::Action(PlayerAction::Stay)),
-            KeyCode::Char('d') => Some(AppCommand::Action(PlayerAction::Double)),
-            KeyCode::Char('p') => Some(AppCommand::Action(PlayerAction::Split)),
-            KeyCode::Char('n') => Some(AppCommand::NewRound),
-            KeyCode::Char('q') => Some(AppCommand::Quit),
-            _ => None,
+    fn handle_baccarat(&mut self, command: BaccaratCommand) {
+        let result = match command {
+            BaccaratCommand::Bet(kind) => self.baccarat.place_bet(kind, self.baccarat_chip()),
+            BaccaratCommand::ClearBets => {
+                self.baccarat.clear_bets();
+                Ok(())
+            }
+            BaccaratCommand::Deal => self.deal_baccarat(),
+            BaccaratCommand::Menu => {
+                self.open_menu();
+                Ok(())
+            }
+        };
+        self.baccarat_error = result.err();
+    }
+
+    /// Deals the next coup. Once a coup is settled, dealing again puts the
+    /// same bets back on the table first.
+    fn deal_baccarat(&mut self) -> Result<(), BaccaratError> {
+        let view = self.baccarat.view();
+        if view.phase == BaccaratState::RoundOver {
+            if view.wagers.is_empty() {
+                return Err(BaccaratError::NoBets);
+            }
+            for wager in &view.wagers {
+                self.baccarat.place_bet(wager.kind, wager.amount)?;
+            }
+        }
+        self.baccarat.deal()?;
+        self.start_clock();
+        Ok(())
+    }
+
+    fn handle_stats(&mut self, command: StatsCommand) {
+        match command {
+            StatsCommand::SwitchProfile => self.open_profile_select(),
+            StatsCommand::Back => match self.screen {
+                Screen::Stats { from_table: true } | Screen::Statement => {
+                    self.screen = Screen::Table
+                }
+                _ => self.open_menu(),
+            },
+        }
+    }
+
+    /// Handles the commands of the profile select screen.
+    fn select_profile(&mut self, command: ProfileCommand) {
+        let Screen::ProfileSelect {
+            names,
+            selected,
+            new_name,
+        } = &mut self.screen
+        else {
+            return;
+        };
+
+        let name = match (command, new_name.as_mut()) {
+            (ProfileCommand::Up, None) => {
+                *selected = selected.saturating_sub(1);
+                return;
+            }
+            (ProfileCommand::Down, None) => {
+                *selected = (*selected + 1).min(names.len());
+                return;
+            }
+            (ProfileCommand::NewProfile, None) => {
+                *new_name = Some(String::new());
+                return;
+            }
+            (ProfileCommand::Select, None) if *selected == names.len() => {
+                *new_name = Some(String::new());
+                return;
+            }
+            (ProfileCommand::Select, None) => names[*selected].clone(),
+            (ProfileCommand::Cancel, Some(_)) => {
+                *new_name = None;
+                return;
+            }
+            // There is nothing to go back to until someone is playing.
+            (ProfileCommand::Cancel, None) => {
+                if self.profile.is_some() {
+                    self.open_menu();
+                }
+                return;
+            }
+            (ProfileCommand::Type(c), Some(name)) => {
+                name.push(c);
+                return;
+            }
+            (ProfileCommand::Erase, Some(name)) => {
+                name.pop();
+                return;
+            }
+            (ProfileCommand::Select, Some(name)) if !name.trim().is_empty() => {
+                name.trim().to_string()
+            }
+            _ => return,
+        };
+
+        self.save_profile();
+        match self.store.load_or_create(&name) {
+            Ok(profile) => {
+                self.profile = Some(profile);
+                self.message = None;
+                self.open_menu();
+            }
+            Err(e) => self.message = Some(e.to_string()),
+        }
+    }
+
+    fn open_menu(&mut self) {
+        self.screen = Screen::MainMenu { selected: 0 };
+    }
+
+    fn open_profile_select(&mut self) {
+        let names = match self.store.list() {
+            Ok(list) => {
+                if let Some((path, e)) = list.unreadable.first() {
+                    self.message = Some(format!("Skipped {}: {}", path.display(), e));
+                }
+                list.names
+            }
+            Err(e) => {
+                self.message = Some(e.to_string());
+                Vec::new()
+            }
+        };
+        self.screen = Screen::ProfileSelect {
+            names,
+            selected: 0,
+            new_name: None,
+        };
+    }
+
+    /// Starts a game with the current settings, ending any game in
+    /// progress.
+    fn start_game(&mut self, kind: GameKind) {
+        match kind {
+            GameKind::Blackjack => {
+                self.game = self.settings.blackjack();
+                self.history = History::new(self.game.bank().balance());
+                self.autobet = None;
+                self.recorded_round = None;
+                self.hinted = None;
+                self.chip = opening_chip(self.game.limits());
+                self.bet_error = None;
+                self.in_progress = true;
+                self.reveal = None;
+                self.screen = Screen::Table;
+                self.game.start_round();
+                self.start_clock();
+            }
+            GameKind::Baccarat => {
+                self.baccarat = self.settings.baccarat();
+                self.baccarat_error = None;
+                self.in_progress = true;
+                self.screen = Screen::Baccarat;
+            }
+        }
+        self.playing = kind;
+    }
+
+    /// Turns over the dealer's cards of a finished round, or records the
+    /// round straight away, and refreshes the hint.
+    fn after_change(&mut self) {
+        if !self.in_progress || self.playing != GameKind::Blackjack {
+            return;
+        }
+        let round = self.game.bank().round();
+        if self.game.state() == BlackjackState::RoundOver
+            && self.recorded_round != Some(round)
+            && self.reveal.is_none()
+        {
+            let dealt = self.game.view().dealer_cards.len();
+            match self.animation.delay().filter(|_| dealt > 2) {
+                Some(delay) => {
+                    let bank = self.game.bank();
+                    let staked = bank
+                        .ledger()
+                        .round(round)
+                        .filter(|t| t.kind == TransactionKind::Wager)
+                        .last()
+                        .map(|t| t.balance);
+                    self.reveal = Some(Reveal {
+                        shown: 2,
+                        next: Instant::now() + delay,
+                        balance: staked.unwrap_or(bank.balance()),
+                    });
+                }
+                None => self.record_round(),
+            }
+        }
+
+        if !self.hints && !self.show_values {
+            self.hint = None;
+            self.values.clear();
+            self.hinted = None;
+            return;
+        }
+        let view = self.game.view();
+        let key = HintKey {
+            round,
+            phase: view.phase,
+            hand: view.active_hand_index,
+            cards: view.player_hands.iter().map(|h| h.cards.len()).sum(),
+        };
+        if self.hinted != Some(key) {
+            self.values = self.game.available_action_values().unwrap_or_default();
+            self.hinted = Some(key);
+        }
+        self.hint = self
+            .values
+            .iter()
+            .filter(|_| self.hints)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(action, _)| *action);
+    }
+
+    /// Turns over the dealer's next card once its pause is up.
+    pub fn tick(&mut self) {
+        let (Some(reveal), Some(delay)) = (&mut self.reveal, self.animation.delay()) else {
+            return;
+        };
+        if Instant::now() < reveal.next {
+            return;
+        }
+        reveal.shown += 1;
+        reveal.next += delay;
+        if reveal.shown >= self.game.view().dealer_cards.len() {
+            self.record_round();
+        }
+    }
+
+    /// How long to wait for a key before the screen next changes.
+    pub fn poll_timeout(&self) -> Duration {
+        match &self.reveal {
+            Some(reveal) => reveal
+                .next
+                .saturating_duration_since(Instant::now())
+                .min(IDLE_REDRAW),
+            None => IDLE_REDRAW,
         }
     }
 
+    /// Starts the profile's session clock once a round has been dealt.
+    fn start_clock(&mut self) {
+        if let Some(profile) = &mut self.profile {
+            profile.start_round();
+        }
+    }
+
+    fn save_profile(&mut self) {
+        if let Some(profile) = &self.profile {
+            if let Err(e) = self.store.save(profile) {
+                self.message = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Ends any reveal and adds the finished round to the history and the
+    /// profile.
+    fn record_round(&mut self) {
+        self.reveal = None;
+        self.recorded_round = Some(self.game.bank().round());
+        self.history.record(self.game.bank());
+        if let Some(profile) = &mut self.profile {
+            profile.record_round(self.game.hand_results());
+        }
+        self.save_profile();
+    }
+
+    fn quit(&mut self) {
+        if self.reveal.is_some() {
+            self.record_round();
+        }
+        self.save_profile();
+        self.should_quit = true;
+    }
+
+    /// Moves autobet on to the next betting system, from off through each
+    /// system and back to off.
+    fn cycle_autobet(&mut self) {
+        let next = match &self.autobet {
+            None => Some(SystemKind::ALL[0]),
+            Some(autobet) if autobet.kind == *SystemKind::ALL.last().unwrap() => None,
+            Some(autobet) => Some(autobet.kind.next()),
+        };
+        // Each system starts from the bet in place when autobet came on.
+        let unit = match &self.autobet {
+            Some(autobet) => autobet.unit,
+            None => self.game.bet(),
+        };
+        self.autobet = next.map(|kind| Autobet {
+            kind,
+            unit,
+            system: kind.build(unit),
+        });
+    }
+
+    /// The betting system in charge of the opening bet, if any.
+    pub fn autobet(&self) -> Option<SystemKind> {
+        self.autobet.as_ref().map(|autobet| autobet.kind)
+    }
+
     pub fn should_quit(&self) -> bool {
         self.should_quit
     }
 }
+
+/// The smallest chip that covers the table minimum on its own.
+fn opening_chip(limits: TableLimits) -> Chip {
+    Chip::ALL
+        .into_iter()
+        .find(|chip| chip.value() >= limits.min_bet)
+        .unwrap_or(Chip::Thousand)
+}
+
+/// The command bound to `code`, if any.
+fn lookup<C: Copy>(code: KeyCode, bindings: &[(Key, C)]) -> Option<C> {
+    bindings
+        .iter()
+        .find(|(key, _)| key.matches(code))
+        .map(|(_, command)| *command)
+}
@@ -0,0 +1,821 @@
+//! The settings file and command-line flags.
+//!
+//! The game reads `card_games/config.toml` from the user's config
+//! directory. Every key is optional:
+//!
+//! ```toml
+//! [keys]
+//! hit = "j"
+//! stay = "k"
+//! quit = "f10"
+//!
+//! [game]
+//! decks = 6
+//! dealer_hits_soft_17 = true
+//! table = 25
+//! rounding = "up"
+//! bankroll = 2500
+//! bet = 50
+//!
+//! [theme]
+//! accent = "cyan"
+//! highlight = "#ffaf00"
+//!
+//! [animation]
+//! speed = "fast"
+//! ```
+//!
+//! Flags such as `--decks 6 --h17 --seed 42` override the file.
+
+use std::{
+    ffi::OsString,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crossterm::event::KeyCode;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use card_games::bank::{chips::Rounding, limits::TablePreset};
+
+use crate::settings::Settings;
+
+/// The usage shown by `--help`.
+pub const USAGE: &str = "\
+Usage: card_games_cli [OPTIONS]
+
+Options:
+  --config <PATH>    Read settings from PATH instead of the user config file
+  --decks <N>        Deal from a shoe of N decks (1 to 8)
+  --h17              The dealer hits soft 17
+  --s17              The dealer stands on soft 17
+  --seed <N>         Shuffle from seed N, to replay the same cards
+  --bankroll <N>     Start each game with $N (1 to 1000000)
+  --bet <N>          Open each game with a bet of $N
+  -h, --help         Show this help";
+
+/// The largest starting bankroll. Winnings on top of it stay far below
+/// what the bank can hold.
+pub const MAX_BANKROLL: u32 = 1_000_000;
+
+/// Where the settings file lives: the user's config directory when one is
+/// known, otherwise beside the game.
+pub fn default_path() -> PathBuf {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from));
+    match config {
+        Some(config) => config.join("card_games").join("config.toml"),
+        None => PathBuf::from("config.toml"),
+    }
+}
+
+/// Everything read from the settings file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub keys: KeyBindings,
+    pub game: GameConfig,
+    pub theme: Theme,
+    pub animation: Animation,
+}
+
+impl Config {
+    /// Reads the settings file at `path`. A file that does not exist gives
+    /// the defaults.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(ConfigError::Io(path.to_path_buf(), e)),
+        };
+        let config: Config = toml::from_str(&text)
+            .map_err(|e| ConfigError::Parse(path.to_path_buf(), e.to_string()))?;
+        config.keys.check()?;
+        config.game.check()?;
+        Ok(config)
+    }
+
+    /// Reads the settings file named by the flags, or the default one, and
+    /// applies the flags over it.
+    pub fn from_args(args: &Args) -> Result<Self, ConfigError> {
+        let path = args.config.clone().unwrap_or_else(default_path);
+        let mut config = Config::load(&path)?;
+        config.game.apply(args);
+        config.game.check()?;
+        Ok(config)
+    }
+}
+
+/// The keys for each command. Arrow keys, Enter, Esc, Backspace, `-` and
+/// `+` always do their usual job and cannot be bound here.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    pub hit: Key,
+    pub stay: Key,
+    pub double: Key,
+    pub split: Key,
+    pub new_round: Key,
+    pub autobet: Key,
+    pub hints: Key,
+    pub values: Key,
+    pub panel: Key,
+    pub stats: Key,
+    pub statement: Key,
+    pub menu: Key,
+    pub quit: Key,
+    pub up: Key,
+    pub down: Key,
+    pub left: Key,
+    pub right: Key,
+    pub new_profile: Key,
+    pub switch_profile: Key,
+    pub bet_player: Key,
+    pub bet_banker: Key,
+    pub bet_tie: Key,
+    pub clear_bets: Key,
+    pub perfect_pairs: Key,
+    pub twenty_one_plus_three: Key,
+    pub lucky_ladies: Key,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            hit: Key::char('h'),
+            stay: Key::char('s'),
+            double: Key::char('d'),
+            split: Key::char('p'),
+            new_round: Key::char('n'),
+            autobet: Key::char('a'),
+            hints: Key::char('t'),
+            values: Key::char('e'),
+            panel: Key::char('g'),
+            stats: Key::char('i'),
+            statement: Key::char('l'),
+            menu: Key::char('m'),
+            quit: Key::char('q'),
+            up: Key::char('k'),
+            down: Key::char('j'),
+            left: Key::char('h'),
+            right: Key::char('l'),
+            new_profile: Key::char('n'),
+            switch_profile: Key::char('p'),
+            bet_player: Key::char('p'),
+            bet_banker: Key::char('b'),
+            bet_tie: Key::char('t'),
+            clear_bets: Key::char('c'),
+            perfect_pairs: Key::char('1'),
+            twenty_one_plus_three: Key::char('2'),
+            lucky_ladies: Key::char('3'),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The keys every screen handles itself.
+    const RESERVED: [KeyCode; 9] = [
+        KeyCode::Esc,
+        KeyCode::Enter,
+        KeyCode::Backspace,
+        KeyCode::Up,
+        KeyCode::Down,
+        KeyCode::Left,
+        KeyCode::Right,
+        KeyCode::Char('-'),
+        KeyCode::Char('+'),
+    ];
+
+    /// Checks that no reserved key is bound and that no key does two
+    /// things on the same screen.
+    fn check(&self) -> Result<(), ConfigError> {
+        let screens: [&[(&'static str, Key)]; 6] = [
+            &[
+                ("hit", self.hit),
+                ("stay", self.stay),
+                ("double", self.double),
+                ("split", self.split),
+                ("new_round", self.new_round),
+                ("autobet", self.autobet),
+                ("hints", self.hints),
+                ("values", self.values),
+                ("panel", self.panel),
+                ("stats", self.stats),
+                ("statement", self.statement),
+                ("perfect_pairs", self.perfect_pairs),
+                ("twenty_one_plus_three", self.twenty_one_plus_three),
+                ("lucky_ladies", self.lucky_ladies),
+                ("menu", self.menu),
+                ("quit", self.quit),
+            ],
+            &[
+                ("up", self.up),
+                ("down", self.down),
+                ("left", self.left),
+                ("right", self.right),
+                ("menu", self.menu),
+                ("quit", self.quit),
+            ],
+            &[
+                ("up", self.up),
+                ("down", self.down),
+                ("new_profile", self.new_profile),
+                ("quit", self.quit),
+            ],
+            &[
+                ("stats", self.stats),
+                ("menu", self.menu),
+                ("switch_profile", self.switch_profile),
+                ("quit", self.quit),
+            ],
+            &[
+                ("statement", self.statement),
+                ("menu", self.menu),
+                ("quit", self.quit),
+            ],
+            &[
+                ("bet_player", self.bet_player),
+                ("bet_banker", self.bet_banker),
+                ("bet_tie", self.bet_tie),
+                ("clear_bets", self.clear_bets),
+                ("new_round", self.new_round),
+                ("menu", self.menu),
+                ("quit", self.quit),
+            ],
+        ];
+        let bound = screens.iter().flat_map(|bindings| bindings.iter());
+        if let Some((command, key)) = bound
+            .clone()
+            .find(|(_, key)| Self::RESERVED.contains(&key.0))
+        {
+            return Err(ConfigError::ReservedKey { key: *key, command });
+        }
+        for bindings in screens {
+            for (i, (first, key)) in bindings.iter().enumerate() {
+                if let Some((second, _)) = bindings[i + 1..].iter().find(|(_, k)| k == key) {
+                    return Err(ConfigError::KeyConflict {
+                        key: *key,
+                        first,
+                        second,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A key that can be bound: a character, or a named key such as `enter`,
+/// `space`, `tab` or `f1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Key(KeyCode);
+
+impl Key {
+    pub const fn char(c: char) -> Self {
+        Key(KeyCode::Char(c))
+    }
+
+    pub fn matches(&self, code: KeyCode) -> bool {
+        self.0 == code
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key::char(c));
+        }
+        let code = match s.to_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => return Err(format!("'{}' is not a key", s)),
+            },
+        };
+        Ok(Key(code))
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Display for Key {
+    /// The key as shown in the controls, such as `H`, `Space` or `F2`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c.to_uppercase()),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// The table and rules a new game starts with.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub decks: usize,
+    pub dealer_hits_soft_17: bool,
+    /// The table minimum: 5, 25 or 100.
+    pub table: u32,
+    /// How odd payouts round: `down`, `up` or `nearest`.
+    #[serde(deserialize_with = "rounding")]
+    pub rounding: Rounding,
+    pub bankroll: u32,
+    /// The opening bet, or the table minimum when unset.
+    pub bet: Option<u32>,
+    pub seed: Option<u64>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        let settings = Settings::default();
+        GameConfig {
+            decks: settings.decks,
+            dealer_hits_soft_17: settings.dealer_hits_soft_17,
+            table: settings.table.limits().min_bet,
+            rounding: settings.rounding,
+            bankroll: settings.bankroll,
+            bet: settings.bet,
+            seed: settings.seed,
+        }
+    }
+}
+
+impl GameConfig {
+    fn apply(&mut self, args: &Args) {
+        if let Some(decks) = args.decks {
+            self.decks = decks;
+        }
+        if let Some(hits) = args.dealer_hits_soft_17 {
+            self.dealer_hits_soft_17 = hits;
+        }
+        if let Some(bankroll) = args.bankroll {
+            self.bankroll = bankroll;
+        }
+        if args.bet.is_some() {
+            self.bet = args.bet;
+        }
+        if args.seed.is_some() {
+            self.seed = args.seed;
+        }
+    }
+
+    fn check(&self) -> Result<(), ConfigError> {
+        if !(1..=8).contains(&self.decks) {
+            return Err(ConfigError::Invalid(format!(
+                "decks must be 1 to 8, not {}",
+                self.decks
+            )));
+        }
+        if !(1..=MAX_BANKROLL).contains(&self.bankroll) {
+            return Err(ConfigError::Invalid(format!(
+                "bankroll must be 1 to {}, not {}",
+                MAX_BANKROLL, self.bankroll
+            )));
+        }
+        self.preset().map(|_| ())
+    }
+
+    fn preset(&self) -> Result<TablePreset, ConfigError> {
+        TablePreset::ALL
+            .into_iter()
+            .find(|preset| preset.limits().min_bet == self.table)
+            .ok_or_else(|| {
+                ConfigError::Invalid(format!("table must be 5, 25 or 100, not {}", self.table))
+            })
+    }
+
+    /// The settings for new games. The config must have passed its checks.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            decks: self.decks,
+            dealer_hits_soft_17: self.dealer_hits_soft_17,
+            table: self.preset().unwrap_or(TablePreset::FiveDollar),
+            rounding: self.rounding,
+            bankroll: self.bankroll,
+            bet: self.bet,
+            seed: self.seed,
+        }
+    }
+}
+
+fn rounding<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Rounding, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    match name.as_str() {
+        "down" => Ok(Rounding::Down),
+        "up" => Ok(Rounding::Up),
+        "nearest" => Ok(Rounding::Nearest),
+        _ => Err(serde::de::Error::custom(format!(
+            "rounding must be down, up or nearest, not '{}'",
+            name
+        ))),
+    }
+}
+
+/// The colours of the interface. Each is a name such as `cyan` or
+/// `light-blue`, or `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Controls, key hints and the shoe gauge.
+    #[serde(deserialize_with = "color")]
+    pub accent: Color,
+    /// The selected entry, the hand in play and the bet.
+    #[serde(deserialize_with = "color")]
+    pub highlight: Color,
+    /// Entries not selected and labels.
+    #[serde(deserialize_with = "color")]
+    pub muted: Color,
+    /// The balance and the bankroll chart.
+    #[serde(deserialize_with = "color")]
+    pub positive: Color,
+    /// Problems to report.
+    #[serde(deserialize_with = "color")]
+    pub negative: Color,
+    /// Hints and the autobet system.
+    #[serde(deserialize_with = "color")]
+    pub info: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            muted: Color::Gray,
+            positive: Color::Green,
+            negative: Color::Red,
+            info: Color::Magenta,
+        }
+    }
+}
+
+fn color<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    name.parse()
+        .map_err(|_| serde::de::Error::custom(format!("'{}' is not a colour", name)))
+}
+
+/// How the dealer's cards are turned over at the end of a round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Animation {
+    pub speed: AnimationSpeed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationSpeed {
+    /// Every card at once.
+    Off,
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl AnimationSpeed {
+    /// The pause before each of the dealer's cards, if any.
+    pub fn delay(&self) -> Option<Duration> {
+        match self {
+            AnimationSpeed::Off => None,
+            AnimationSpeed::Slow => Some(Duration::from_millis(700)),
+            AnimationSpeed::Normal => Some(Duration::from_millis(400)),
+            AnimationSpeed::Fast => Some(Duration::from_millis(150)),
+        }
+    }
+}
+
+/// The command-line flags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    pub config: Option<PathBuf>,
+    pub decks: Option<usize>,
+    pub dealer_hits_soft_17: Option<bool>,
+    pub seed: Option<u64>,
+    pub bankroll: Option<u32>,
+    pub bet: Option<u32>,
+    pub help: bool,
+}
+
+impl Args {
+    /// Reads the flags, without the program name.
+    pub fn parse(args: impl IntoIterator<Item = OsString>) -> Result<Self, ConfigError> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.to_string_lossy().into_owned();
+            let mut value = || {
+                args.next()
+                    .map(|v| v.to_string_lossy().into_owned())
+                    .ok_or_else(|| ConfigError::Argument(format!("{} needs a value", arg)))
+            };
+            match arg.as_str() {
+                "--config" => parsed.config = Some(PathBuf::from(value()?)),
+                "--decks" => parsed.decks = Some(number(&arg, &value()?)?),
+                "--h17" => parsed.dealer_hits_soft_17 = Some(true),
+                "--s17" => parsed.dealer_hits_soft_17 = Some(false),
+                "--seed" => parsed.seed = Some(number(&arg, &value()?)?),
+                "--bankroll" => {
+                    let bankroll = number(&arg, &value()?)?;
+                    if !(1..=MAX_BANKROLL).contains(&bankroll) {
+                        return Err(ConfigError::Argument(format!(
+                            "--bankroll must be 1 to {}, not {}",
+                            MAX_BANKROLL, bankroll
+                        )));
+                    }
+                    parsed.bankroll = Some(bankroll);
+                }
+                "--bet" => parsed.bet = Some(number(&arg, &value()?)?),
+                "-h" | "--help" => parsed.help = true,
+                _ => return Err(ConfigError::Argument(format!("unknown flag '{}'", arg))),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn number<T: FromStr>(flag: &str, value: &str) -> Result<T, ConfigError> {
+    value
+        .parse()
+        .map_err(|_| ConfigError::Argument(format!("{} needs a number, not '{}'", flag, value)))
+}
+
+/// A settings file or flag that cannot be used.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, std::io::Error),
+    /// A file that is not valid TOML, or has keys or values the game
+    /// does not know.
+    Parse(PathBuf, String),
+    /// Two commands bound to the same key on one screen.
+    KeyConflict {
+        key: Key,
+        first: &'static str,
+        second: &'static str,
+    },
+    /// A key the screens keep for themselves, such as Esc.
+    ReservedKey {
+        key: Key,
+        command: &'static str,
+    },
+    /// A value out of range.
+    Invalid(String),
+    Argument(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "Cannot read {}: {}", path.display(), e),
+            ConfigError::Parse(path, e) => write!(f, "Cannot use {}: {}", path.display(), e),
+            ConfigError::KeyConflict { key, first, second } => {
+                write!(f, "'{}' is bound to both {} and {}", key, first, second)
+            }
+            ConfigError::ReservedKey { key, command } => {
+                write!(
+                    f,
+                    "'{}' cannot be bound to {}: it is kept for menus",
+                    key, command
+                )
+            }
+            ConfigError::Invalid(message) => write!(f, "Invalid setting: {}", message),
+            ConfigError::Argument(message) => write!(f, "Invalid flag: {}\n\n{}", message, USAGE),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(flags: &[&str]) -> Result<Args, ConfigError> {
+        Args::parse(flags.iter().map(OsString::from))
+    }
+
+    fn temp_file(name: &str, text: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("card_games_{}_{}", std::process::id(), name));
+        std::fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn keys_parse_and_print() {
+        assert_eq!("x".parse::<Key>(), Ok(Key::char('x')));
+        assert_eq!("Space".parse::<Key>(), Ok(Key::char(' ')));
+        assert_eq!("f2".parse::<Key>(), Ok(Key(KeyCode::F(2))));
+        assert_eq!("ESC".parse::<Key>(), Ok(Key(KeyCode::Esc)));
+        assert!("f13".parse::<Key>().is_err());
+        assert!("hyper".parse::<Key>().is_err());
+
+        assert_eq!(Key::char('h').to_string(), "H");
+        assert_eq!(Key::char(' ').to_string(), "Space");
+        assert_eq!(Key(KeyCode::F(2)).to_string(), "F2");
+    }
+
+    #[test]
+    fn flags_are_read() {
+        let parsed = args(&["--decks", "6", "--h17", "--seed", "42", "--bet", "25"]).unwrap();
+        assert_eq!(parsed.decks, Some(6));
+        assert_eq!(parsed.dealer_hits_soft_17, Some(true));
+        assert_eq!(parsed.seed, Some(42));
+        assert_eq!(parsed.bet, Some(25));
+        assert_eq!(parsed.bankroll, None);
+
+        // The last of --h17 and --s17 wins.
+        let parsed = args(&["--h17", "--s17"]).unwrap();
+        assert_eq!(parsed.dealer_hits_soft_17, Some(false));
+        assert!(args(&["-h"]).unwrap().help);
+    }
+
+    #[test]
+    fn bad_flags_are_refused() {
+        for flags in [
+            &["--decks"][..],
+            &["--decks", "six"],
+            &["--turbo"],
+            &["--bankroll", "0"],
+            &["--bankroll", "4294967295"],
+        ] {
+            assert!(
+                matches!(args(flags), Err(ConfigError::Argument(_))),
+                "{:?}",
+                flags
+            );
+        }
+    }
+
+    #[test]
+    fn flags_override_the_file() {
+        let mut game = GameConfig::default();
+        game.apply(&args(&["--decks", "4", "--h17", "--bankroll", "500"]).unwrap());
+        assert_eq!(game.decks, 4);
+        assert!(game.dealer_hits_soft_17);
+        assert_eq!(game.bankroll, 500);
+        assert_eq!(game.table, GameConfig::default().table);
+    }
+
+    #[test]
+    fn a_key_may_not_do_two_things_on_one_screen() {
+        assert!(KeyBindings::default().check().is_ok());
+
+        let clash = KeyBindings {
+            stay: Key::char('h'),
+            ..KeyBindings::default()
+        };
+        assert!(matches!(
+            clash.check(),
+            Err(ConfigError::KeyConflict {
+                first: "hit",
+                second: "stay",
+                ..
+            })
+        ));
+
+        // Quit works on every screen, so it may not take a menu key.
+        let quit_on_up = KeyBindings {
+            quit: Key::char('k'),
+            ..KeyBindings::default()
+        };
+        assert!(matches!(
+            quit_on_up.check(),
+            Err(ConfigError::KeyConflict { .. })
+        ));
+    }
+
+    #[test]
+    fn reserved_keys_may_not_be_bound() {
+        for key in ["esc", "enter", "up", "left", "backspace", "-", "+"] {
+            let bindings = KeyBindings {
+                quit: key.parse().unwrap(),
+                ..KeyBindings::default()
+            };
+            assert!(
+                matches!(
+                    bindings.check(),
+                    Err(ConfigError::ReservedKey {
+                        command: "quit",
+                        ..
+                    })
+                ),
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn game_settings_are_range_checked() {
+        assert!(GameConfig::default().check().is_ok());
+        for game in [
+            GameConfig {
+                decks: 0,
+                ..GameConfig::default()
+            },
+            GameConfig {
+                decks: 9,
+                ..GameConfig::default()
+            },
+            GameConfig {
+                table: 10,
+                ..GameConfig::default()
+            },
+            GameConfig {
+                bankroll: 0,
+                ..GameConfig::default()
+            },
+            GameConfig {
+                bankroll: MAX_BANKROLL + 1,
+                ..GameConfig::default()
+            },
+        ] {
+            assert!(matches!(game.check(), Err(ConfigError::Invalid(_))));
+        }
+    }
+
+    #[test]
+    fn a_missing_file_gives_the_defaults() {
+        let path = std::env::temp_dir().join("card_games_no_such_config.toml");
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.keys, KeyBindings::default());
+        assert_eq!(config.game, GameConfig::default());
+    }
+
+    #[test]
+    fn files_are_read() {
+        let path = temp_file(
+            "good.toml",
+            "[keys]\nquit = \"f10\"\n[game]\ndecks = 6\nrounding = \"nearest\"\n\
+             [theme]\naccent = \"#ffaf00\"\n[animation]\nspeed = \"off\"\n",
+        );
+        let config = Config::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.keys.quit, Key(KeyCode::F(10)));
+        assert_eq!(config.game.decks, 6);
+        assert_eq!(config.game.rounding, Rounding::Nearest);
+        assert_eq!(config.theme.accent, Color::Rgb(0xff, 0xaf, 0x00));
+        assert_eq!(config.animation.speed, AnimationSpeed::Off);
+    }
+
+    #[test]
+    fn bad_files_are_reported() {
+        for (name, text) in [
+            ("syntax.toml", "[game\n"),
+            ("unknown.toml", "[game]\nturbo = true\n"),
+            ("rounding.toml", "[game]\nrounding = \"sideways\"\n"),
+            ("colour.toml", "[theme]\naccent = \"plaid\"\n"),
+        ] {
+            let path = temp_file(name, text);
+            let result = Config::load(&path);
+            std::fs::remove_file(&path).unwrap();
+            assert!(matches!(result, Err(ConfigError::Parse(..))), "{}", name);
+        }
+
+        let path = temp_file("decks.toml", "[game]\ndecks = 12\n");
+        let result = Config::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(ConfigError::Invalid(_))));
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+
+use card_games::bank::{bank::Bank, betting::Outcome};
+
+/// How many balances the history keeps.
+const CAPACITY: usize = 500;
+
+/// A rolling record of the rounds played this session, for the side
+/// panel.
+pub struct History {
+    /// The balance before the first round and after each one since,
+    /// oldest first.
+    balances: VecDeque<u32>,
+    rounds: u32,
+    wins: u32,
+    losses: u32,
+    pushes: u32,
+    /// Rounds won in a row when positive, lost in a row when negative.
+    /// Pushes leave it alone.
+    streak: i32,
+}
+
+impl History {
+    pub fn new(balance: u32) -> Self {
+        Self {
+            balances: VecDeque::from([balance]),
+            rounds: 0,
+            wins: 0,
+            losses: 0,
+            pushes: 0,
+            streak: 0,
+        }
+    }
+
+    /// Adds the bank's latest round, judged by its net.
+    pub fn record(&mut self, bank: &Bank) {
+        let summary = bank.ledger().round_summary(bank.round());
+        self.rounds += 1;
+        match Outcome::from_summary(&summary) {
+            Outcome::Win => {
+                self.wins += 1;
+                self.streak = self.streak.max(0) + 1;
+            }
+            Outcome::Loss => {
+                self.losses += 1;
+                self.streak = self.streak.min(0) - 1;
+            }
+            Outcome::Push => self.pushes += 1,
+        }
+
+        if self.balances.len() == CAPACITY {
+            self.balances.pop_front();
+        }
+        self.balances.push_back(bank.balance());
+    }
+
+    /// The most recent balances, oldest first, at most `count` of them.
+    pub fn balances(&self, count: usize) -> impl Iterator<Item = u32> + '_ {
+        let skip = self.balances.len().saturating_sub(count);
+        self.balances.iter().skip(skip).copied()
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn wins(&self) -> u32 {
+        self.wins
+    }
+
+    pub fn losses(&self) -> u32 {
+        self.losses
+    }
+
+    pub fn pushes(&self) -> u32 {
+        self.pushes
+    }
+
+    /// Share of rounds won, or `None` before the first round.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.rounds > 0).then(|| self.wins as f64 / self.rounds as f64)
+    }
+
+    pub fn streak(&self) -> i32 {
+        self.streak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays a round staking 10 and getting `returned` back.
+    fn play(bank: &mut Bank, history: &mut History, returned: u32) {
+        bank.start_round();
+        bank.wager(10).unwrap();
+        if returned > 0 {
+            bank.payout(returned).unwrap();
+        }
+        history.record(bank);
+    }
+
+    #[test]
+    fn streaks_count_wins_and_losses_in_a_row() {
+        let mut bank = Bank::new(1_000);
+        let mut history = History::new(bank.balance());
+
+        play(&mut bank, &mut history, 20);
+        play(&mut bank, &mut history, 20);
+        assert_eq!(history.streak(), 2);
+
+        // A push leaves the streak alone.
+        play(&mut bank, &mut history, 10);
+        assert_eq!(history.streak(), 2);
+
+        play(&mut bank, &mut history, 0);
+        assert_eq!(history.streak(), -1);
+        play(&mut bank, &mut history, 0);
+        assert_eq!(history.streak(), -2);
+
+        assert_eq!(
+            (
+                history.rounds(),
+                history.wins(),
+                history.losses(),
+                history.pushes()
+            ),
+            (5, 2, 2, 1)
+        );
+        assert_eq!(history.win_rate(), Some(0.4));
+    }
+
+    #[test]
+    fn only_the_latest_balances_are_kept() {
+        let mut bank = Bank::new(10_000);
+        let mut history = History::new(bank.balance());
+        assert_eq!(history.win_rate(), None);
+
+        for _ in 0..CAPACITY + 10 {
+            play(&mut bank, &mut history, 0);
+        }
+        let balances: Vec<u32> = history.balances(usize::MAX).collect();
+        assert_eq!(balances.len(), CAPACITY);
+        assert_eq!(balances.last(), Some(&bank.balance()));
+        assert_eq!(balances[0], 10_000 - 11 * 10);
+
+        let recent: Vec<u32> = history.balances(2).collect();
+        assert_eq!(recent, vec![bank.balance() + 10, bank.balance()]);
+    }
+}
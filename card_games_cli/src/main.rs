@@ -4,20 +4,56 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::{self, Stdout};
+use std::{io, path::PathBuf};
 
 mod app;
+mod config;
+mod history;
+mod settings;
 mod ui;
 
 use app::App;
+use card_games::player::profile::ProfileStore;
+use config::{Args, Config};
+
+/// Where profiles are kept: the user's data directory when one is known,
+/// otherwise a `profiles` directory beside the game.
+fn profile_dir() -> PathBuf {
+    let data = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from));
+    match data {
+        Some(data) => data.join("card_games").join("profiles"),
+        None => PathBuf::from("profiles"),
+    }
+}
 
 fn main() -> anyhow::Result<()> {
+    let args = Args::parse(std::env::args_os().skip(1))?;
+    if args.help {
+        println!("{}", config::USAGE);
+        return Ok(());
+    }
+    let config = Config::from_args(&args)?;
+
+    install_panic_hook();
     setup_terminal()?;
-    let result = run_app();
+    let result = run_app(config);
     restore_terminal()?;
     result
 }
 
+/// Puts the terminal back before a panic is reported, so that the message
+/// can be read and the shell is left usable.
+fn install_panic_hook() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        hook(info);
+    }));
+}
+
 fn setup_terminal() -> anyhow::Result<()> {
     enable_raw_mode()?;
 
@@ -34,28 +70,27 @@ fn restore_terminal() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn run_app() -> anyhow::Result<()> {
+fn run_app(config: Config) -> anyhow::Result<()> {
     let stdout = io::stdout();
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new();
+    let mut app = App::new(ProfileStore::new(profile_dir()), config);
 
     loop {
-        let view = app.view();
-
         terminal.draw(|frame| {
-            ui::draw(frame, &view);
+            ui::draw(frame, &app);
         })?;
 
         if app.should_quit() {
             break;
         }
 
-        if crossterm::event::poll(std::time::Duration::from_millis(250))? {
+        if crossterm::event::poll(app.poll_timeout())? {
             let event = crossterm::event::read()?;
             app.handle_event(event);
         }
+        app.tick();
     }
 
     Ok(())
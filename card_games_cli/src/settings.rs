@@ -0,0 +1,237 @@
+use card_games::{
+    bank::{
+        chips::{PayoutRule, Rounding},
+        limits::TablePreset,
+    },
+    game::{
+        baccarat::{Baccarat, Commission},
+        blackjack::blackjack::Blackjack,
+    },
+};
+
+/// Deck counts offered on the settings screen.
+const DECKS: [usize; 5] = [1, 2, 4, 6, 8];
+
+/// Starting bankrolls offered on the settings screen.
+const BANKROLLS: [u32; 6] = [250, 500, 1_000, 2_500, 5_000, 10_000];
+
+/// How the next game is set up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub decks: usize,
+    pub dealer_hits_soft_17: bool,
+    pub table: TablePreset,
+    /// How odd payouts, such as 3:2 on a $5 bet, round to whole dollars.
+    pub rounding: Rounding,
+    pub bankroll: u32,
+    /// The opening bet, or the table minimum when unset.
+    pub bet: Option<u32>,
+    /// Shuffles from this seed when set, so a game can be replayed.
+    pub seed: Option<u64>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            decks: 1,
+            dealer_hits_soft_17: false,
+            table: TablePreset::FiveDollar,
+            rounding: Rounding::Down,
+            bankroll: 1_000,
+            bet: None,
+            seed: None,
+        }
+    }
+}
+
+impl Settings {
+    /// A new blackjack game with these settings.
+    pub fn blackjack(&self) -> Blackjack {
+        let game = Blackjack::new()
+            .with_decks(self.decks)
+            .with_dealer_hits_soft_17(self.dealer_hits_soft_17)
+            .with_limits(self.table.limits())
+            .with_payout_rule(PayoutRule::new(1, self.rounding))
+            .with_bankroll(self.bankroll);
+        let mut game = match self.seed {
+            Some(seed) => game.with_seed(seed),
+            None => game,
+        };
+        if let Some(bet) = self.bet {
+            // A bet the bankroll cannot cover leaves the table minimum.
+            let _ = game.set_bet(self.table.limits().clamp(bet));
+        }
+        game
+    }
+
+    /// A new baccarat game with these settings. Baccarat has no soft 17
+    /// or odd payouts, so those settings are left out.
+    pub fn baccarat(&self) -> Baccarat {
+        Baccarat::with_rules(self.decks, Commission::Standard)
+            .with_limits(self.table.limits())
+            .with_bankroll(self.bankroll)
+    }
+}
+
+/// One line of the settings screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingField {
+    Decks,
+    SoftSeventeen,
+    Table,
+    Rounding,
+    Bankroll,
+    Seed,
+}
+
+impl SettingField {
+    pub const ALL: [SettingField; 6] = [
+        SettingField::Decks,
+        SettingField::SoftSeventeen,
+        SettingField::Table,
+        SettingField::Rounding,
+        SettingField::Bankroll,
+        SettingField::Seed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingField::Decks => "Decks",
+            SettingField::SoftSeventeen => "Dealer on soft 17",
+            SettingField::Table => "Table",
+            SettingField::Rounding => "Odd payouts",
+            SettingField::Bankroll => "Starting bankroll",
+            SettingField::Seed => "Shuffle seed",
+        }
+    }
+
+    pub fn value(&self, settings: &Settings) -> String {
+        match self {
+            SettingField::Decks => settings.decks.to_string(),
+            SettingField::SoftSeventeen => match settings.dealer_hits_soft_17 {
+                true => "hits".to_string(),
+                false => "stands".to_string(),
+            },
+            SettingField::Table => settings.table.to_string(),
+            SettingField::Rounding => settings.rounding.to_string(),
+            SettingField::Bankroll => format!("${}", settings.bankroll),
+            SettingField::Seed => match settings.seed {
+                Some(seed) => seed.to_string(),
+                None => "random".to_string(),
+            },
+        }
+    }
+
+    /// Moves the setting one step forward, or back when `forward` is
+    /// false. Lists of choices wrap around; the seed steps down from 0 to
+    /// random.
+    pub fn adjust(&self, settings: &mut Settings, forward: bool) {
+        match self {
+            SettingField::Decks => settings.decks = step(&DECKS, &settings.decks, forward),
+            SettingField::SoftSeventeen => {
+                settings.dealer_hits_soft_17 = !settings.dealer_hits_soft_17
+            }
+            SettingField::Table => {
+                settings.table = step(&TablePreset::ALL, &settings.table, forward)
+            }
+            SettingField::Rounding => {
+                settings.rounding = step(&Rounding::ALL, &settings.rounding, forward)
+            }
+            SettingField::Bankroll => {
+                settings.bankroll = step(&BANKROLLS, &settings.bankroll, forward)
+            }
+            SettingField::Seed => {
+                settings.seed = match (settings.seed, forward) {
+                    (None, true) => Some(0),
+                    (None, false) => None,
+                    (Some(0), false) => None,
+                    (Some(seed), true) => Some(seed.saturating_add(1)),
+                    (Some(seed), false) => Some(seed - 1),
+                }
+            }
+        }
+    }
+}
+
+/// The choice after or before `current`, wrapping around. A value not in
+/// the list moves to the first choice.
+fn step<T: Copy + PartialEq>(choices: &[T], current: &T, forward: bool) -> T {
+    let len = choices.len();
+    match choices.iter().position(|c| c == current) {
+        Some(i) if forward => choices[(i + 1) % len],
+        Some(i) => choices[(i + len - 1) % len],
+        None => choices[0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_wraps_around_and_resets_unknown_values() {
+        assert_eq!(step(&DECKS, &1, true), 2);
+        assert_eq!(step(&DECKS, &8, true), 1);
+        assert_eq!(step(&DECKS, &1, false), 8);
+        assert_eq!(step(&DECKS, &3, false), 1);
+    }
+
+    #[test]
+    fn adjust_moves_each_setting() {
+        let mut settings = Settings::default();
+
+        SettingField::Decks.adjust(&mut settings, true);
+        assert_eq!(settings.decks, 2);
+        SettingField::SoftSeventeen.adjust(&mut settings, false);
+        assert!(settings.dealer_hits_soft_17);
+        SettingField::Table.adjust(&mut settings, false);
+        assert_eq!(settings.table, *TablePreset::ALL.last().unwrap());
+        SettingField::Rounding.adjust(&mut settings, true);
+        assert_eq!(settings.rounding, Rounding::Up);
+        SettingField::Bankroll.adjust(&mut settings, true);
+        assert_eq!(settings.bankroll, 2_500);
+
+        assert_eq!(SettingField::Decks.value(&settings), "2");
+        assert_eq!(SettingField::SoftSeventeen.value(&settings), "hits");
+        assert_eq!(SettingField::Bankroll.value(&settings), "$2500");
+    }
+
+    #[test]
+    fn the_seed_steps_between_random_and_numbers() {
+        let mut settings = Settings::default();
+        SettingField::Seed.adjust(&mut settings, false);
+        assert_eq!(settings.seed, None);
+        SettingField::Seed.adjust(&mut settings, true);
+        assert_eq!(settings.seed, Some(0));
+        SettingField::Seed.adjust(&mut settings, true);
+        assert_eq!(settings.seed, Some(1));
+        SettingField::Seed.adjust(&mut settings, false);
+        SettingField::Seed.adjust(&mut settings, false);
+        assert_eq!(settings.seed, None);
+        assert_eq!(SettingField::Seed.value(&settings), "random");
+    }
+
+    #[test]
+    fn games_start_with_the_settings() {
+        let settings = Settings {
+            decks: 6,
+            dealer_hits_soft_17: true,
+            table: TablePreset::ALL[1],
+            bet: Some(1),
+            ..Settings::default()
+        };
+        let game = settings.blackjack();
+        assert_eq!(game.decks(), 6);
+        assert!(game.dealer_hits_soft_17());
+        assert_eq!(game.bet(), TablePreset::ALL[1].limits().min_bet);
+        assert_eq!(game.bank().balance(), 1_000);
+
+        let baccarat = Settings {
+            bankroll: 2_500,
+            ..settings
+        }
+        .baccarat();
+        assert_eq!(baccarat.limits(), TablePreset::ALL[1].limits());
+        assert_eq!(baccarat.bank().balance(), 2_500);
+    }
+}
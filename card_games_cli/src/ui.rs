@@ -2,17 +2,287 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Gauge, Paragraph, Sparkline},
     Frame,
 };
 
-use card_games::game::blackjack::{
-    types::{GameResult, PlayerAction},
-    view::{BlackjackView, VisibleCard},
+use card_games::{
+    bank::{chips::ChipStack, ledger::TransactionKind},
+    cards::card::Card,
+    game::{
+        baccarat::{
+            scoreboard::{BigRoad, ROAD_ROWS},
+            types::{BaccaratState, BetKind},
+            view::BaccaratView,
+            Outcome,
+        },
+        blackjack::{
+            side_bets::SideBetKind,
+            types::{GameResult, PlayerAction},
+            view::{BlackjackView, VisibleCard},
+        },
+    },
+    player::stats::Stats,
+};
+
+use crate::{
+    app::{App, GameKind, Screen},
+    config::{Key, Theme},
+    settings::SettingField,
 };
 
 /// Entry point called from `terminal.draw(|f| ...)`
-pub fn draw(f: &mut Frame, view: &BlackjackView) {
+pub fn draw(f: &mut Frame, app: &App) {
+    match app.screen() {
+        Screen::ProfileSelect {
+            names,
+            selected,
+            new_name,
+        } => draw_profile_select(f, f.area(), app, names, *selected, new_name.as_deref()),
+        Screen::MainMenu { selected } => {
+            let entries = app
+                .menu_items()
+                .iter()
+                .map(|item| item.label(app.in_progress()).to_string())
+                .collect::<Vec<_>>();
+            let heading = match app.profile() {
+                Some(profile) => format!("Card Games - playing as {}", profile.name()),
+                None => "Card Games".to_string(),
+            };
+            let keys = app.keys();
+            draw_menu(
+                f,
+                app,
+                "Menu",
+                &heading,
+                &entries,
+                *selected,
+                &format!(
+                    "[Up/Down] Choose   [Enter] Select   {}",
+                    key_hint(keys.quit, "Quit")
+                ),
+            );
+        }
+        Screen::GameSelect { selected } => {
+            let entries = GameKind::ALL
+                .iter()
+                .map(|kind| kind.name().to_string())
+                .collect::<Vec<_>>();
+            let keys = app.keys();
+            draw_menu(
+                f,
+                app,
+                "Games",
+                "Choose a game",
+                &entries,
+                *selected,
+                &format!(
+                    "[Up/Down] Choose   [Enter] Play   [Esc] Back   {}",
+                    key_hint(keys.quit, "Quit")
+                ),
+            );
+        }
+        Screen::Settings { selected } => draw_settings(f, f.area(), app, *selected),
+        Screen::Help => draw_help(f, f.area(), app),
+        Screen::Table => draw_table(f, app),
+        Screen::Baccarat => draw_baccarat(f, app),
+        Screen::Stats { .. } => draw_stats(f, f.area(), app),
+        Screen::Statement => draw_statement(f, f.area(), app),
+    }
+}
+
+/// A titled list with one entry highlighted, filling the screen.
+fn draw_menu(
+    f: &mut Frame,
+    app: &App,
+    title: &str,
+    heading: &str,
+    entries: &[String],
+    selected: usize,
+    footer: &str,
+) {
+    let theme = app.theme();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            heading.to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::raw(""),
+    ];
+    for (i, entry) in entries.iter().enumerate() {
+        lines.push(menu_line(entry, i == selected, theme));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(Span::styled(
+        footer.to_string(),
+        Style::default().fg(theme.accent),
+    )));
+
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), f.area());
+}
+
+/// One entry of a list, marked and highlighted when selected.
+fn menu_line(text: &str, selected: bool, theme: &Theme) -> Line<'static> {
+    let (prefix, style) = if selected {
+        (
+            "> ",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        ("  ", Style::default().fg(theme.muted))
+    };
+    Line::from(Span::styled(format!("{}{}", prefix, text), style))
+}
+
+fn draw_settings(f: &mut Frame, area: Rect, app: &App, selected: usize) {
+    let theme = app.theme();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Settings for the next game",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::raw(""),
+    ];
+    for (i, field) in SettingField::ALL.iter().enumerate() {
+        let text = format!("{:<20}< {} >", field.label(), field.value(app.settings()));
+        lines.push(menu_line(&text, i == selected, theme));
+    }
+    lines.push(Line::raw(""));
+    if app.in_progress() {
+        lines.push(Line::from(Span::styled(
+            "Changes apply from the next new game.",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        format!(
+            "[Up/Down] Choose   [Left/Right] Change   [Esc] Back   {}",
+            key_hint(app.keys().quit, "Quit")
+        ),
+        Style::default().fg(theme.accent),
+    )));
+
+    let block = Block::default().title("Settings").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect, app: &App) {
+    let (theme, keys) = (app.theme(), app.keys());
+    let table = [
+        vec![
+            key_hint(keys.hit, "Hit"),
+            key_hint(keys.stay, "Stay"),
+            key_hint(keys.double, "Double"),
+            key_hint(keys.split, "Split"),
+            key_hint(keys.new_round, "New round"),
+        ],
+        vec![
+            key_hint(keys.autobet, "Cycle autobet"),
+            key_hint(keys.hints, "Strategy hints"),
+            key_hint(keys.values, "Action values"),
+            key_hint(keys.panel, "Side panel"),
+        ],
+        vec![
+            key_hint(keys.stats, "Stats"),
+            key_hint(keys.statement, "Statement"),
+            key_hint(keys.menu, "Menu"),
+            key_hint(keys.quit, "Quit"),
+        ],
+    ]
+    .map(|row| row.join("   "));
+    let betting = "[Left/Right] Choose a chip   [+/-] Add it to or take it off the next bet";
+    let side_bets = [
+        key_hint(keys.perfect_pairs, "Perfect Pairs"),
+        key_hint(keys.twenty_one_plus_three, "21+3"),
+        key_hint(keys.lucky_ladies, "Lucky Ladies"),
+        "- a side bet of one chip, or take it down".to_string(),
+    ]
+    .join("   ");
+    let menus = format!(
+        "[Up/Down] or [{}/{}] Choose   [Enter] Select   [Esc] Back   {}",
+        keys.up,
+        keys.down,
+        key_hint(keys.quit, "Quit")
+    );
+    let baccarat = [
+        key_hint(keys.bet_player, "Bet Player"),
+        key_hint(keys.bet_banker, "Bet Banker"),
+        key_hint(keys.bet_tie, "Bet Tie"),
+        key_hint(keys.clear_bets, "Clear bets"),
+        key_hint(keys.new_round, "Deal"),
+    ]
+    .join("   ");
+    let sections: [(&str, Vec<String>); 4] = [
+        (
+            "Blackjack",
+            [
+                "Get closer to 21 than the dealer without going over.",
+                "Blackjack pays 3:2; the dealer peeks for blackjack.",
+                "Double takes one more card for twice the bet.",
+                "Split plays a pair as two hands.",
+            ]
+            .map(String::from)
+            .to_vec(),
+        ),
+        (
+            "At the table",
+            table
+                .into_iter()
+                .chain([betting.to_string(), side_bets])
+                .collect(),
+        ),
+        (
+            "Baccarat",
+            vec![
+                "Bet on the hand closer to 9, Player or Banker, or on a tie.".to_string(),
+                "Banker pays 19:20, a tie 8:1; Player and Banker push on a tie.".to_string(),
+                baccarat,
+            ],
+        ),
+        ("In menus", vec![menus]),
+    ];
+
+    let mut lines = Vec::new();
+    for (heading, rows) in sections {
+        lines.push(Line::from(Span::styled(
+            heading,
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(rows.iter().map(|row| Line::raw(format!("  {}", row))));
+        lines.push(Line::raw(""));
+    }
+    lines.push(Line::from(Span::styled(
+        format!("[Esc] Back   {}", key_hint(keys.quit, "Quit")),
+        Style::default().fg(theme.accent),
+    )));
+
+    let block = Block::default().title("Help").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The width of the side panel.
+const PANEL_WIDTH: u16 = 32;
+
+/// The narrowest screen that still shows the side panel beside the table.
+const MIN_WIDTH_WITH_PANEL: u16 = 72;
+
+fn draw_table(f: &mut Frame, app: &App) {
+    let view = &app.view();
+    let mut area = f.area();
+    if app.panel_shown() && area.width >= MIN_WIDTH_WITH_PANEL {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(40), Constraint::Length(PANEL_WIDTH)])
+            .split(area);
+        area = columns[0];
+        draw_panel(f, columns[1], app);
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -20,11 +290,16 @@ pub fn draw(f: &mut Frame, view: &BlackjackView) {
             Constraint::Min(7),    // player
             Constraint::Min(3),    // status / controls
         ])
-        .split(f.area());
+        .split(area);
 
-    draw_dealer(f, chunks[0], view);
-    draw_player(f, chunks[1], view);
-    draw_status(f, chunks[2], view);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(20), Constraint::Length(SIDE_BETS_WIDTH)])
+        .split(chunks[0]);
+    draw_dealer(f, top[0], view);
+    draw_side_bets(f, top[1], view, app);
+    draw_player(f, chunks[1], view, app.theme());
+    draw_status(f, chunks[2], view, app);
 }
 
 fn draw_dealer(f: &mut Frame, area: ratatui::layout::Rect, view: &BlackjackView) {
@@ -36,14 +311,55 @@ fn draw_dealer(f: &mut Frame, area: ratatui::layout::Rect, view: &BlackjackView)
         _ => "Score: ?".to_string(),
     };
 
-    let text = vec![Line::from("Dealer"), Line::from(cards), Line::from(score)];
+    let text = vec![Line::from("Dealer"), cards, Line::from(score)];
 
     let block = Block::default().title("Dealer").borders(Borders::ALL);
 
     f.render_widget(Paragraph::new(text).block(block), area);
 }
 
-fn draw_player(f: &mut Frame, area: Rect, view: &BlackjackView) {
+/// The width of the side bets beside the dealer.
+const SIDE_BETS_WIDTH: u16 = 44;
+
+/// Each side bet with its key, the stake for the next round and how it
+/// did on this round's deal.
+fn draw_side_bets(f: &mut Frame, area: Rect, view: &BlackjackView, app: &App) {
+    let (theme, keys) = (app.theme(), app.keys());
+    let lines = SideBetKind::ALL
+        .iter()
+        .map(|kind| {
+            let key = match kind {
+                SideBetKind::PerfectPairs => keys.perfect_pairs,
+                SideBetKind::TwentyOnePlusThree => keys.twenty_one_plus_three,
+                SideBetKind::LuckyLadies => keys.lucky_ladies,
+            };
+            let stake = match app.side_bets().iter().find(|(k, _)| k == kind) {
+                Some((_, amount)) => format!("${}", amount),
+                None => "-".to_string(),
+            };
+            let mut spans = vec![Span::raw(format!(
+                "{:<20}{:>5}  ",
+                key_hint(key, &kind.to_string()),
+                stake
+            ))];
+            if let Some(result) = view.side_bets.iter().find(|result| result.kind == *kind) {
+                spans.push(match result.outcome {
+                    Some(outcome) if result.payout > 0 => Span::styled(
+                        format!("{} +${}", outcome, result.payout - result.wager),
+                        Style::default().fg(theme.positive),
+                    ),
+                    _ => Span::styled("Lost", Style::default().fg(theme.muted)),
+                });
+            }
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
+
+    let block = Block::default().title("Side bets").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_player(f: &mut Frame, area: Rect, view: &BlackjackView, theme: &Theme) {
     let mut lines = Vec::new();
 
     lines.push(Line::from(Span::styled(
@@ -57,10 +373,10 @@ fn draw_player(f: &mut Frame, area: Rect, view: &BlackjackView) {
         let prefix = if is_active { "> " } else { "  " };
         let style = if is_active {
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::Gray)
+            Style::default().fg(theme.muted)
         };
 
         lines.push(Line::from(Span::styled(
@@ -86,7 +402,7 @@ fn draw_player(f: &mut Frame, area: Rect, view: &BlackjackView) {
     f.render_widget(Paragraph::new(lines).block(block), area);
 }
 
-fn draw_status(f: &mut Frame, area: Rect, view: &BlackjackView) {
+fn draw_status(f: &mut Frame, area: Rect, view: &BlackjackView, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -96,12 +412,13 @@ fn draw_status(f: &mut Frame, area: Rect, view: &BlackjackView) {
         ])
         .split(area);
 
-    draw_phase_and_result(f, chunks[0], view);
-    draw_bank(f, chunks[1], view);
-    draw_controls(f, chunks[2], view);
+    draw_phase_and_result(f, chunks[0], view, app);
+    draw_bank(f, chunks[1], view, app);
+    draw_controls(f, chunks[2], view, app);
 }
 
-fn draw_phase_and_result(f: &mut Frame, area: Rect, view: &BlackjackView) {
+fn draw_phase_and_result(f: &mut Frame, area: Rect, view: &BlackjackView, app: &App) {
+    let theme = app.theme();
     let mut lines = vec![Line::from(Span::styled(
         view.phase.to_string(),
         Style::default().add_modifier(Modifier::BOLD),
@@ -110,54 +427,141 @@ fn draw_phase_and_result(f: &mut Frame, area: Rect, view: &BlackjackView) {
     if view.result != GameResult::Pending {
         lines.push(Line::from(view.result.to_string()));
     }
+    if let Some(hint) = app.hint() {
+        lines.push(Line::from(Span::styled(
+            format!("Hint: {}", action_name(hint)),
+            Style::default().fg(theme.info),
+        )));
+    }
+    if let Some(values) = app.action_values().filter(|values| !values.is_empty()) {
+        let best = values
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(f64::MIN, f64::max);
+        let mut spans = vec![Span::styled("EV ", Style::default().fg(theme.muted))];
+        for (action, value) in values {
+            let style = match *value == best {
+                true => Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
+                false => Style::default().fg(theme.info),
+            };
+            spans.push(Span::styled(
+                format!("{} {:+.3}  ", action_name(*action), value),
+                style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    if let Some(message) = app.message() {
+        lines.push(Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(theme.negative),
+        )));
+    }
 
     let block = Block::default().borders(Borders::ALL).title("Game");
 
     f.render_widget(Paragraph::new(lines).block(block), area);
 }
 
-fn draw_bank(f: &mut Frame, area: Rect, view: &BlackjackView) {
+fn draw_bank(f: &mut Frame, area: Rect, view: &BlackjackView, app: &App) {
+    let theme = app.theme();
     let total_bet: u32 = view.player_hands.iter().map(|h| h.bet_amount).sum();
 
-    let line = Line::from(vec![
-        Span::styled("Balance: ", Style::default().fg(Color::Gray)),
+    let mut spans = vec![
+        Span::styled("Balance: ", Style::default().fg(theme.muted)),
         Span::styled(
             format!("${}", view.bank_balance),
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.positive)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("    "),
-        Span::styled("Bet: ", Style::default().fg(Color::Gray)),
+        Span::styled("Bet: ", Style::default().fg(theme.muted)),
         Span::styled(
             format!("${}", total_bet),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         ),
-    ]);
+    ];
+    if let Some(kind) = app.autobet() {
+        spans.push(Span::raw("    "));
+        spans.push(Span::styled("Autobet: ", Style::default().fg(theme.muted)));
+        spans.push(Span::styled(
+            kind.to_string(),
+            Style::default().fg(theme.info),
+        ));
+    }
 
-    let block = Block::default().borders(Borders::ALL).title("Bank");
+    let next_bet = app.next_bet();
+    let mut lines = vec![
+        Line::from(spans),
+        Line::from(vec![
+            Span::styled("Next bet: ", Style::default().fg(theme.muted)),
+            Span::raw(format!("${}  ", next_bet)),
+            Span::styled(
+                ChipStack::from_amount(next_bet).to_string(),
+                Style::default().fg(theme.highlight),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Chip: ", Style::default().fg(theme.muted)),
+            Span::styled(
+                format!("{} ({})", app.chip(), app.chip().color()),
+                Style::default().fg(theme.highlight),
+            ),
+        ]),
+    ];
+    if let Some(error) = app.bet_error() {
+        lines.push(Line::from(Span::styled(
+            error.to_string(),
+            Style::default().fg(theme.negative),
+        )));
+    }
 
-    f.render_widget(Paragraph::new(line).block(block), area);
+    let title = format!("Bank - odd payouts {}", view.payout_rule.rounding());
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
 }
 
-fn draw_controls(f: &mut Frame, area: Rect, view: &BlackjackView) {
+fn draw_controls(f: &mut Frame, area: Rect, view: &BlackjackView, app: &App) {
+    let (theme, keys) = (app.theme(), app.keys());
     let mut controls = view
         .available_actions
         .iter()
-        .map(|c| match c {
-            PlayerAction::Hit => "[H] Hit",
-            PlayerAction::Stay => "[S] Stay",
-            PlayerAction::Double => "[D] Double",
-            PlayerAction::Split => "[P] Split",
+        .map(|action| {
+            let key = match action {
+                PlayerAction::Hit => keys.hit,
+                PlayerAction::Stay => keys.stay,
+                PlayerAction::Double => keys.double,
+                PlayerAction::Split => keys.split,
+            };
+            key_hint(key, action_name(*action))
         })
         .collect::<Vec<_>>();
 
     if view.can_start_new_round {
-        controls.push("[N] New Round");
+        controls.push(key_hint(keys.new_round, "New Round"));
+        controls.push("[Left/Right] Chip   [+/-] Bet".to_string());
     }
-    controls.push("[Q] Quit");
+    controls.push(match app.autobet() {
+        Some(kind) => key_hint(keys.autobet, &format!("Autobet: {}", kind)),
+        None => key_hint(keys.autobet, "Autobet: off"),
+    });
+    controls.push(match app.hints_enabled() {
+        true => key_hint(keys.hints, "Hints: on"),
+        false => key_hint(keys.hints, "Hints: off"),
+    });
+    controls.push(match app.values_enabled() {
+        true => key_hint(keys.values, "Values: on"),
+        false => key_hint(keys.values, "Values: off"),
+    });
+    controls.push(key_hint(keys.panel, "Panel"));
+    controls.push(key_hint(keys.stats, "Stats"));
+    controls.push(key_hint(keys.statement, "Statement"));
+    controls.push(key_hint(keys.menu, "Menu"));
+    controls.push(key_hint(keys.quit, "Quit"));
 
     let controls = controls.join("   ");
 
@@ -165,12 +569,480 @@ fn draw_controls(f: &mut Frame, area: Rect, view: &BlackjackView) {
 
     f.render_widget(
         Paragraph::new(controls)
-            .style(Style::default().fg(Color::Cyan))
+            .style(Style::default().fg(theme.accent))
             .block(block),
         area,
     );
 }
 
+/// Bankroll history, round counters and how far into the shoe play is.
+fn draw_panel(f: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // bankroll
+            Constraint::Length(6), // counters
+            Constraint::Length(3), // penetration
+        ])
+        .split(area);
+    let history = app.history();
+
+    // One bar per round, as many as fit, measured from the lowest balance
+    // shown so that small swings stay visible.
+    let width = chunks[0].width.saturating_sub(2) as usize;
+    let balances: Vec<u32> = history.balances(width).collect();
+    let low = balances.iter().copied().min().unwrap_or(0);
+    let high = balances.iter().copied().max().unwrap_or(0);
+    let bars: Vec<u64> = balances.iter().map(|b| (b - low) as u64 + 1).collect();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Bankroll ${}-${}", low, high)),
+        )
+        .data(&bars)
+        .style(Style::default().fg(theme.positive));
+    f.render_widget(sparkline, chunks[0]);
+
+    let streak = match history.streak() {
+        0 => "-".to_string(),
+        n if n > 0 => format!("W{}", n),
+        n => format!("L{}", -n),
+    };
+    let lines = vec![
+        Line::from(format!("Rounds: {}", history.rounds())),
+        Line::from(format!(
+            "W/L/P: {}/{}/{}",
+            history.wins(),
+            history.losses(),
+            history.pushes()
+        )),
+        Line::from(format!("Win rate: {}", percent(history.win_rate()))),
+        Line::from(format!("Streak: {}", streak)),
+    ];
+    let block = Block::default().borders(Borders::ALL).title("Session");
+    f.render_widget(Paragraph::new(lines).block(block), chunks[1]);
+
+    let penetration = app.penetration().clamp(0.0, 1.0);
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title("Shoe"))
+        .gauge_style(Style::default().fg(theme.accent))
+        .ratio(penetration)
+        .label(format!("{:.0}% dealt", penetration * 100.0));
+    f.render_widget(gauge, chunks[2]);
+}
+
+fn draw_baccarat(f: &mut Frame, app: &App) {
+    let view = &app.baccarat_view();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5), // hands
+            Constraint::Length(7), // coup + bets
+            Constraint::Min(8),    // scoreboards
+            Constraint::Length(4), // controls
+        ])
+        .split(f.area());
+
+    let hands = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[0]);
+    draw_baccarat_hand(f, hands[0], "Player", &view.player_cards, view.player_total);
+    draw_baccarat_hand(f, hands[1], "Banker", &view.banker_cards, view.banker_total);
+
+    draw_coup(f, chunks[1], view, app);
+
+    let roads = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(chunks[2]);
+    draw_bead_plate(f, roads[0], &view.bead_plate, app.theme());
+    draw_big_road(f, roads[1], &view.big_road, app.theme());
+
+    let keys = app.keys();
+    let controls = [
+        key_hint(keys.bet_player, "Player"),
+        key_hint(keys.bet_banker, "Banker"),
+        key_hint(keys.bet_tie, "Tie"),
+        key_hint(keys.clear_bets, "Clear"),
+        key_hint(keys.new_round, "Deal"),
+        key_hint(keys.menu, "Menu"),
+        key_hint(keys.quit, "Quit"),
+    ]
+    .join("   ");
+    let block = Block::default().borders(Borders::ALL).title("Controls");
+    f.render_widget(
+        Paragraph::new(controls)
+            .style(Style::default().fg(app.theme().accent))
+            .block(block),
+        chunks[3],
+    );
+}
+
+fn draw_baccarat_hand(f: &mut Frame, area: Rect, title: &str, cards: &[Card], total: Option<u8>) {
+    let cards: Vec<VisibleCard> = cards.iter().copied().map(VisibleCard::FaceUp).collect();
+    let total = match total {
+        Some(total) => format!("Total: {}", total),
+        None => "Total: -".to_string(),
+    };
+    let lines = vec![Line::from(render_cards(&cards)), Line::from(total)];
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The last coup's result, the bets on the table and the bank.
+fn draw_coup(f: &mut Frame, area: Rect, view: &BaccaratView, app: &App) {
+    let theme = app.theme();
+    let result = match (view.phase, view.outcome) {
+        (BaccaratState::RoundOver, Some(outcome)) if view.natural => {
+            format!("{} with a natural", outcome)
+        }
+        (BaccaratState::RoundOver, Some(outcome)) => outcome.to_string(),
+        _ => "Place your bets".to_string(),
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        result,
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+
+    let staked = |kind: BetKind| {
+        view.wagers
+            .iter()
+            .find(|w| w.kind == kind)
+            .map_or(0, |w| w.amount)
+    };
+    lines.push(Line::from(format!(
+        "Player ${}   Banker ${}   Tie ${}",
+        staked(BetKind::Player),
+        staked(BetKind::Banker),
+        staked(BetKind::Tie)
+    )));
+    let mut bank = vec![
+        Span::styled("Balance: ", Style::default().fg(theme.muted)),
+        Span::styled(
+            format!("${}", view.bank_balance),
+            Style::default()
+                .fg(theme.positive)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("    "),
+        Span::styled("Chip: ", Style::default().fg(theme.muted)),
+        Span::styled(
+            format!("${}", app.baccarat_chip()),
+            Style::default().fg(theme.highlight),
+        ),
+    ];
+    if view.phase == BaccaratState::RoundOver {
+        bank.push(Span::raw("    "));
+        bank.push(Span::styled("Paid: ", Style::default().fg(theme.muted)));
+        bank.push(Span::raw(format!("${}", view.last_payout)));
+    }
+    lines.push(Line::from(bank));
+    lines.push(Line::from(Span::styled(
+        format!("{} cards left in the shoe", view.cards_remaining),
+        Style::default().fg(theme.muted),
+    )));
+    if let Some(error) = app.baccarat_error() {
+        lines.push(Line::from(Span::styled(
+            error.to_string(),
+            Style::default().fg(theme.negative),
+        )));
+    }
+
+    let block = Block::default().borders(Borders::ALL).title("Coup");
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// One outcome on a scoreboard: its letter in the colour of its side.
+fn road_mark(outcome: Outcome, theme: &Theme) -> Span<'static> {
+    let (letter, colour) = match outcome {
+        Outcome::PlayerWin => ("P", theme.info),
+        Outcome::BankerWin => ("B", theme.negative),
+        Outcome::Tie => ("T", theme.positive),
+    };
+    Span::styled(letter, Style::default().fg(colour))
+}
+
+/// The number of two-character columns that fit inside a bordered `area`.
+fn road_columns(area: Rect) -> usize {
+    (area.width.saturating_sub(2) / 2) as usize
+}
+
+fn draw_bead_plate(f: &mut Frame, area: Rect, plate: &[Vec<Outcome>], theme: &Theme) {
+    // The newest columns, as many as fit.
+    let shown = &plate[plate.len().saturating_sub(road_columns(area))..];
+    let lines = (0..ROAD_ROWS)
+        .map(|row| {
+            let spans = shown.iter().flat_map(|column| match column.get(row) {
+                Some(outcome) => [road_mark(*outcome, theme), Span::raw(" ")],
+                None => [Span::raw(" "), Span::raw(" ")],
+            });
+            Line::from(spans.collect::<Vec<_>>())
+        })
+        .collect::<Vec<_>>();
+    let block = Block::default().borders(Borders::ALL).title("Bead plate");
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The big road, with the number of ties after a result beside its mark.
+fn draw_big_road(f: &mut Frame, area: Rect, road: &BigRoad, theme: &Theme) {
+    let first = road.width().saturating_sub(road_columns(area));
+    let lines = (0..road.rows())
+        .map(|row| {
+            let spans = (first..road.width()).flat_map(|column| match road.get(column, row) {
+                Some(cell) => {
+                    let ties = match cell.ties {
+                        0 => Span::raw(" "),
+                        n => {
+                            Span::styled(n.min(9).to_string(), Style::default().fg(theme.positive))
+                        }
+                    };
+                    [road_mark(cell.outcome, theme), ties]
+                }
+                None => [Span::raw(" "), Span::raw(" ")],
+            });
+            Line::from(spans.collect::<Vec<_>>())
+        })
+        .collect::<Vec<_>>();
+    let title = match road.leading_ties() {
+        0 => "Big road".to_string(),
+        n => format!("Big road - {} opening ties", n),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// A key and what it does, as shown in the controls: `[H] Hit`.
+fn key_hint(key: Key, label: &str) -> String {
+    format!("[{}] {}", key, label)
+}
+
+fn action_name(action: PlayerAction) -> &'static str {
+    match action {
+        PlayerAction::Hit => "Hit",
+        PlayerAction::Stay => "Stay",
+        PlayerAction::Double => "Double",
+        PlayerAction::Split => "Split",
+    }
+}
+
+fn draw_profile_select(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    names: &[String],
+    selected: usize,
+    new_name: Option<&str>,
+) {
+    let (theme, keys) = (app.theme(), app.keys());
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Who is playing?",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::raw(""),
+    ];
+
+    let entries = names
+        .iter()
+        .map(String::as_str)
+        .chain(["+ New profile"])
+        .enumerate();
+    for (i, name) in entries {
+        lines.push(menu_line(name, i == selected && new_name.is_none(), theme));
+    }
+
+    lines.push(Line::raw(""));
+    match new_name {
+        Some(name) => {
+            lines.push(Line::from(vec![
+                Span::raw("Name: "),
+                Span::styled(
+                    format!("{}_", name),
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            lines.push(Line::from(Span::styled(
+                "[Enter] Create   [Esc] Cancel",
+                Style::default().fg(theme.accent),
+            )));
+        }
+        None => lines.push(Line::from(Span::styled(
+            format!(
+                "[Up/Down] Choose   [Enter] Select   {}   {}",
+                key_hint(keys.new_profile, "New profile"),
+                key_hint(keys.quit, "Quit")
+            ),
+            Style::default().fg(theme.accent),
+        ))),
+    }
+    if let Some(message) = app.message() {
+        lines.push(Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(theme.negative),
+        )));
+    }
+
+    let block = Block::default().title("Profiles").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// A label and how to show that figure from a set of stats.
+type StatRow = (&'static str, fn(&Stats) -> String);
+
+fn draw_stats(f: &mut Frame, area: Rect, app: &App) {
+    let Some(profile) = app.profile() else {
+        return;
+    };
+    let (session, lifetime) = (profile.session(), profile.lifetime());
+    let (theme, keys) = (app.theme(), app.keys());
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{:<20}{:>14}{:>14}", profile.name(), "Session", "Lifetime"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::raw(""),
+    ];
+    let rows: [StatRow; 11] = [
+        ("Hands", |s| s.hands.to_string()),
+        ("Won / lost / pushed", |s| {
+            format!("{}/{}/{}", s.wins, s.losses, s.pushes)
+        }),
+        ("Win rate", |s| percent(s.win_rate())),
+        ("Blackjacks", |s| s.blackjacks.to_string()),
+        ("Doubles won", |s| s.doubles_won.to_string()),
+        ("Splits won", |s| s.splits_won.to_string()),
+        ("Biggest win", |s| format!("${}", s.biggest_win)),
+        ("Net", |s| dollars(s.net)),
+        ("Time played", |s| {
+            let minutes = s.time_played.as_secs() / 60;
+            format!("{}h {:02}m", minutes / 60, minutes % 60)
+        }),
+        ("Hinted decisions", |s| s.decisions.to_string()),
+        ("Strategy accuracy", |s| percent(s.accuracy())),
+    ];
+    for (label, value) in rows {
+        lines.push(Line::from(format!(
+            "{:<20}{:>14}{:>14}",
+            label,
+            value(&session),
+            value(&lifetime)
+        )));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "[{}/Esc] Back   {}   {}",
+            keys.stats,
+            key_hint(keys.switch_profile, "Switch profile"),
+            key_hint(keys.quit, "Quit")
+        ),
+        Style::default().fg(theme.accent),
+    )));
+
+    let block = Block::default().title("Stats").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The ledger of the blackjack bank: the latest transactions that fit,
+/// newest last, over the totals for the game.
+fn draw_statement(f: &mut Frame, area: Rect, app: &App) {
+    let (theme, keys) = (app.theme(), app.keys());
+    let bank = app.bank();
+    let ledger = bank.ledger();
+
+    let mut summary = vec![
+        Line::raw(""),
+        Line::raw(format!(
+            "Wagered ${}   Insurance ${}   Paid ${}   Refunded ${}",
+            ledger.total(TransactionKind::Wager),
+            ledger.total(TransactionKind::Insurance),
+            ledger.total(TransactionKind::Payout),
+            ledger.total(TransactionKind::Refund),
+        )),
+        Line::raw(format!(
+            "Session net {}   Max drawdown ${}",
+            dollars(ledger.net_for_session(bank.session())),
+            ledger.max_drawdown()
+        )),
+        Line::raw(match ledger.biggest_win() {
+            Some((round, net)) => format!("Biggest win {} in round {}", dollars(net), round),
+            None => "Biggest win -".to_string(),
+        }),
+        Line::raw(""),
+        Line::from(Span::styled(
+            format!(
+                "[{}/Esc] Back   {}",
+                keys.statement,
+                key_hint(keys.quit, "Quit")
+            ),
+            Style::default().fg(theme.accent),
+        )),
+    ];
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{:<8}{:<12}{:>12}{:>12}", "Round", "", "Amount", "Balance"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::raw(format!(
+            "{:<8}{:<12}{:>12}{:>12}",
+            "",
+            "Opening",
+            "",
+            format!("${}", ledger.opening_balance())
+        )),
+    ];
+    // Two lines for the borders, two for the header.
+    let room = (area.height as usize).saturating_sub(summary.len() + 4);
+    let transactions = ledger.transactions();
+    for transaction in &transactions[transactions.len().saturating_sub(room)..] {
+        let color = match transaction.kind.is_credit() {
+            true => theme.positive,
+            false => theme.negative,
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "{:<8}{:<12}",
+                transaction.round,
+                transaction.kind.to_string()
+            )),
+            Span::styled(
+                format!("{:>12}", dollars(transaction.signed_amount())),
+                Style::default().fg(color),
+            ),
+            Span::raw(format!("{:>12}", format!("${}", transaction.balance))),
+        ]));
+    }
+    lines.append(&mut summary);
+
+    let block = Block::default().title("Statement").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// An amount of money with its sign: `$15` or `-$40`.
+fn dollars(amount: i64) -> String {
+    match amount {
+        amount if amount < 0 => format!("-${}", amount.unsigned_abs()),
+        amount => format!("${}", amount),
+    }
+}
+
+fn percent(share: Option<f64>) -> String {
+    match share {
+        Some(share) => format!("{:.1}%", share * 100.0),
+        None => "-".to_string(),
+    }
+}
+
 fn render_cards(cards: &[VisibleCard]) -> Vec<Span<'static>> {
     cards
         .iter()